@@ -0,0 +1,63 @@
+//! Compares the latency of calls made through one shared `BittrexClient` against
+//! creating a new client (and with it a new connection pool) for every call.
+//!
+//! Run with `cargo bench --bench connection_reuse`.
+extern crate mockito;
+extern crate bittrex_api;
+
+use std::time::{Duration, Instant};
+
+use mockito::mock;
+use bittrex_api::BittrexClient;
+
+const ITERATIONS: u32 = 200;
+
+fn main() {
+    let _mock = mock("GET", "/public/getmarkets")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : [{
+                    "MarketCurrency" : "LTC",
+                    "BaseCurrency" : "BTC",
+                    "MarketCurrencyLong" : "Litecoin",
+                    "BaseCurrencyLong" : "Bitcoin",
+                    "MinTradeSize" : 0.01000000,
+                    "MarketName" : "BTC-LTC",
+                    "IsActive" : true,
+                    "Created" : "2014-02-13T00:00:00"
+                }
+            ]
+        }"#)
+        .create();
+
+    let shared_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+    let shared = measure(|| {
+        shared_client.get_markets().unwrap();
+    });
+
+    let fresh = measure(|| {
+        let client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+        client.get_markets().unwrap();
+    });
+
+    println!("shared client:         {:>8.3} ms/call", as_millis(shared));
+    println!("new client per call:   {:>8.3} ms/call", as_millis(fresh));
+    println!("speedup:               {:>8.1}x", as_millis(fresh) / as_millis(shared));
+}
+
+fn measure<F: FnMut()>(mut call: F) -> Duration {
+    // Warm up, so the shared client has an open connection before measuring.
+    call();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        call();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1000.0 + f64::from(duration.subsec_nanos()) / 1_000_000.0
+}
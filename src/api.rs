@@ -0,0 +1,236 @@
+//! Traits over the endpoints, so code can be generic over the client it uses.
+//!
+//! `BittrexClient` implements `MarketData` and, with credentials, `Trading` and `Account`.
+//! `PaperTradingClient` implements `Trading` and `Account`, so a strategy written against these traits can be dry-run,
+//! and tests can pass their own implementations instead of a client.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bittrex_api::{BittrexClient, Decimal, Market, MarketData, PaperTradingClient, Trading};
+//! use bittrex_api::error::BittrexError;
+//!
+//! /// Buys one unit at the current ask.
+//! fn buy_at_ask<M: MarketData, T: Trading>(market_data: &M, trading: &T, market: &Market) -> Result<String, BittrexError> {
+//!     let ticker = market_data.get_ticker(market)?;
+//!     trading.buy_limit(market, Decimal::new(1, 0), ticker.ask).map(|uuid| uuid.uuid)
+//! }
+//!
+//! let market = "BTC-LTC".parse().unwrap();
+//! let market_data = BittrexClient::new_public();
+//! buy_at_ask(&market_data, &PaperTradingClient::new(market_data.clone()), &market).unwrap();
+//! buy_at_ask(&market_data, &BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string()), &market).unwrap();
+//! ```
+use client::{Authenticated, BittrexClient};
+use decimal::Decimal;
+use error::{BittrexError, BittrexErrorType};
+use market::{Currency, Market};
+use paper_trading::PaperTradingClient;
+use values::*;
+
+/// The public market data endpoints.
+pub trait MarketData {
+    fn get_markets(&self) -> Result<Vec<BittrexMarket>, BittrexError>;
+    fn get_currencies(&self) -> Result<Vec<BittrexCurrency>, BittrexError>;
+    fn get_ticker(&self, market: &Market) -> Result<BittrexTicker, BittrexError>;
+    fn get_market_summaries(&self) -> Result<Vec<BittrexMarketSummary>, BittrexError>;
+    fn get_market_summary(&self, market: &Market) -> Result<BittrexMarketSummary, BittrexError>;
+    fn get_order_book(&self, market: &Market, book_type: BittrexOrderType) -> Result<BittrexPublicOrderBook, BittrexError>;
+    fn get_market_history(&self, market: &Market) -> Result<Vec<BittrexTrade>, BittrexError>;
+}
+
+/// Placing and cancelling orders and querying them.
+pub trait Trading {
+    fn buy_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid, BittrexError>;
+    fn sell_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid, BittrexError>;
+    fn cancel_order(&self, order_id: &str) -> Result<(), BittrexError>;
+    fn get_open_orders(&self) -> Result<Vec<BittrexOpenOrder>, BittrexError>;
+    fn get_open_orders_by_market(&self, market: &Market) -> Result<Vec<BittrexOpenOrder>, BittrexError>;
+    fn get_order(&self, order_id: &str) -> Result<BittrexOrder, BittrexError>;
+    fn get_order_history(&self) -> Result<Vec<BittrexHistoryOrder>, BittrexError>;
+    fn get_order_history_by_market(&self, market: &Market) -> Result<Vec<BittrexHistoryOrder>, BittrexError>;
+}
+
+/// Balances, deposits and withdrawals.
+pub trait Account {
+    fn get_balances(&self) -> Result<Vec<BittrexBalance>, BittrexError>;
+    fn get_balance(&self, currency: &Currency) -> Result<BittrexBalance, BittrexError>;
+    fn get_deposit_address(&self, currency: &Currency) -> Result<BittrexAddress, BittrexError>;
+    fn get_deposit_history(&self) -> Result<Vec<BittrexTransaction>, BittrexError>;
+    fn get_deposit_history_by_currency(&self, currency: &Currency) -> Result<Vec<BittrexTransaction>, BittrexError>;
+    fn get_withdrawal_history(&self) -> Result<Vec<BittrexTransaction>, BittrexError>;
+    fn get_withdrawal_history_by_currency(&self, currency: &Currency) -> Result<Vec<BittrexTransaction>, BittrexError>;
+    fn withdraw(&self, currency: &Currency, quantity: Decimal, address: &str, payment_id: &str) -> Result<BittrexUuid, BittrexError>;
+}
+
+impl<A> MarketData for BittrexClient<A> {
+    fn get_markets(&self) -> Result<Vec<BittrexMarket>, BittrexError> {
+        BittrexClient::get_markets(self)
+    }
+
+    fn get_currencies(&self) -> Result<Vec<BittrexCurrency>, BittrexError> {
+        BittrexClient::get_currencies(self)
+    }
+
+    fn get_ticker(&self, market: &Market) -> Result<BittrexTicker, BittrexError> {
+        BittrexClient::get_ticker(self, market)
+    }
+
+    fn get_market_summaries(&self) -> Result<Vec<BittrexMarketSummary>, BittrexError> {
+        BittrexClient::get_market_summaries(self)
+    }
+
+    fn get_market_summary(&self, market: &Market) -> Result<BittrexMarketSummary, BittrexError> {
+        BittrexClient::get_market_summary(self, market)
+    }
+
+    fn get_order_book(&self, market: &Market, book_type: BittrexOrderType) -> Result<BittrexPublicOrderBook, BittrexError> {
+        BittrexClient::get_order_book(self, market, book_type)
+    }
+
+    fn get_market_history(&self, market: &Market) -> Result<Vec<BittrexTrade>, BittrexError> {
+        BittrexClient::get_market_history(self, market)
+    }
+}
+
+impl Trading for BittrexClient<Authenticated> {
+    fn buy_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid, BittrexError> {
+        BittrexClient::buy_limit(self, market, quantity, rate)
+    }
+
+    fn sell_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid, BittrexError> {
+        BittrexClient::sell_limit(self, market, quantity, rate)
+    }
+
+    fn cancel_order(&self, order_id: &str) -> Result<(), BittrexError> {
+        BittrexClient::cancel_order(self, order_id)
+    }
+
+    fn get_open_orders(&self) -> Result<Vec<BittrexOpenOrder>, BittrexError> {
+        BittrexClient::get_open_orders(self)
+    }
+
+    fn get_open_orders_by_market(&self, market: &Market) -> Result<Vec<BittrexOpenOrder>, BittrexError> {
+        BittrexClient::get_open_orders_by_market(self, market)
+    }
+
+    fn get_order(&self, order_id: &str) -> Result<BittrexOrder, BittrexError> {
+        BittrexClient::get_order(self, order_id)
+    }
+
+    fn get_order_history(&self) -> Result<Vec<BittrexHistoryOrder>, BittrexError> {
+        BittrexClient::get_order_history(self)
+    }
+
+    fn get_order_history_by_market(&self, market: &Market) -> Result<Vec<BittrexHistoryOrder>, BittrexError> {
+        BittrexClient::get_order_history_by_market(self, market)
+    }
+}
+
+impl Account for BittrexClient<Authenticated> {
+    fn get_balances(&self) -> Result<Vec<BittrexBalance>, BittrexError> {
+        BittrexClient::get_balances(self)
+    }
+
+    fn get_balance(&self, currency: &Currency) -> Result<BittrexBalance, BittrexError> {
+        BittrexClient::get_balance(self, currency)
+    }
+
+    fn get_deposit_address(&self, currency: &Currency) -> Result<BittrexAddress, BittrexError> {
+        BittrexClient::get_deposit_address(self, currency)
+    }
+
+    fn get_deposit_history(&self) -> Result<Vec<BittrexTransaction>, BittrexError> {
+        BittrexClient::get_deposit_history(self)
+    }
+
+    fn get_deposit_history_by_currency(&self, currency: &Currency) -> Result<Vec<BittrexTransaction>, BittrexError> {
+        BittrexClient::get_deposit_history_by_currency(self, currency)
+    }
+
+    fn get_withdrawal_history(&self) -> Result<Vec<BittrexTransaction>, BittrexError> {
+        BittrexClient::get_withdrawal_history(self)
+    }
+
+    fn get_withdrawal_history_by_currency(&self, currency: &Currency) -> Result<Vec<BittrexTransaction>, BittrexError> {
+        BittrexClient::get_withdrawal_history_by_currency(self, currency)
+    }
+
+    fn withdraw(&self, currency: &Currency, quantity: Decimal, address: &str, payment_id: &str) -> Result<BittrexUuid, BittrexError> {
+        BittrexClient::withdraw(self, currency, quantity, address, payment_id)
+    }
+}
+
+impl<A> Trading for PaperTradingClient<A> {
+    fn buy_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid, BittrexError> {
+        PaperTradingClient::buy_limit(self, market, quantity, rate)
+    }
+
+    fn sell_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid, BittrexError> {
+        PaperTradingClient::sell_limit(self, market, quantity, rate)
+    }
+
+    fn cancel_order(&self, order_id: &str) -> Result<(), BittrexError> {
+        PaperTradingClient::cancel_order(self, order_id)
+    }
+
+    fn get_open_orders(&self) -> Result<Vec<BittrexOpenOrder>, BittrexError> {
+        PaperTradingClient::get_open_orders(self)
+    }
+
+    fn get_open_orders_by_market(&self, market: &Market) -> Result<Vec<BittrexOpenOrder>, BittrexError> {
+        PaperTradingClient::get_open_orders_by_market(self, market)
+    }
+
+    fn get_order(&self, order_id: &str) -> Result<BittrexOrder, BittrexError> {
+        PaperTradingClient::get_order(self, order_id)
+    }
+
+    fn get_order_history(&self) -> Result<Vec<BittrexHistoryOrder>, BittrexError> {
+        PaperTradingClient::get_order_history(self)
+    }
+
+    fn get_order_history_by_market(&self, market: &Market) -> Result<Vec<BittrexHistoryOrder>, BittrexError> {
+        PaperTradingClient::get_order_history_by_market(self, market)
+    }
+}
+
+/// Balances come from the simulated account. Deposits and withdrawals are not simulated: their histories are empty
+/// and deposit addresses and withdrawals fail with a `Configuration` error.
+impl<A> Account for PaperTradingClient<A> {
+    fn get_balances(&self) -> Result<Vec<BittrexBalance>, BittrexError> {
+        PaperTradingClient::get_balances(self)
+    }
+
+    fn get_balance(&self, currency: &Currency) -> Result<BittrexBalance, BittrexError> {
+        PaperTradingClient::get_balance(self, currency)
+    }
+
+    fn get_deposit_address(&self, _currency: &Currency) -> Result<BittrexAddress, BittrexError> {
+        Err(not_simulated("Deposit addresses"))
+    }
+
+    fn get_deposit_history(&self) -> Result<Vec<BittrexTransaction>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn get_deposit_history_by_currency(&self, _currency: &Currency) -> Result<Vec<BittrexTransaction>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn get_withdrawal_history(&self) -> Result<Vec<BittrexTransaction>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn get_withdrawal_history_by_currency(&self, _currency: &Currency) -> Result<Vec<BittrexTransaction>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn withdraw(&self, _currency: &Currency, _quantity: Decimal, _address: &str, _payment_id: &str) -> Result<BittrexUuid, BittrexError> {
+        Err(not_simulated("Withdrawals"))
+    }
+}
+
+fn not_simulated(what: &str) -> BittrexError {
+    BittrexError::new(BittrexErrorType::Configuration, format!("{} are not simulated by the paper trading client", what))
+}
@@ -0,0 +1,286 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use futures::{future, Future, IntoFuture};
+use futures::future::Loop;
+use serde;
+use serde::de::IgnoredAny;
+use reqwest::Error as ReqwestError;
+use reqwest::async::{Client, RequestBuilder, Response};
+use tokio_timer::Delay;
+
+use client::{Authenticated, BittrexClientBuilder, Public, decode_response, check_return_single_response, check_return_vec_response, check_return_single_vec_response, check_return_empty_response};
+use endpoint;
+use error::{ BittrexError, BittrexErrorType };
+use decimal::Decimal;
+use market::{Currency, Market};
+use rate_limit::{EndpointClass, RateLimiter};
+use retry::{self, RetryPolicy};
+use values::*;
+
+/// Future returned by the calls of an `AsyncBittrexClient`.
+pub type BittrexFuture<T> = Box<dyn Future<Item = T, Error = BittrexError> + Send>;
+
+/// Non-blocking client for the Bittrex API.
+///
+/// Provides the same endpoints as `BittrexClient`, but returns futures instead of blocking.
+/// They have to be run on a tokio runtime.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// extern crate futures;
+/// extern crate bittrex_api;
+///
+/// use futures::Future;
+/// use bittrex_api::BittrexClient;
+///
+/// # fn main() {
+/// let bittrex_client = BittrexClient::builder()
+///     .credentials("APIKEY".to_string(), "APISECRET".to_string())
+///     .build_async()
+///     .unwrap();
+/// let balances = bittrex_client.get_balances()
+///     .map(|balances| println!("{} balances", balances.len()));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct AsyncBittrexClient<A = Authenticated> {
+    api_url: String,
+    v2_api_url: String,
+    auth: A,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
+    http_client: Client,
+}
+
+impl AsyncBittrexClient<Public> {
+    /// Creates a client without credentials, which only provides the public endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized. Use `BittrexClientBuilder::build_async` to handle this case.
+    pub fn new_public() -> Self {
+        BittrexClientBuilder::new().build_async().expect("HTTP client should be initialized!")
+    }
+}
+
+impl AsyncBittrexClient<Authenticated> {
+    /// Creates a client with the default settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized. Use `BittrexClientBuilder::build_async` to handle this case.
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        BittrexClientBuilder::new().credentials(api_key, api_secret).build_async().expect("HTTP client should be initialized!")
+    }
+}
+
+impl<A> AsyncBittrexClient<A> {
+    pub(crate) fn from_parts(api_url: String, v2_api_url: String, auth: A, retry_policy: RetryPolicy, rate_limiter: RateLimiter, http_client: Client) -> Self {
+        AsyncBittrexClient { api_url: api_url, v2_api_url: v2_api_url, auth: auth, retry_policy: retry_policy, rate_limiter: rate_limiter, http_client: http_client }
+    }
+
+    /// Async variant of `BittrexClient::get_markets`.
+    pub fn get_markets(&self) -> BittrexFuture<Vec<BittrexMarket>> {
+        Box::new(self.call_public_api::<BittrexAPIVecResult<BittrexMarket>>(endpoint::get_markets(&self.api_url)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_currencies`.
+    pub fn get_currencies(&self) -> BittrexFuture<Vec<BittrexCurrency>> {
+        Box::new(self.call_public_api::<BittrexAPIVecResult<BittrexCurrency>>(endpoint::get_currencies(&self.api_url)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_ticker`.
+    pub fn get_ticker(&self, market: &Market) -> BittrexFuture<BittrexTicker> {
+        Box::new(self.call_public_api::<BittrexAPIResult<BittrexTicker>>(endpoint::get_ticker(&self.api_url, market)).and_then(check_return_single_response))
+    }
+
+    /// Async variant of `BittrexClient::get_market_summaries`.
+    pub fn get_market_summaries(&self) -> BittrexFuture<Vec<BittrexMarketSummary>> {
+        Box::new(self.call_public_api::<BittrexAPIVecResult<BittrexMarketSummary>>(endpoint::get_market_summaries(&self.api_url)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_market_summary`.
+    pub fn get_market_summary(&self, market: &Market) -> BittrexFuture<BittrexMarketSummary> {
+        Box::new(self.call_public_api::<BittrexAPIVecResult<BittrexMarketSummary>>(endpoint::get_market_summary(&self.api_url, market)).and_then(check_return_single_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_order_book`.
+    pub fn get_order_book(&self, market: &Market, book_type: BittrexOrderType) -> BittrexFuture<BittrexPublicOrderBook> {
+        Box::new(self.call_public_api::<BittrexAPIResult<BittrexPublicOrderBook>>(endpoint::get_order_book(&self.api_url, market, book_type)).and_then(check_return_single_response))
+    }
+
+    /// Async variant of `BittrexClient::get_market_history`.
+    pub fn get_market_history(&self, market: &Market) -> BittrexFuture<Vec<BittrexTrade>> {
+        Box::new(self.call_public_api::<BittrexAPIVecResult<BittrexTrade>>(endpoint::get_market_history(&self.api_url, market)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_ticks`.
+    pub fn get_ticks(&self, market: &Market, tick_interval: TickInterval) -> BittrexFuture<Vec<BittrexCandle>> {
+        Box::new(self.call_public_api::<BittrexAPIVecResult<BittrexCandle>>(endpoint::get_ticks(&self.v2_api_url, market, tick_interval)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_latest_tick`.
+    pub fn get_latest_tick(&self, market: &Market, tick_interval: TickInterval) -> BittrexFuture<BittrexCandle> {
+        Box::new(self.call_public_api::<BittrexAPIVecResult<BittrexCandle>>(endpoint::get_latest_tick(&self.v2_api_url, market, tick_interval)).and_then(check_return_single_vec_response))
+    }
+
+    fn call_public_api<T>(&self, request: endpoint::Request) -> BittrexFuture<T> where for<'de> T: serde::Deserialize<'de> + Send + 'static {
+        let http_client = self.http_client.clone();
+        let response = self.send_with_retries(EndpointClass::Public, self.retry_policy.retries_for(false), move || Ok(http_client.get(&request.url())));
+
+        Box::new(response.and_then(read_response))
+    }
+
+    /// Sends the request built by `request`, building and sending it again for each retry,
+    /// so private calls get a fresh nonce and signature. Failures before a response was received
+    /// and responses with a transient HTTP status are retried. Every attempt is subject to the rate limit of `class`.
+    fn send_with_retries<F>(&self, class: EndpointClass, max_retries: u32, request: F) -> BittrexFuture<Response> where F: Fn() -> Result<RequestBuilder, BittrexError> + Send + Sync + 'static {
+        let retry_policy = self.retry_policy.clone();
+        let rate_limiter = self.rate_limiter.clone();
+        let request = Arc::new(request);
+
+        Box::new(future::loop_fn(0, move |retries| {
+            let retry_policy = retry_policy.clone();
+            let request = request.clone();
+            rate_limiter.acquire(class).into_future()
+                .and_then(wait)
+                .and_then(move |_| request())
+                .and_then(|request| request.send().then(Ok))
+                .and_then(move |result: ::std::result::Result<Response, ReqwestError>| -> BittrexFuture<Loop<Response, u32>> {
+                    let result = result.map_err(BittrexError::from);
+                    let transient = match result {
+                        Ok(ref resp) => retry::is_transient_status(resp.status()),
+                        Err(ref error) => error.error_type == BittrexErrorType::Transport,
+                    };
+                    if transient && retries < max_retries {
+                        return Box::new(wait(retry_policy.delay(retries)).map(move |_| Loop::Continue(retries + 1)));
+                    }
+                    Box::new(result.map(Loop::Break).into_future())
+                })
+        }))
+    }
+}
+
+impl AsyncBittrexClient<Authenticated> {
+    /// Async variant of `BittrexClient::get_open_orders`.
+    pub fn get_open_orders(&self) -> BittrexFuture<Vec<BittrexOpenOrder>> {
+        Box::new(self.call_private_api::<BittrexAPIVecResult<BittrexOpenOrder>>(endpoint::get_open_orders(&self.api_url)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_open_orders_by_market`.
+    pub fn get_open_orders_by_market(&self, market: &Market) -> BittrexFuture<Vec<BittrexOpenOrder>> {
+        Box::new(self.call_private_api::<BittrexAPIVecResult<BittrexOpenOrder>>(endpoint::get_open_orders_by_market(&self.api_url, market)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_order`.
+    pub fn get_order(&self, order_id: &str) -> BittrexFuture<BittrexOrder> {
+        Box::new(self.call_private_api::<BittrexAPIResult<BittrexOrder>>(endpoint::get_order(&self.api_url, order_id)).and_then(check_return_single_response))
+    }
+
+    /// Async variant of `BittrexClient::get_order_history`.
+    pub fn get_order_history(&self) -> BittrexFuture<Vec<BittrexHistoryOrder>> {
+        Box::new(self.call_private_api::<BittrexAPIVecResult<BittrexHistoryOrder>>(endpoint::get_order_history(&self.api_url)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_order_history_by_market`.
+    pub fn get_order_history_by_market(&self, market: &Market) -> BittrexFuture<Vec<BittrexHistoryOrder>> {
+        Box::new(self.call_private_api::<BittrexAPIVecResult<BittrexHistoryOrder>>(endpoint::get_order_history_by_market(&self.api_url, market)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_withdrawal_history`.
+    pub fn get_withdrawal_history(&self) -> BittrexFuture<Vec<BittrexTransaction>> {
+        Box::new(self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(endpoint::get_withdrawal_history(&self.api_url)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_withdrawal_history_by_currency`.
+    pub fn get_withdrawal_history_by_currency(&self, currency: &Currency) -> BittrexFuture<Vec<BittrexTransaction>> {
+        Box::new(self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(endpoint::get_withdrawal_history_by_currency(&self.api_url, currency)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_deposit_history`.
+    pub fn get_deposit_history(&self) -> BittrexFuture<Vec<BittrexTransaction>> {
+        Box::new(self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(endpoint::get_deposit_history(&self.api_url)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_deposit_history_by_currency`.
+    pub fn get_deposit_history_by_currency(&self, currency: &Currency) -> BittrexFuture<Vec<BittrexTransaction>> {
+        Box::new(self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(endpoint::get_deposit_history_by_currency(&self.api_url, currency)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_balances`.
+    pub fn get_balances(&self) -> BittrexFuture<Vec<BittrexBalance>> {
+        Box::new(self.call_private_api::<BittrexAPIVecResult<BittrexBalance>>(endpoint::get_balances(&self.api_url)).and_then(check_return_vec_response))
+    }
+
+    /// Async variant of `BittrexClient::get_balance`.
+    pub fn get_balance(&self, currency: &Currency) -> BittrexFuture<BittrexBalance> {
+        Box::new(self.call_private_api::<BittrexAPIResult<BittrexBalance>>(endpoint::get_balance(&self.api_url, currency)).and_then(check_return_single_response))
+    }
+
+    /// Async variant of `BittrexClient::get_deposit_address`.
+    pub fn get_deposit_address(&self, currency: &Currency) -> BittrexFuture<BittrexAddress> {
+        Box::new(self.call_private_api::<BittrexAPIResult<BittrexAddress>>(endpoint::get_deposit_address(&self.api_url, currency)).and_then(check_return_single_response))
+    }
+
+    /// Async variant of `BittrexClient::withdraw`.
+    pub fn withdraw(&self, currency: &Currency, quantity: Decimal, address: &str, payment_id: &str) -> BittrexFuture<BittrexUuid> {
+        Box::new(self.call_trading_api::<BittrexAPIResult<BittrexUuid>>(endpoint::withdraw(&self.api_url, currency, quantity, address, payment_id)).and_then(check_return_single_response))
+    }
+
+    /// Async variant of `BittrexClient::buy_limit`.
+    pub fn buy_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> BittrexFuture<BittrexUuid> {
+        Box::new(self.call_trading_api::<BittrexAPIResult<BittrexUuid>>(endpoint::buy_limit(&self.api_url, market, quantity, rate)).and_then(check_return_single_response))
+    }
+
+    /// Async variant of `BittrexClient::sell_limit`.
+    pub fn sell_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> BittrexFuture<BittrexUuid> {
+        Box::new(self.call_trading_api::<BittrexAPIResult<BittrexUuid>>(endpoint::sell_limit(&self.api_url, market, quantity, rate)).and_then(check_return_single_response))
+    }
+
+    /// Async variant of `BittrexClient::cancel_order`.
+    pub fn cancel_order(&self, order_id: &str) -> BittrexFuture<()> {
+        Box::new(self.call_trading_api::<BittrexAPIResult<IgnoredAny>>(endpoint::cancel_order(&self.api_url, order_id)).and_then(check_return_empty_response))
+    }
+
+    /// Calls an idempotent private endpoint, which is retried according to the retry policy.
+    fn call_private_api<T>(&self, request: endpoint::Request) -> BittrexFuture<T> where for<'de> T: serde::Deserialize<'de> + Send + 'static {
+        let max_retries = self.retry_policy.retries_for(false);
+        self.call_signed_api(max_retries, request)
+    }
+
+    /// Calls a private endpoint which changes state (orders, withdrawals) and is only retried if the retry policy opts in.
+    fn call_trading_api<T>(&self, request: endpoint::Request) -> BittrexFuture<T> where for<'de> T: serde::Deserialize<'de> + Send + 'static {
+        let max_retries = self.retry_policy.retries_for(true);
+        self.call_signed_api(max_retries, request)
+    }
+
+    fn call_signed_api<T>(&self, max_retries: u32, request: endpoint::Request) -> BittrexFuture<T> where for<'de> T: serde::Deserialize<'de> + Send + 'static {
+        let http_client = self.http_client.clone();
+        let auth = self.auth.clone();
+        let response = self.send_with_retries(EndpointClass::Private, max_retries, move || {
+            let (signed_url, signature) = auth.sign_url(&request)?;
+            Ok(http_client.get(&signed_url).header("apisign", signature))
+        });
+
+        Box::new(response.and_then(read_response))
+    }
+}
+
+fn read_response<T>(mut resp: Response) -> BittrexFuture<T> where for<'de> T: serde::Deserialize<'de> + Send + 'static {
+    let status = resp.status();
+    let url = resp.url().to_string();
+
+    let read_url = url.clone();
+    Box::new(resp.text()
+        .map_err(move |error| BittrexError::from(error).with_status(status.as_u16()).with_url(&read_url))
+        .and_then(move |body| decode_response(status, &url, &body)))
+}
+
+fn wait(delay: Duration) -> BittrexFuture<()> {
+    if delay == Duration::from_secs(0) {
+        return Box::new(future::ok(()));
+    }
+    Box::new(Delay::new(Instant::now() + delay)
+        .map_err(|error| BittrexError::new(BittrexErrorType::Transport, format!("Timer error: {}", error))))
+}
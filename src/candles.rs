@@ -0,0 +1,269 @@
+//! Candles aggregated locally from trades.
+//!
+//! A `CandleAggregator` turns the trades of a market, e.g. from polling `get_market_history`, into candles for
+//! intervals the API does not offer (`BarType::Time`), or into bars of a fixed number of trades (`BarType::Trades`)
+//! or a minimum volume (`BarType::Volume`).
+//!
+//! Trades which were already seen (by `BittrexTrade::id`) are ignored, so overlapping windows of the market history
+//! can be pushed as they are. Trades may arrive out of order: they are buffered for the allowed lateness and only
+//! aggregated once no earlier trade is expected anymore, in the order of their timestamps and ids. Trades arriving
+//! after that are dropped and counted in `late_trades`.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use bittrex_api::BittrexClient;
+//! use bittrex_api::candles::{BarType, CandleAggregator};
+//!
+//! let bittrex_client = BittrexClient::new_public();
+//! let market = "BTC-LTC".parse().unwrap();
+//! let mut aggregator = CandleAggregator::new(BarType::Time(Duration::from_secs(3 * 60)))
+//!     .lateness(Duration::from_secs(10));
+//!
+//! loop {
+//!     for candle in aggregator.push_all(bittrex_client.get_market_history(&market).unwrap()) {
+//!         println!("{} O {} H {} L {} C {} V {}", candle.time_stamp, candle.open, candle.high, candle.low, candle.close, candle.volume);
+//!     }
+//!     std::thread::sleep(Duration::from_secs(30));
+//! }
+//! ```
+use std::collections::{BTreeMap, BTreeSet};
+use std::mem;
+use std::time::Duration;
+
+use chrono::{self, DateTime, Utc};
+
+use decimal::Decimal;
+use values::{BittrexCandle, BittrexTrade};
+
+/// Number of trade ids remembered to recognize duplicates.
+const MAX_REMEMBERED_IDS: usize = 10_000;
+
+/// When a bar is complete.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BarType {
+    /// Candles of a fixed interval, aligned to the UNIX epoch (4 hour candles start at 0:00, 4:00, ... UTC).
+    /// Intervals without trades produce no candle.
+    Time(Duration),
+    /// Bars of the given number of trades.
+    Trades(u32),
+    /// Bars which are complete as soon as their volume reaches the given quantity. Trades are not split,
+    /// so the volume of a bar may exceed it.
+    Volume(Decimal),
+}
+
+/// Aggregates trades into candles. See the module documentation.
+pub struct CandleAggregator {
+    bar_type: BarType,
+    lateness: chrono::Duration,
+    newest: Option<DateTime<Utc>>,
+    pending: BTreeMap<(DateTime<Utc>, u32), BittrexTrade>,
+    seen_ids: BTreeSet<u32>,
+    current: Option<Bar>,
+    late_trades: u64,
+    duplicate_trades: u64,
+}
+
+/// The bar which is being aggregated.
+#[derive(Debug)]
+struct Bar {
+    candle: BittrexCandle,
+    trades: u32,
+    /// Number of the interval since the UNIX epoch, for time bars.
+    interval: i64,
+}
+
+impl CandleAggregator {
+    /// # Panics
+    ///
+    /// Panics if the interval, number of trades or volume of `bar_type` is zero.
+    pub fn new(bar_type: BarType) -> Self {
+        match bar_type {
+            BarType::Time(interval) => assert!(millis(interval) > 0, "Interval must be at least one millisecond"),
+            BarType::Trades(trades) => assert!(trades > 0, "Bars need at least one trade"),
+            BarType::Volume(volume) => assert!(volume.is_positive(), "Volume must be positive"),
+        }
+
+        CandleAggregator {
+            bar_type: bar_type,
+            lateness: chrono::Duration::zero(),
+            newest: None,
+            pending: BTreeMap::new(),
+            seen_ids: BTreeSet::new(),
+            current: None,
+            late_trades: 0,
+            duplicate_trades: 0,
+        }
+    }
+
+    /// How long a trade may arrive after a newer trade (default: zero). Bars are completed this much later.
+    pub fn lateness(mut self, lateness: Duration) -> Self {
+        self.lateness = chrono::Duration::milliseconds(millis(lateness));
+        self
+    }
+
+    /// Adds a trade and returns the bars it completed.
+    pub fn push(&mut self, trade: BittrexTrade) -> Vec<BittrexCandle> {
+        if !self.seen_ids.insert(trade.id) {
+            self.duplicate_trades += 1;
+            return Vec::new();
+        }
+        if self.seen_ids.len() > MAX_REMEMBERED_IDS {
+            let oldest = *self.seen_ids.iter().next().expect("Ids should not be empty");
+            self.seen_ids.remove(&oldest);
+        }
+
+        if self.watermark().map(|watermark| trade.time_stamp < watermark).unwrap_or(false) {
+            self.late_trades += 1;
+            return Vec::new();
+        }
+
+        if self.newest.map(|newest| trade.time_stamp > newest).unwrap_or(true) {
+            self.newest = Some(trade.time_stamp);
+        }
+        self.pending.insert((trade.time_stamp, trade.id), trade);
+        self.release()
+    }
+
+    /// Adds a batch of trades in any order, e.g. the result of `get_market_history` (which is newest first),
+    /// and returns the bars they completed. The batch is sorted first, so none of its trades is late for another.
+    pub fn push_all<I>(&mut self, trades: I) -> Vec<BittrexCandle> where I: IntoIterator<Item = BittrexTrade> {
+        let mut trades: Vec<BittrexTrade> = trades.into_iter().collect();
+        trades.sort_by_key(|trade| (trade.time_stamp, trade.id));
+
+        let mut candles = Vec::new();
+        for trade in trades {
+            candles.extend(self.push(trade));
+        }
+        candles
+    }
+
+    /// Aggregates all buffered trades and returns the remaining bars including the incomplete last one.
+    pub fn flush(&mut self) -> Vec<BittrexCandle> {
+        let mut candles = Vec::new();
+        let pending = mem::take(&mut self.pending);
+        for (_, trade) in pending {
+            candles.extend(self.add(&trade));
+        }
+        candles.extend(self.current.take().map(|bar| bar.candle));
+        candles
+    }
+
+    /// Number of trades which were dropped because they arrived too late.
+    pub fn late_trades(&self) -> u64 {
+        self.late_trades
+    }
+
+    /// Number of trades which were ignored because they were already seen.
+    pub fn duplicate_trades(&self) -> u64 {
+        self.duplicate_trades
+    }
+
+    /// Trades older than the watermark are not expected anymore.
+    fn watermark(&self) -> Option<DateTime<Utc>> {
+        self.newest.map(|newest| newest - self.lateness)
+    }
+
+    /// Aggregates the buffered trades older than the watermark and completes a time bar which ends before it.
+    fn release(&mut self) -> Vec<BittrexCandle> {
+        let watermark = match self.watermark() {
+            Some(watermark) => watermark,
+            None => return Vec::new(),
+        };
+
+        let mut candles = Vec::new();
+        while self.pending.keys().next().map(|&(time_stamp, _)| time_stamp < watermark).unwrap_or(false) {
+            let key = *self.pending.keys().next().expect("Pending trades should not be empty");
+            let trade = self.pending.remove(&key).expect("Pending trade should exist");
+            candles.extend(self.add(&trade));
+        }
+
+        if let BarType::Time(interval) = self.bar_type {
+            let ended = self.current.as_ref()
+                .map(|bar| (bar.interval + 1) * millis(interval) <= watermark.timestamp_millis())
+                .unwrap_or(false);
+            if ended {
+                candles.extend(self.current.take().map(|bar| bar.candle));
+            }
+        }
+        candles
+    }
+
+    /// Adds a trade to the current bar and returns the bar it completed, if any.
+    fn add(&mut self, trade: &BittrexTrade) -> Option<BittrexCandle> {
+        let interval = match self.bar_type {
+            BarType::Time(interval) => floor_div(trade.time_stamp.timestamp_millis(), millis(interval)),
+            _ => 0,
+        };
+
+        let mut completed = None;
+        if self.current.as_ref().map(|bar| bar.interval != interval).unwrap_or(false) {
+            completed = self.current.take().map(|bar| bar.candle);
+        }
+
+        let bar_type = self.bar_type;
+        let bar = self.current.get_or_insert_with(|| Bar::new(trade, bar_type, interval));
+        bar.add(trade);
+
+        let complete = match bar_type {
+            BarType::Time(_) => false,
+            BarType::Trades(trades) => bar.trades >= trades,
+            BarType::Volume(volume) => bar.candle.volume >= volume,
+        };
+        if complete {
+            return self.current.take().map(|bar| bar.candle);
+        }
+        completed
+    }
+}
+
+impl Bar {
+    fn new(trade: &BittrexTrade, bar_type: BarType, interval: i64) -> Self {
+        let time_stamp = match bar_type {
+            BarType::Time(length) => {
+                let offset = trade.time_stamp.timestamp_millis() - interval * millis(length);
+                trade.time_stamp - chrono::Duration::milliseconds(offset)
+            },
+            _ => trade.time_stamp,
+        };
+
+        Bar {
+            candle: BittrexCandle {
+                time_stamp: time_stamp,
+                open: trade.price,
+                high: trade.price,
+                low: trade.price,
+                close: trade.price,
+                volume: Decimal::zero(),
+                base_volume: Decimal::zero(),
+            },
+            trades: 0,
+            interval: interval,
+        }
+    }
+
+    fn add(&mut self, trade: &BittrexTrade) {
+        let candle = &mut self.candle;
+        if trade.price > candle.high {
+            candle.high = trade.price;
+        }
+        if trade.price < candle.low {
+            candle.low = trade.price;
+        }
+        candle.close = trade.price;
+        candle.volume += trade.quantity;
+        candle.base_volume += trade.total;
+        self.trades += 1;
+    }
+}
+
+fn millis(duration: Duration) -> i64 {
+    (duration.as_secs() * 1000 + u64::from(duration.subsec_millis())) as i64
+}
+
+/// Division rounding towards negative infinity, so times before the epoch fall into the right interval.
+fn floor_div(dividend: i64, divisor: i64) -> i64 {
+    let quotient = dividend / divisor;
+    if dividend % divisor < 0 { quotient - 1 } else { quotient }
+}
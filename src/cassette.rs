@@ -0,0 +1,234 @@
+//! Recording and replaying of HTTP traffic for deterministic tests.
+//!
+//! A `Cassette` holds request/response pairs. `BittrexClientBuilder::record` records all calls of a client
+//! into a cassette, which can be saved to a file. A `ReplayTransport` serves the recorded responses back
+//! without network access.
+//!
+//! The `apikey` and `nonce` parameters are redacted before a request is recorded and the `apisign` header
+//! is not recorded at all, so cassettes can be committed. Requests are matched by method, path, the remaining
+//! query parameters in any order, and body.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bittrex_api::BittrexClient;
+//! use bittrex_api::cassette::{Cassette, ReplayTransport};
+//!
+//! // Record real traffic once ...
+//! let cassette = Cassette::new();
+//! let bittrex_client = BittrexClient::builder()
+//!     .credentials("APIKEY".to_string(), "APISECRET".to_string())
+//!     .record(cassette.clone())
+//!     .build()
+//!     .unwrap();
+//! bittrex_client.get_balances().unwrap();
+//! cassette.save("tests/cassettes/balances.json").unwrap();
+//!
+//! // ... and replay it offline.
+//! let bittrex_client = BittrexClient::builder()
+//!     .credentials("APIKEY".to_string(), "APISECRET".to_string())
+//!     .transport(ReplayTransport::new(Cassette::load("tests/cassettes/balances.json").unwrap()))
+//!     .build()
+//!     .unwrap();
+//! let balances = bittrex_client.get_balances().unwrap();
+//! ```
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use reqwest::Url;
+use serde_json;
+
+use error::{BittrexError, BittrexErrorType};
+use transport::{HttpRequest, HttpResponse, HttpTransport, StatusCode};
+
+const VOLATILE_PARAMS: &[&str] = &["apikey", "nonce"];
+const REDACTED: &str = "REDACTED";
+
+/// A recorded request/response pair.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The HTTP method of the request, e.g. `GET`.
+    pub method: String,
+    /// The url of the request with the volatile parameters redacted.
+    pub url: String,
+    /// The JSON body of v3 `POST` requests.
+    #[serde(default)]
+    pub request_body: Option<String>,
+    pub status: u16,
+    pub body: String,
+}
+
+/// Recorded interactions, shared by all clones.
+#[derive(Clone, Debug, Default)]
+pub struct Cassette {
+    interactions: Arc<Mutex<Vec<Interaction>>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn new() -> Self {
+        Cassette::default()
+    }
+
+    pub fn from_interactions(interactions: Vec<Interaction>) -> Self {
+        Cassette { interactions: Arc::new(Mutex::new(interactions)) }
+    }
+
+    /// Loads a cassette saved with `save`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, BittrexError> {
+        let mut content = String::new();
+        File::open(path.as_ref())
+            .and_then(|mut file| file.read_to_string(&mut content))
+            .map_err(|error| io_error("read", path.as_ref(), error))?;
+
+        let cassette_file: CassetteFile = serde_json::from_str(&content)?;
+        Ok(Cassette::from_interactions(cassette_file.interactions))
+    }
+
+    /// Saves all interactions recorded so far as JSON.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), BittrexError> {
+        let content = serde_json::to_string_pretty(&CassetteFile { interactions: self.interactions() })?;
+        File::create(path.as_ref())
+            .and_then(|mut file| file.write_all(content.as_bytes()))
+            .map_err(|error| io_error("write", path.as_ref(), error))
+    }
+
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.lock().clone()
+    }
+
+    fn record(&self, request: &HttpRequest, response: &HttpResponse) {
+        self.lock().push(Interaction {
+            method: request.method.to_string(),
+            url: redact_params(&request.url),
+            request_body: request.body.clone(),
+            status: response.status.as_u16(),
+            body: response.body.clone(),
+        });
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Interaction>> {
+        self.interactions.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Sends requests through another transport and records them with their responses into a cassette.
+/// Failures before a response was received are not recorded.
+pub struct RecordingTransport<T> {
+    inner: T,
+    cassette: Cassette,
+}
+
+impl<T: HttpTransport> RecordingTransport<T> {
+    pub fn new(inner: T, cassette: Cassette) -> Self {
+        RecordingTransport { inner: inner, cassette: cassette }
+    }
+}
+
+impl<T: HttpTransport> HttpTransport for RecordingTransport<T> {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, BittrexError> {
+        let response = self.inner.send(request)?;
+        self.cassette.record(request, &response);
+        Ok(response)
+    }
+}
+
+/// Answers requests with the responses recorded in a cassette.
+///
+/// Each request is answered with the first recorded interaction with the same method, path, non-volatile
+/// query parameters and body which wasn't served yet. Once all matching interactions were served, the last one is repeated.
+/// A request without any matching interaction fails with `BittrexErrorType::Configuration`.
+pub struct ReplayTransport {
+    interactions: Vec<(RequestKey, Interaction)>,
+    served: Mutex<Vec<bool>>,
+}
+
+impl ReplayTransport {
+    pub fn new(cassette: Cassette) -> Self {
+        let interactions: Vec<_> = cassette.interactions().into_iter()
+            .map(|interaction| (RequestKey::new(&interaction.method, &interaction.url, &interaction.request_body), interaction))
+            .collect();
+        let served = vec![false; interactions.len()];
+        ReplayTransport { interactions: interactions, served: Mutex::new(served) }
+    }
+}
+
+impl HttpTransport for ReplayTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, BittrexError> {
+        let key = RequestKey::new(request.method.as_str(), &request.url, &request.body);
+        let mut served = self.served.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let matching: Vec<usize> = self.interactions.iter().enumerate()
+            .filter(|&(_, &(ref interaction_key, _))| *interaction_key == key)
+            .map(|(index, _)| index)
+            .collect();
+        let index = match matching.iter().find(|&&index| !served[index]).or_else(|| matching.last()) {
+            Some(&index) => index,
+            None => return Err(BittrexError::new(BittrexErrorType::Configuration, "No recorded interaction for the request".to_string())
+                .with_url(&request.url)),
+        };
+        served[index] = true;
+
+        let interaction = &self.interactions[index].1;
+        let status = StatusCode::from_u16(interaction.status)
+            .map_err(|_| BittrexError::new(BittrexErrorType::Configuration, format!("Invalid recorded status {}", interaction.status)))?;
+        Ok(HttpResponse { status: status, url: request.url.clone(), body: interaction.body.clone() })
+    }
+}
+
+/// Method, path, sorted non-volatile query parameters and body of a request.
+#[derive(Debug, PartialEq, Eq)]
+struct RequestKey {
+    method: String,
+    path: String,
+    params: Vec<(String, String)>,
+    body: Option<String>,
+}
+
+impl RequestKey {
+    fn new(method: &str, url: &str, body: &Option<String>) -> Self {
+        let (path, params) = RequestKey::path_and_params(url);
+        RequestKey { method: method.to_uppercase(), path: path, params: params, body: body.clone() }
+    }
+
+    fn path_and_params(url: &str) -> (String, Vec<(String, String)>) {
+        match Url::parse(url) {
+            Ok(url) => {
+                let mut params: Vec<(String, String)> = url.query_pairs()
+                    .filter(|&(ref name, _)| !name.is_empty() && !VOLATILE_PARAMS.contains(&name.as_ref()))
+                    .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                    .collect();
+                params.sort();
+                (url.path().to_string(), params)
+            },
+            Err(_) => (url.to_string(), Vec::new()),
+        }
+    }
+}
+
+/// Replaces the values of the volatile query parameters with `REDACTED`.
+fn redact_params(url: &str) -> String {
+    let (base, query) = match url.find('?') {
+        Some(index) => (&url[..index], &url[index + 1..]),
+        None => return url.to_string(),
+    };
+
+    let params: Vec<String> = query.split('&')
+        .map(|param| {
+            let name = param.split('=').next().unwrap_or("");
+            if VOLATILE_PARAMS.contains(&name) { format!("{}={}", name, REDACTED) } else { param.to_string() }
+        })
+        .collect();
+    format!("{}?{}", base, params.join("&"))
+}
+
+fn io_error(action: &str, path: &Path, error: ::std::io::Error) -> BittrexError {
+    BittrexError::new(BittrexErrorType::Configuration, format!("Could not {} cassette {}: {}", action, path.display(), error))
+        .with_source(error)
+}
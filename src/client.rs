@@ -1,41 +1,401 @@
-use time;
 use std;
 use std::str;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 use hmac::{Hmac, Mac, MacResult};
 use sha2::Sha512;
 use generic_array::typenum::U64;
 use serde;
-use reqwest::{Client, Proxy};
-use reqwest::header::Headers;
-
+use serde::de::IgnoredAny;
+use serde_json;
+use reqwest::{async, Client, Proxy, StatusCode};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+
+use async_client::AsyncBittrexClient;
+use cassette::{Cassette, RecordingTransport};
+use endpoint;
 use error::{ BittrexError, BittrexErrorType };
+use decimal::Decimal;
+use market::{Currency, Market};
+use nonce::{MonotonicNonce, NonceSource};
+use rate_limit::{EndpointClass, RateLimiter};
+use retry::{self, RetryPolicy};
+use socket::{self, SocketClient};
+use transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
+use v3::{self, BittrexV3Client};
 use values::*;
 
 const API_URL: &str = "https://bittrex.com/api/v1.1";
+const V2_API_URL: &str = "https://bittrex.com/Api/v2.0";
+pub(crate) const DEFAULT_USER_AGENT: &str = concat!("bittrex-api/", env!("CARGO_PKG_VERSION"));
+const DEFAULT_CONNECT_TIMEOUT: u64 = 10;
+const DEFAULT_TIMEOUT: u64 = 30;
+const DEFAULT_MAX_IDLE_PER_HOST: usize = 8;
 
 pub type Result<T> = std::result::Result<T, BittrexError>;
 
-pub struct BittrexClient {
+/// Client for the Bittrex API.
+///
+/// A `BittrexClient<Authenticated>` (or just `BittrexClient`) has API credentials and provides all endpoints.
+/// A `BittrexClient<Public>` has no credentials and only provides the `/public/*` endpoints.
+///
+/// The underlying HTTP client and its connection pool are created once and shared by all calls
+/// (and all clones of the client), so consecutive calls reuse open TLS connections.
+/// Requests are sent through an `HttpTransport`, which can be replaced with `BittrexClientBuilder::transport`.
+#[derive(Clone)]
+pub struct BittrexClient<A = Authenticated> {
     api_url: String,
+    v2_api_url: String,
+    auth: A,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
+    transport: Arc<dyn HttpTransport>,
+}
+
+/// Client which only provides the public endpoints and needs no API credentials.
+pub type BittrexPublicClient = BittrexClient<Public>;
+
+/// Marks a client without API credentials. Calling a private endpoint on it does not compile:
+///
+/// ```rust,compile_fail
+/// use bittrex_api::BittrexClient;
+///
+/// let bittrex_client = BittrexClient::new_public();
+/// let balances = bittrex_client.get_balances();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Public;
+
+/// Marks a client with the API key and secret used to sign the private `/market/*` and `/account/*` calls.
+#[derive(Clone)]
+pub struct Authenticated {
     api_key: String,
     api_secret: String,
+    nonce_source: Arc<dyn NonceSource>,
+}
+
+/// Builder to configure a `BittrexClient`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use bittrex_api::{BittrexClient, RetryPolicy};
+///
+/// let bittrex_client = BittrexClient::builder()
+///     .credentials("APIKEY".to_string(), "APISECRET".to_string())
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-trading-bot/1.0".to_string())
+///     .retry_policy(RetryPolicy::new(3, Duration::from_millis(500)))
+///     .build()
+///     .unwrap();
+/// ```
+pub struct BittrexClientBuilder<A = Public> {
+    api_url: Option<String>,
+    v2_api_url: Option<String>,
+    auth: A,
     http_proxy: Option<String>,
-    https_proxy: Option<String>
+    https_proxy: Option<String>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    keep_alive: bool,
+    max_idle_per_host: usize,
+    user_agent: String,
+    default_headers: Vec<(String, String)>,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
+    transport: Option<Arc<dyn HttpTransport>>,
+    cassette: Option<Cassette>,
+}
+
+impl BittrexClientBuilder<Public> {
+    pub fn new() -> Self {
+        BittrexClientBuilder {
+            api_url: None,
+            v2_api_url: None,
+            auth: Public,
+            http_proxy: None,
+            https_proxy: None,
+            connect_timeout: Some(Duration::from_secs(DEFAULT_CONNECT_TIMEOUT)),
+            timeout: Some(Duration::from_secs(DEFAULT_TIMEOUT)),
+            keep_alive: true,
+            max_idle_per_host: DEFAULT_MAX_IDLE_PER_HOST,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            default_headers: Vec::new(),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: RateLimiter::new(),
+            transport: None,
+            cassette: None,
+        }
+    }
+}
+
+/// Applies the settings shared by the blocking and the async reqwest `ClientBuilder`.
+macro_rules! configure_http_client {
+    ($builder:expr, $client_builder:expr) => {{
+        let mut client_builder = $client_builder
+            .default_headers($builder.build_headers()?)
+            .max_idle_per_host(if $builder.keep_alive { $builder.max_idle_per_host } else { 0 });
+
+        if let Some(connect_timeout) = $builder.connect_timeout {
+            client_builder = client_builder.connect_timeout(connect_timeout);
+        }
+
+        if let Some(ref http_proxy) = $builder.http_proxy {
+            client_builder = client_builder.proxy(Proxy::http(http_proxy)?);
+        }
+
+        if let Some(ref https_proxy) = $builder.https_proxy {
+            client_builder = client_builder.proxy(Proxy::https(https_proxy)?);
+        }
+        client_builder
+    }}
+}
+
+impl<A> BittrexClientBuilder<A> {
+    /// Overrides the base url of the API (default: `https://bittrex.com/api/v1.1`, or `https://api.bittrex.com/v3`
+    /// for `build_v3` and `https://socket.bittrex.com/signalr` for `build_socket`).
+    pub fn api_url(mut self, api_url: String) -> Self {
+        self.api_url = Some(api_url);
+        self
+    }
+
+    /// Overrides the base url of the v2 API, which provides the candle endpoints (default: `https://bittrex.com/Api/v2.0`).
+    pub fn v2_api_url(mut self, v2_api_url: String) -> Self {
+        self.v2_api_url = Some(v2_api_url);
+        self
+    }
+
+    /// Sets the API key and secret used to sign the private `/market/*` and `/account/*` calls.
+    pub fn credentials(self, api_key: String, api_secret: String) -> BittrexClientBuilder<Authenticated> {
+        BittrexClientBuilder {
+            api_url: self.api_url,
+            v2_api_url: self.v2_api_url,
+            auth: Authenticated { api_key: api_key, api_secret: api_secret, nonce_source: Arc::new(MonotonicNonce::new()) },
+            http_proxy: self.http_proxy,
+            https_proxy: self.https_proxy,
+            connect_timeout: self.connect_timeout,
+            timeout: self.timeout,
+            keep_alive: self.keep_alive,
+            max_idle_per_host: self.max_idle_per_host,
+            user_agent: self.user_agent,
+            default_headers: self.default_headers,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            transport: self.transport,
+            cassette: self.cassette,
+        }
+    }
+
+    pub fn http_proxy(mut self, http_proxy: String) -> Self {
+        self.http_proxy = Some(http_proxy);
+        self
+    }
+
+    pub fn https_proxy(mut self, https_proxy: String) -> Self {
+        self.https_proxy = Some(https_proxy);
+        self
+    }
+
+    /// Timeout for establishing a connection (default: 10 seconds). `None` waits forever.
+    pub fn connect_timeout<T: Into<Option<Duration>>>(mut self, connect_timeout: T) -> Self {
+        self.connect_timeout = connect_timeout.into();
+        self
+    }
+
+    /// Timeout for a whole request including reading the response (default: 30 seconds). `None` waits forever.
+    pub fn timeout<T: Into<Option<Duration>>>(mut self, timeout: T) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// Whether connections are kept open and reused between calls (default: `true`).
+    pub fn keep_alive(mut self, keep_alive: bool) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// Maximum number of idle connections kept open per host (default: 8).
+    pub fn max_idle_per_host(mut self, max_idle_per_host: usize) -> Self {
+        self.max_idle_per_host = max_idle_per_host;
+        self
+    }
+
+    /// Overrides the `User-Agent` header (default: `bittrex-api/<version>`).
+    pub fn user_agent(mut self, user_agent: String) -> Self {
+        self.user_agent = user_agent;
+        self
+    }
+
+    /// Adds a header which is sent with every request.
+    pub fn default_header(mut self, name: String, value: String) -> Self {
+        self.default_headers.push((name, value));
+        self
+    }
+
+    /// Sets the policy for retrying idempotent calls (default: `RetryPolicy::none()`).
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the rate limiter for all calls (default: no limit).
+    /// Pass clones of the same `RateLimiter` to several clients to limit them together.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Sends the requests of the blocking client through `transport` instead of a reqwest `Client`.
+    /// The HTTP settings of the builder (proxies, timeouts, headers, ...) are not applied to it.
+    /// `build_async` ignores the transport.
+    pub fn transport<T>(mut self, transport: T) -> Self where T: HttpTransport + 'static {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Records all calls of the blocking client with their responses into `cassette`.
+    /// See the `cassette` module for replaying them.
+    pub fn record(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    pub fn build(self) -> Result<BittrexClient<A>> {
+        let mut transport: Arc<dyn HttpTransport> = match self.transport {
+            Some(ref transport) => transport.clone(),
+            None => {
+                let client_builder = configure_http_client!(self, Client::builder())
+                    .timeout(self.timeout);
+                Arc::new(ReqwestTransport::new(client_builder.build()?))
+            },
+        };
+        if let Some(ref cassette) = self.cassette {
+            transport = Arc::new(RecordingTransport::new(transport, cassette.clone()));
+        }
+
+        Ok(BittrexClient {
+            api_url: self.api_url.unwrap_or_else(|| API_URL.to_string()),
+            v2_api_url: self.v2_api_url.unwrap_or_else(|| V2_API_URL.to_string()),
+            auth: self.auth,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            transport: transport,
+        })
+    }
+
+    /// Builds an `AsyncBittrexClient` with the same settings.
+    pub fn build_async(self) -> Result<AsyncBittrexClient<A>> {
+        let mut client_builder = configure_http_client!(self, async::Client::builder());
+
+        if let Some(timeout) = self.timeout {
+            client_builder = client_builder.timeout(timeout);
+        }
+
+        let api_url = self.api_url.unwrap_or_else(|| API_URL.to_string());
+        let v2_api_url = self.v2_api_url.unwrap_or_else(|| V2_API_URL.to_string());
+        Ok(AsyncBittrexClient::from_parts(api_url, v2_api_url, self.auth, self.retry_policy, self.rate_limiter, client_builder.build()?))
+    }
+
+    /// Builds a `BittrexV3Client` for the v3 API with the same settings.
+    pub fn build_v3(self) -> Result<BittrexV3Client<A>> {
+        let api_url = self.api_url.clone().unwrap_or_else(|| v3::API_URL.to_string());
+        Ok(BittrexV3Client::from_parts(api_url, self.build()?))
+    }
+
+    /// Builds a `SocketClient` for the websocket API with the same proxies, headers, timeout and rate limiter.
+    pub fn build_socket(self) -> Result<SocketClient> {
+        let api_url = self.api_url.clone().unwrap_or_else(|| socket::SOCKET_URL.to_string());
+        let socket_client = SocketClient::from_parts(&api_url, self.http_proxy.clone(), self.https_proxy.clone(), self.build_headers()?, self.rate_limiter.clone());
+        Ok(match self.timeout {
+            Some(timeout) => socket_client.timeout(timeout),
+            None => socket_client,
+        })
+    }
+
+    fn build_headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, header_value(&self.user_agent)?);
+
+        for &(ref name, ref value) in &self.default_headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| BittrexError::new(BittrexErrorType::Configuration, format!("Invalid header name '{}'", name)))?;
+            headers.insert(header_name, header_value(value)?);
+        }
+        Ok(headers)
+    }
+}
+
+impl BittrexClientBuilder<Authenticated> {
+    /// Sets the source of the nonces of private calls (default: `MonotonicNonce`).
+    /// See the `nonce` module for sources which survive restarts or are shared by several processes.
+    pub fn nonce_source<N>(mut self, nonce_source: N) -> Self where N: NonceSource + 'static {
+        self.auth.nonce_source = Arc::new(nonce_source);
+        self
+    }
+}
+
+impl Default for BittrexClientBuilder<Public> {
+    fn default() -> Self {
+        BittrexClientBuilder::new()
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue> {
+    HeaderValue::from_str(value)
+        .map_err(|_| BittrexError::new(BittrexErrorType::Configuration, format!("Invalid header value '{}'", value)))
+}
+
+impl BittrexClient<Public> {
+    /// Creates a client without credentials, which only provides the public endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized. Use `BittrexClient::builder` to handle this case.
+    pub fn new_public() -> Self {
+        BittrexClient::builder().build().expect("HTTP client should be initialized!")
+    }
+
+    pub fn builder() -> BittrexClientBuilder<Public> {
+        BittrexClientBuilder::new()
+    }
 }
 
-impl BittrexClient {
+impl BittrexClient<Authenticated> {
+    /// Creates a client with the default settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized. Use `BittrexClient::builder` to handle this case.
     pub fn new(api_key: String, api_secret: String) -> Self {
-        BittrexClient { api_url: API_URL.to_string(), api_key: api_key, api_secret: api_secret, http_proxy: None, https_proxy: None }
+        BittrexClient::builder().credentials(api_key, api_secret).build().expect("HTTP client should be initialized!")
     }
 
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized. Use `BittrexClient::builder` to handle this case.
     pub fn new_override_api_url(api_key: String, api_secret: String, api_url: String) -> Self {
-        BittrexClient { api_url: api_url, api_key: api_key, api_secret: api_secret, http_proxy: None, https_proxy: None }
+        BittrexClient::builder().credentials(api_key, api_secret).api_url(api_url).build().expect("HTTP client should be initialized!")
     }
 
+    /// # Panics
+    ///
+    /// Panics if a proxy url is invalid or the HTTP client cannot be initialized. Use `BittrexClient::builder` to handle this case.
     pub fn new_with_proxy(api_key: String, api_secret: String, http_proxy: Option<String>, https_proxy: Option<String>) -> Self {
-        BittrexClient { api_url: API_URL.to_string(), api_key: api_key, api_secret: api_secret, http_proxy: http_proxy, https_proxy: https_proxy }
+        let mut builder = BittrexClient::builder().credentials(api_key, api_secret);
+        if let Some(http_proxy) = http_proxy {
+            builder = builder.http_proxy(http_proxy);
+        }
+        if let Some(https_proxy) = https_proxy {
+            builder = builder.https_proxy(https_proxy);
+        }
+        builder.build().expect("HTTP client should be initialized!")
     }
+}
 
+impl<A> BittrexClient<A> {
     /// Returns all available market data
     ///
     /// # Examples
@@ -47,8 +407,8 @@ impl BittrexClient {
     /// let markets = bittrex_client.get_markets().unwrap();
     /// ```
     pub fn get_markets(&self) -> Result<Vec<BittrexMarket>> {
-        let markets = self.call_public_api::<BittrexAPIVecResult<BittrexMarket>>(&format!("{}/public/getmarkets", self.api_url))?;
-        self.check_return_vec_response(markets)
+        let markets = self.call_public_api::<BittrexAPIVecResult<BittrexMarket>>(&endpoint::get_markets(&self.api_url))?;
+        check_return_vec_response(markets)
     }
 
     /// Returns all available currencies
@@ -62,8 +422,8 @@ impl BittrexClient {
     /// let currencies = bittrex_client.get_currencies().unwrap();
     /// ```
     pub fn get_currencies(&self) -> Result<Vec<BittrexCurrency>> {
-        let currencies = self.call_public_api::<BittrexAPIVecResult<BittrexCurrency>>(&format!("{}/public/getcurrencies", self.api_url))?;
-        self.check_return_vec_response(currencies)
+        let currencies = self.call_public_api::<BittrexAPIVecResult<BittrexCurrency>>(&endpoint::get_currencies(&self.api_url))?;
+        check_return_vec_response(currencies)
     }
 
     /// Returns ticker by market name
@@ -71,14 +431,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Market};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let ticker = bittrex_client.get_ticker("BTC-LTC").unwrap();
+    /// let market: Market = "BTC-LTC".parse().unwrap();
+    /// let ticker = bittrex_client.get_ticker(&market).unwrap();
     /// ```
-    pub fn get_ticker(&self, market: &str) -> Result<BittrexTicker> {
-        let ticker = self.call_public_api::<BittrexAPIResult<BittrexTicker>>(&format!("{}/public/getticker?market={}", self.api_url, market))?;
-        self.check_return_single_response(ticker)        
+    pub fn get_ticker(&self, market: &Market) -> Result<BittrexTicker> {
+        let ticker = self.call_public_api::<BittrexAPIResult<BittrexTicker>>(&endpoint::get_ticker(&self.api_url, market))?;
+        check_return_single_response(ticker)        
     }
 
     /// Returns all market summaries
@@ -92,8 +453,8 @@ impl BittrexClient {
     /// let summaries = bittrex_client.get_market_summaries().unwrap();
     /// ```
     pub fn get_market_summaries(&self) -> Result<Vec<BittrexMarketSummary>> {
-        let summaries = self.call_public_api::<BittrexAPIVecResult<BittrexMarketSummary>>(&format!("{}/public/getmarketsummaries", self.api_url))?;
-        self.check_return_vec_response(summaries)
+        let summaries = self.call_public_api::<BittrexAPIVecResult<BittrexMarketSummary>>(&endpoint::get_market_summaries(&self.api_url))?;
+        check_return_vec_response(summaries)
     }
 
     /// Returns market summary by market name
@@ -101,14 +462,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Market};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let summary = bittrex_client.get_market_summary("BTC-LTC").unwrap();
+    /// let market: Market = "BTC-LTC".parse().unwrap();
+    /// let summary = bittrex_client.get_market_summary(&market).unwrap();
     /// ```
-    pub fn get_market_summary(&self, market: &str) -> Result<BittrexMarketSummary> {
-        let summary = self.call_public_api::<BittrexAPIVecResult<BittrexMarketSummary>>(&format!("{}/public/getmarketsummary?market={}", self.api_url, market))?;
-        self.check_return_single_vec_response(summary)
+    pub fn get_market_summary(&self, market: &Market) -> Result<BittrexMarketSummary> {
+        let summary = self.call_public_api::<BittrexAPIVecResult<BittrexMarketSummary>>(&endpoint::get_market_summary(&self.api_url, market))?;
+        check_return_single_vec_response(summary)
     }
 
     /// Returns the order book of the given market.
@@ -116,15 +478,16 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Market};
     /// use bittrex_api::values::BittrexOrderType;
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let order_book = bittrex_client.get_order_book("BTC-LTC", BittrexOrderType::Both).unwrap();
+    /// let market: Market = "BTC-LTC".parse().unwrap();
+    /// let order_book = bittrex_client.get_order_book(&market, BittrexOrderType::Both).unwrap();
     /// ```
-    pub fn get_order_book(&self, market: &str, book_type: BittrexOrderType) -> Result<BittrexPublicOrderBook> {
-        let order_book = self.call_public_api::<BittrexAPIResult<BittrexPublicOrderBook>>(&format!("{}/public/getorderbook?market={}&type={}", self.api_url, market, book_type))?;
-        self.check_return_single_response(order_book)
+    pub fn get_order_book(&self, market: &Market, book_type: BittrexOrderType) -> Result<BittrexPublicOrderBook> {
+        let order_book = self.call_public_api::<BittrexAPIResult<BittrexPublicOrderBook>>(&endpoint::get_order_book(&self.api_url, market, book_type))?;
+        check_return_single_response(order_book)
     }
 
     /// Returns the market history of the given market.
@@ -132,16 +495,80 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Market};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let market_history = bittrex_client.get_market_history("BTC-LTC").unwrap();
+    /// let market: Market = "BTC-LTC".parse().unwrap();
+    /// let market_history = bittrex_client.get_market_history(&market).unwrap();
     /// ```
-    pub fn get_market_history(&self, market: &str) -> Result<Vec<BittrexTrade>> {
-        let market_history = self.call_public_api::<BittrexAPIVecResult<BittrexTrade>>(&format!("{}/public/getmarkethistory?market={}", self.api_url, market))?;
-        self.check_return_vec_response(market_history)
+    pub fn get_market_history(&self, market: &Market) -> Result<Vec<BittrexTrade>> {
+        let market_history = self.call_public_api::<BittrexAPIVecResult<BittrexTrade>>(&endpoint::get_market_history(&self.api_url, market))?;
+        check_return_vec_response(market_history)
     }
 
+    /// Returns the candles of a market for the given interval, oldest first (v2 `GetTicks`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::values::TickInterval;
+    ///
+    /// let bittrex_client = BittrexClient::new_public();
+    /// let candles = bittrex_client.get_ticks(&"BTC-LTC".parse().unwrap(), TickInterval::Hour).unwrap();
+    /// ```
+    pub fn get_ticks(&self, market: &Market, tick_interval: TickInterval) -> Result<Vec<BittrexCandle>> {
+        let ticks = self.call_public_api::<BittrexAPIVecResult<BittrexCandle>>(&endpoint::get_ticks(&self.v2_api_url, market, tick_interval))?;
+        check_return_vec_response(ticks)
+    }
+
+    /// Returns the current, still open candle of a market for the given interval (v2 `GetLatestTick`).
+    pub fn get_latest_tick(&self, market: &Market, tick_interval: TickInterval) -> Result<BittrexCandle> {
+        let tick = self.call_public_api::<BittrexAPIVecResult<BittrexCandle>>(&endpoint::get_latest_tick(&self.v2_api_url, market, tick_interval))?;
+        check_return_single_vec_response(tick)
+    }
+
+    fn call_public_api<T>(&self, request: &endpoint::Request) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
+        let resp = self.send_with_retries(EndpointClass::Public, self.retry_policy.retries_for(false), || Ok(HttpRequest::get(request.url(), Vec::new())))?;
+        read_response(resp)
+    }
+
+    /// Number of retries the retry policy allows for calls which change state or not.
+    pub(crate) fn max_retries(&self, changes_state: bool) -> u32 {
+        self.retry_policy.retries_for(changes_state)
+    }
+
+    pub(crate) fn auth(&self) -> &A {
+        &self.auth
+    }
+
+    /// Sends the request built by `request`, building and sending it again for each retry,
+    /// so private calls get a fresh nonce and signature. Failures before a response was received
+    /// and responses with a transient HTTP status are retried. Every attempt is subject to the rate limit of `class`.
+    pub(crate) fn send_with_retries<F>(&self, class: EndpointClass, max_retries: u32, request: F) -> Result<HttpResponse> where F: Fn() -> Result<HttpRequest> {
+        let mut retries = 0;
+        loop {
+            let rate_limit_delay = self.rate_limiter.acquire(class)?;
+            if rate_limit_delay > Duration::from_secs(0) {
+                thread::sleep(rate_limit_delay);
+            }
+
+            let result = self.transport.send(&request()?);
+            let transient = match result {
+                Ok(ref resp) => retry::is_transient_status(resp.status),
+                Err(ref error) => error.error_type == BittrexErrorType::Transport,
+            };
+            if transient && retries < max_retries {
+                thread::sleep(self.retry_policy.delay(retries));
+                retries += 1;
+                continue;
+            }
+            return result;
+        }
+    }
+}
+
+impl BittrexClient<Authenticated> {
     /// Returns the open orders of the user given by the api_key and api_secret.
     ///
     /// # Examples
@@ -153,8 +580,8 @@ impl BittrexClient {
     /// let open_orders = bittrex_client.get_open_orders().unwrap();
     /// ```
     pub fn get_open_orders(&self) -> Result<Vec<BittrexOpenOrder>> {
-        let open_orders = self.call_private_api::<BittrexAPIVecResult<BittrexOpenOrder>>(&format!("{}/market/getopenorders?", self.api_url))?;
-        self.check_return_vec_response(open_orders)
+        let open_orders = self.call_private_api::<BittrexAPIVecResult<BittrexOpenOrder>>(&endpoint::get_open_orders(&self.api_url))?;
+        check_return_vec_response(open_orders)
     }
 
     /// Returns the open orders of the given market and of the user given by the api_key and api_secret.
@@ -162,14 +589,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Market};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let open_orders = bittrex_client.get_open_orders_by_market("BTC-LTC").unwrap();
+    /// let market: Market = "BTC-LTC".parse().unwrap();
+    /// let open_orders = bittrex_client.get_open_orders_by_market(&market).unwrap();
     /// ```
-    pub fn get_open_orders_by_market(&self, market: &str) -> Result<Vec<BittrexOpenOrder>> {
-        let open_orders = self.call_private_api::<BittrexAPIVecResult<BittrexOpenOrder>>(&format!("{}/market/getopenorders?market={}", self.api_url, market))?;
-        self.check_return_vec_response(open_orders)
+    pub fn get_open_orders_by_market(&self, market: &Market) -> Result<Vec<BittrexOpenOrder>> {
+        let open_orders = self.call_private_api::<BittrexAPIVecResult<BittrexOpenOrder>>(&endpoint::get_open_orders_by_market(&self.api_url, market))?;
+        check_return_vec_response(open_orders)
     }
 
     /// Returns the order given by the order_id.
@@ -183,8 +611,8 @@ impl BittrexClient {
     /// let order = bittrex_client.get_order("ORDERID").unwrap();
     /// ```
     pub fn get_order(&self, order_id: &str) -> Result<BittrexOrder> {
-        let order = self.call_private_api::<BittrexAPIResult<BittrexOrder>>(&format!("{}/account/getorder?uuid={}", self.api_url, order_id))?;
-        self.check_return_single_response(order)
+        let order = self.call_private_api::<BittrexAPIResult<BittrexOrder>>(&endpoint::get_order(&self.api_url, order_id))?;
+        check_return_single_response(order)
     }
 
     /// Returns the order history of the user given by the api_key and api_secret.
@@ -198,8 +626,8 @@ impl BittrexClient {
     /// let order_history = bittrex_client.get_order_history().unwrap();
     /// ```
     pub fn get_order_history(&self) -> Result<Vec<BittrexHistoryOrder>> {
-        let order_history = self.call_private_api::<BittrexAPIVecResult<BittrexHistoryOrder>>(&format!("{}/account/getorderhistory?", self.api_url))?;
-        self.check_return_vec_response(order_history)
+        let order_history = self.call_private_api::<BittrexAPIVecResult<BittrexHistoryOrder>>(&endpoint::get_order_history(&self.api_url))?;
+        check_return_vec_response(order_history)
     }
 
     /// Returns the order history of the given market and of the user given by the api_key and api_secret.
@@ -207,14 +635,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Market};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let order_history = bittrex_client.get_order_history_by_market("BTC-LTC").unwrap();
+    /// let market: Market = "BTC-LTC".parse().unwrap();
+    /// let order_history = bittrex_client.get_order_history_by_market(&market).unwrap();
     /// ```
-    pub fn get_order_history_by_market(&self, market: &str) -> Result<Vec<BittrexHistoryOrder>> {
-        let order_history = self.call_private_api::<BittrexAPIVecResult<BittrexHistoryOrder>>(&format!("{}/account/getorderhistory?market={}", self.api_url, market))?;
-        self.check_return_vec_response(order_history)
+    pub fn get_order_history_by_market(&self, market: &Market) -> Result<Vec<BittrexHistoryOrder>> {
+        let order_history = self.call_private_api::<BittrexAPIVecResult<BittrexHistoryOrder>>(&endpoint::get_order_history_by_market(&self.api_url, market))?;
+        check_return_vec_response(order_history)
     }
 
     /// Returns the withdrawal history of the user given by the api_key and api_secret.
@@ -228,8 +657,8 @@ impl BittrexClient {
     /// let withdrawal_history = bittrex_client.get_withdrawal_history().unwrap();
     /// ```
     pub fn get_withdrawal_history(&self) -> Result<Vec<BittrexTransaction>> {
-        let withdrawal_history = self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(&format!("{}/account/getwithdrawalhistory?", self.api_url))?;
-        self.check_return_vec_response(withdrawal_history)
+        let withdrawal_history = self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(&endpoint::get_withdrawal_history(&self.api_url))?;
+        check_return_vec_response(withdrawal_history)
     }
 
     /// Returns the withdrawal history of the given market and of the user given by the api_key and api_secret.
@@ -237,14 +666,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Currency};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let withdrawal_history = bittrex_client.get_withdrawal_history_by_currency("BTC-LTC").unwrap();
+    /// let currency = Currency::new("BTC").unwrap();
+    /// let withdrawal_history = bittrex_client.get_withdrawal_history_by_currency(&currency).unwrap();
     /// ```
-    pub fn get_withdrawal_history_by_currency(&self, currency: &str) -> Result<Vec<BittrexTransaction>> {
-        let withdrawal_history = self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(&format!("{}/account/getwithdrawalhistory?currency={}", self.api_url, currency))?;
-        self.check_return_vec_response(withdrawal_history)
+    pub fn get_withdrawal_history_by_currency(&self, currency: &Currency) -> Result<Vec<BittrexTransaction>> {
+        let withdrawal_history = self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(&endpoint::get_withdrawal_history_by_currency(&self.api_url, currency))?;
+        check_return_vec_response(withdrawal_history)
     }
 
     /// Returns the deposit history of the user given by the api_key and api_secret.
@@ -258,8 +688,8 @@ impl BittrexClient {
     /// let deposit_history = bittrex_client.get_deposit_history().unwrap();
     /// ```
     pub fn get_deposit_history(&self) -> Result<Vec<BittrexTransaction>> {
-        let deposit_history = self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(&format!("{}/account/getdeposithistory?", self.api_url))?;
-        self.check_return_vec_response(deposit_history)
+        let deposit_history = self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(&endpoint::get_deposit_history(&self.api_url))?;
+        check_return_vec_response(deposit_history)
     }
 
     /// Returns the deposit history of the given currency and of the user given by the api_key and api_secret.
@@ -267,14 +697,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Currency};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let deposit_history = bittrex_client.get_deposit_history_by_currency("BTC").unwrap();
+    /// let currency = Currency::new("BTC").unwrap();
+    /// let deposit_history = bittrex_client.get_deposit_history_by_currency(&currency).unwrap();
     /// ```
-    pub fn get_deposit_history_by_currency(&self, currency: &str) -> Result<Vec<BittrexTransaction>> {
-        let deposit_history = self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(&format!("{}/account/getdeposithistory?currency={}", self.api_url, currency))?;
-        self.check_return_vec_response(deposit_history)
+    pub fn get_deposit_history_by_currency(&self, currency: &Currency) -> Result<Vec<BittrexTransaction>> {
+        let deposit_history = self.call_private_api::<BittrexAPIVecResult<BittrexTransaction>>(&endpoint::get_deposit_history_by_currency(&self.api_url, currency))?;
+        check_return_vec_response(deposit_history)
     }
 
     /// Returns the balances of the user given by the api_key and api_secret.
@@ -288,8 +719,8 @@ impl BittrexClient {
     /// let balances = bittrex_client.get_balances().unwrap();
     /// ```
     pub fn get_balances(&self) -> Result<Vec<BittrexBalance>> {
-        let balances = self.call_private_api::<BittrexAPIVecResult<BittrexBalance>>(&format!("{}/account/getbalances?", self.api_url))?;
-        self.check_return_vec_response(balances)
+        let balances = self.call_private_api::<BittrexAPIVecResult<BittrexBalance>>(&endpoint::get_balances(&self.api_url))?;
+        check_return_vec_response(balances)
     }
 
     /// Returns the balance of the given currency and of the user given by the api_key and api_secret.
@@ -297,14 +728,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Currency};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let balance = bittrex_client.get_balance("BTC").unwrap();
+    /// let currency = Currency::new("BTC").unwrap();
+    /// let balance = bittrex_client.get_balance(&currency).unwrap();
     /// ```
-    pub fn get_balance(&self, currency: &str) -> Result<BittrexBalance> {
-        let balance = self.call_private_api::<BittrexAPIResult<BittrexBalance>>(&format!("{}/account/getbalance?currency={}", self.api_url, currency))?;
-        self.check_return_single_response(balance)
+    pub fn get_balance(&self, currency: &Currency) -> Result<BittrexBalance> {
+        let balance = self.call_private_api::<BittrexAPIResult<BittrexBalance>>(&endpoint::get_balance(&self.api_url, currency))?;
+        check_return_single_response(balance)
     }
 
     /// Returns the deposit address of the given currency and of the user given by the api_key and api_secret.
@@ -312,14 +744,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Currency};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let deposit_history = bittrex_client.get_deposit_address("BTC").unwrap();
+    /// let currency = Currency::new("BTC").unwrap();
+    /// let deposit_history = bittrex_client.get_deposit_address(&currency).unwrap();
     /// ```
-    pub fn get_deposit_address(&self, currency: &str) -> Result<BittrexAddress> {
-        let deposit_address = self.call_private_api::<BittrexAPIResult<BittrexAddress>>(&format!("{}/account/getdepositaddress?currency={}", self.api_url, currency))?;
-        self.check_return_single_response(deposit_address)
+    pub fn get_deposit_address(&self, currency: &Currency) -> Result<BittrexAddress> {
+        let deposit_address = self.call_private_api::<BittrexAPIResult<BittrexAddress>>(&endpoint::get_deposit_address(&self.api_url, currency))?;
+        check_return_single_response(deposit_address)
     }
 
     /// Withdraws tokens of the user given by the api_key and api_secret.
@@ -327,14 +760,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Currency, Decimal};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let withdraw_uuid = bittrex_client.withdraw("BTC", 1.5, "BITCOINADDRESS", "").unwrap();
+    /// let currency = Currency::new("BTC").unwrap();
+    /// let withdraw_uuid = bittrex_client.withdraw(&currency, Decimal::new(15, 1), "BITCOINADDRESS", "").unwrap();
     /// ```
-    pub fn withdraw(&self, currency: &str, quantity: f64, address: &str, payment_id: &str) -> Result<BittrexUuid> {
-        let withdraw = self.call_private_api::<BittrexAPIResult<BittrexUuid>>(&format!("{}/account/withdraw?currency={}&quantity={}&address={}&paymentid={}", self.api_url, currency, quantity, address, payment_id))?;
-        self.check_return_single_response(withdraw)
+    pub fn withdraw(&self, currency: &Currency, quantity: Decimal, address: &str, payment_id: &str) -> Result<BittrexUuid> {
+        let withdraw = self.call_trading_api::<BittrexAPIResult<BittrexUuid>>(&endpoint::withdraw(&self.api_url, currency, quantity, address, payment_id))?;
+        check_return_single_response(withdraw)
     }
 
     /// Places a buy order on the given market for the user given by the api_key and api_secret.
@@ -342,14 +776,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Decimal, Market};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let buy_uuid = bittrex_client.buy_limit("BTC-LTC", 1.5, 0.00023).unwrap();
+    /// let market: Market = "BTC-LTC".parse().unwrap();
+    /// let buy_uuid = bittrex_client.buy_limit(&market, Decimal::new(15, 1), Decimal::new(23, 5)).unwrap();
     /// ```
-    pub fn buy_limit(&self, market: &str, quantity: f64, rate: f64) -> Result<BittrexUuid> {
-        let buy_limit = self.call_private_api::<BittrexAPIResult<BittrexUuid>>(&format!("{}/market/buylimit?market={}&quantity={}&rate={}", self.api_url, market, quantity, rate))?;
-        self.check_return_single_response(buy_limit)
+    pub fn buy_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid> {
+        let buy_limit = self.call_trading_api::<BittrexAPIResult<BittrexUuid>>(&endpoint::buy_limit(&self.api_url, market, quantity, rate))?;
+        check_return_single_response(buy_limit)
     }
 
     /// Places a sell order on the given market for the user given by the api_key and api_secret.
@@ -357,14 +792,15 @@ impl BittrexClient {
     /// # Examples
     /// 
     /// ```rust,no_run
-    /// use bittrex_api::BittrexClient;
+    /// use bittrex_api::{BittrexClient, Decimal, Market};
     ///
     /// let bittrex_client = BittrexClient::new("APIKEY".to_string(), "APISECRET".to_string());
-    /// let sell_uuid = bittrex_client.sell_limit("BTC-LTC", 1.5, 0.00023).unwrap();
+    /// let market: Market = "BTC-LTC".parse().unwrap();
+    /// let sell_uuid = bittrex_client.sell_limit(&market, Decimal::new(15, 1), Decimal::new(23, 5)).unwrap();
     /// ```
-    pub fn sell_limit(&self, market: &str, quantity: f64, rate: f64) -> Result<BittrexUuid> {
-        let sell_limit = self.call_private_api::<BittrexAPIResult<BittrexUuid>>(&format!("{}/market/selllimit?market={}&quantity={}&rate={}", self.api_url, market, quantity, rate))?;
-        self.check_return_single_response(sell_limit)
+    pub fn sell_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid> {
+        let sell_limit = self.call_trading_api::<BittrexAPIResult<BittrexUuid>>(&endpoint::sell_limit(&self.api_url, market, quantity, rate))?;
+        check_return_single_response(sell_limit)
     }
 
     /// Cancels an order for the user given by the api_key and api_secret.
@@ -378,83 +814,118 @@ impl BittrexClient {
     /// bittrex_client.cancel_order("ORDERID").unwrap();
     /// ```
     pub fn cancel_order(&self, order_id: &str) -> Result<()> {
-        self.call_private_api::<BittrexAPIResult<()>>(&format!("{}/market/cancel?uuid={}", self.api_url, order_id))?;
-        Ok(())
+        let cancel = self.call_trading_api::<BittrexAPIResult<IgnoredAny>>(&endpoint::cancel_order(&self.api_url, order_id))?;
+        check_return_empty_response(cancel)
     }
 
-    fn call_public_api<T>(&self, url: &str) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
-        let client = self.get_client()?;
-        let mut resp = client.get(url)?.send()?;
-        let result : T = resp.json()?;
-        
-        Ok(result)
+    /// Calls an idempotent private endpoint, which is retried according to the retry policy.
+    fn call_private_api<T>(&self, request: &endpoint::Request) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
+        let resp = self.send_with_retries(EndpointClass::Private, self.retry_policy.retries_for(false), || self.signed_request(request))?;
+        read_response(resp)
     }
 
-    fn call_private_api<T>(&self, url: &str) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
-        let url_with_key = format!("{}&apikey={}&nonce={}", url, self.api_key, time::precise_time_ns());
-        let hmac = self.sign_call(&url_with_key);
-        
-        let mut headers = Headers::new();
-        headers.set_raw("apisign", self.to_hex_string(hmac.code()));
+    /// Calls a private endpoint which changes state (orders, withdrawals) and is only retried if the retry policy opts in.
+    fn call_trading_api<T>(&self, request: &endpoint::Request) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
+        let resp = self.send_with_retries(EndpointClass::Private, self.retry_policy.retries_for(true), || self.signed_request(request))?;
+        read_response(resp)
+    }
 
-        let client = self.get_client()?;
-        let mut resp = client.get(&url_with_key)?.headers(headers).send()?;
-        let result : T = resp.json()?;
-        
-        Ok(result)
+    fn signed_request(&self, request: &endpoint::Request) -> Result<HttpRequest> {
+        let (signed_url, signature) = self.auth.sign_url(request)?;
+
+        Ok(HttpRequest::get(signed_url, vec![("apisign".to_string(), signature)]))
     }
+}
 
-    fn sign_call(&self, msg: &str) -> MacResult<U64> {
-        let mut hmac = Hmac::<Sha512>::new(self.api_secret.as_bytes());
-        hmac.input(msg.as_bytes());
-        
-        MacResult::from_slice(hmac.result().code())
+impl Authenticated {
+    pub(crate) fn api_key(&self) -> &str {
+        &self.api_key
     }
 
-    fn get_client(&self) -> Result<Client> {
-        let mut client_builder = Client::builder()?;
+    pub(crate) fn api_secret(&self) -> &str {
+        &self.api_secret
+    }
 
-        if let Some(ref http_proxy) = self.http_proxy {
-            client_builder.proxy(Proxy::http(http_proxy)?);
-        }
+    /// Appends the api key and a new nonce to the url of a private endpoint
+    /// and returns it together with its signature for the `apisign` header.
+    pub(crate) fn sign_url(&self, request: &endpoint::Request) -> Result<(String, String)> {
+        let signed_url = request.signed_url(&self.api_key, self.nonce_source.next_nonce()?);
+        let signature = signature(&self.api_secret, &signed_url);
 
-        if let Some(ref https_proxy) = self.https_proxy {
-            client_builder.proxy(Proxy::https(https_proxy)?);
-        }
-        
-        Ok(client_builder.build()?)
+        Ok((signed_url, signature))
+    }
+}
+
+/// Returns the `apisign` header of a signed url: the HMAC-SHA512 of the url keyed with the api secret, as upper case hex.
+pub(crate) fn signature(api_secret: &str, signed_url: &str) -> String {
+    let mut hmac = Hmac::<Sha512>::new(api_secret.as_bytes());
+    hmac.input(signed_url.as_bytes());
+    let code: MacResult<U64> = hmac.result();
+
+    to_hex_string(code.code())
+}
+
+/// A successful response without result (`result: null`) is turned into a `NoResults` error.
+pub(crate) fn check_return_single_response<T>(bittrex_api_result: BittrexAPIResult<T>) -> Result<T> {
+    if bittrex_api_result.success {
+        return bittrex_api_result.result
+            .ok_or_else(|| BittrexError::new(BittrexErrorType::NoResults, "Result is missing".to_string()));
     }
+    Err(BittrexError::api(bittrex_api_result.message))
+}
 
-    fn check_return_single_response<T>(&self, bittrex_api_result: BittrexAPIResult<T>) -> Result<T> {
-        if bittrex_api_result.success {
-            return Ok(bittrex_api_result.result.expect("Result should exist!"));
-        }
-        Err(BittrexError { error_type: BittrexErrorType::APIError, message: bittrex_api_result.message })
+/// A successful response without result (`result: null`) is treated as an empty list.
+pub(crate) fn check_return_vec_response<T>(bittrex_api_result: BittrexAPIVecResult<T>) -> Result<Vec<T>> {
+    if bittrex_api_result.success {
+        return Ok(bittrex_api_result.result.unwrap_or_default());
     }
+    Err(BittrexError::api(bittrex_api_result.message))
+}
 
-    fn check_return_vec_response<T>(&self, bittrex_api_result: BittrexAPIVecResult<T>) -> Result<Vec<T>> {
-        if bittrex_api_result.success {
-            return Ok(bittrex_api_result.result.expect("Result should exist!"));
-        }
-        Err(BittrexError { error_type: BittrexErrorType::APIError, message: bittrex_api_result.message })
+/// For calls which return no result (like `cancel_order`), only checks whether the call succeeded.
+pub(crate) fn check_return_empty_response(bittrex_api_result: BittrexAPIResult<IgnoredAny>) -> Result<()> {
+    if bittrex_api_result.success {
+        return Ok(());
     }
+    Err(BittrexError::api(bittrex_api_result.message))
+}
 
-    fn check_return_single_vec_response<T>(&self, bittrex_api_result: BittrexAPIVecResult<T>) -> Result<T> {
-        if bittrex_api_result.success {
-            let mut result = bittrex_api_result.result.expect("Result should exist!");
-            return match result.len() {
-                1 => Ok(result.remove(0)),
-                0 => Err(BittrexError { error_type: BittrexErrorType::NoResults, message: "Maybe check your parameters?".to_string() }),
-                _ => Err(BittrexError { error_type: BittrexErrorType::APIError, message: "Multiple results found! Maybe check your parameters?".to_string() })
-            }
+pub(crate) fn check_return_single_vec_response<T>(bittrex_api_result: BittrexAPIVecResult<T>) -> Result<T> {
+    if bittrex_api_result.success {
+        let mut result = bittrex_api_result.result.unwrap_or_default();
+        return match result.len() {
+            1 => Ok(result.remove(0)),
+            0 => Err(BittrexError::new(BittrexErrorType::NoResults, "Maybe check your parameters?".to_string())),
+            _ => Err(BittrexError::new(BittrexErrorType::Decoding, "Multiple results found! Maybe check your parameters?".to_string()))
         }
-        Err(BittrexError { error_type: BittrexErrorType::APIError, message: bittrex_api_result.message })
     }
+    Err(BittrexError::api(bittrex_api_result.message))
+}
 
-    fn to_hex_string(&self, bytes: &[u8]) -> String {
-        let strs: Vec<String> = bytes.iter()
-                                    .map(|b| format!("{:02X}", b))
-                                    .collect();
-        strs.join("")
-    }
-}
\ No newline at end of file
+fn read_response<T>(resp: HttpResponse) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
+    decode_response(resp.status, &resp.url, &resp.body)
+}
+
+/// Decodes the body of a response. Responses with an HTTP error status carry no API result and are turned into an error.
+pub(crate) fn decode_response<T>(status: StatusCode, url: &str, body: &str) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
+    if !status.is_success() {
+        return Err(BittrexError::new(BittrexErrorType::HttpStatus(status.as_u16()), format!("HTTP status {}", status))
+            .with_status(status.as_u16())
+            .with_url(url)
+            .with_body(body));
+    }
+    serde_json::from_str(body).map_err(|error| {
+        BittrexError::new(BittrexErrorType::Decoding, error.to_string())
+            .with_status(status.as_u16())
+            .with_url(url)
+            .with_body(body)
+            .with_source(error)
+    })
+}
+
+fn to_hex_string(bytes: &[u8]) -> String {
+    let strs: Vec<String> = bytes.iter()
+                                .map(|b| format!("{:02X}", b))
+                                .collect();
+    strs.join("")
+}
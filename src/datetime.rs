@@ -0,0 +1,102 @@
+use std::fmt;
+use std::error::Error as StdError;
+
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use serde::{Serializer, Deserialize, Deserializer};
+use serde::de;
+
+/// Format Bittrex uses for its timestamps. They are always UTC, but come without a timezone.
+const BITTREX_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// Other formats seen in the wild, tried after `BITTREX_FORMAT` and RFC 3339.
+const FALLBACK_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S%.f", "%m/%d/%Y %I:%M:%S %p"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDateTimeError {
+    pub value: String,
+}
+
+/// Parses a Bittrex timestamp into an UTC datetime.
+///
+/// Accepts timestamps with and without fractional seconds (`2014-07-09T07:19:30.15`,
+/// `2014-02-13T00:00:00`), which are treated as UTC, as well as RFC 3339 timestamps
+/// with an explicit offset (`2014-07-09T07:19:30Z`).
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate chrono;
+/// extern crate bittrex_api;
+///
+/// use chrono::Timelike;
+/// use bittrex_api::datetime;
+///
+/// # fn main() {
+/// let time_stamp = datetime::parse("2014-07-09T07:19:30.15").unwrap();
+///
+/// assert_eq!(time_stamp.nanosecond(), 150_000_000);
+/// # }
+/// ```
+pub fn parse(value: &str) -> Result<DateTime<Utc>, ParseDateTimeError> {
+    let value = value.trim();
+
+    if let Ok(naive) = NaiveDateTime::parse_from_str(value, BITTREX_FORMAT) {
+        return Ok(Utc.from_utc_datetime(&naive));
+    }
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date_time.with_timezone(&Utc));
+    }
+    FALLBACK_FORMATS.iter()
+        .filter_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .next()
+        .map(|naive| Utc.from_utc_datetime(&naive))
+        .ok_or_else(|| ParseDateTimeError { value: value.to_string() })
+}
+
+/// Formats a datetime the way Bittrex does (`2014-07-09T07:19:30.150`).
+pub fn format(date_time: &DateTime<Utc>) -> String {
+    date_time.format(BITTREX_FORMAT).to_string()
+}
+
+impl fmt::Display for ParseDateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown timestamp format '{}'", self.value)
+    }
+}
+
+impl StdError for ParseDateTimeError {
+    fn description(&self) -> &str {
+        "Unknown timestamp format"
+    }
+}
+
+/// Serde helpers for `DateTime<Utc>` fields, used with `#[serde(with = "datetime")]`.
+pub fn serialize<S>(date_time: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+    serializer.serialize_str(&format(date_time))
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error> where D: Deserializer<'de> {
+    let value = String::deserialize(deserializer)?;
+    parse(&value).map_err(de::Error::custom)
+}
+
+/// Serde helpers for `Option<DateTime<Utc>>` fields, used with `#[serde(with = "datetime::option")]`.
+pub mod option {
+    use chrono::{DateTime, Utc};
+    use serde::{Serializer, Deserialize, Deserializer};
+    use serde::de;
+
+    pub fn serialize<S>(date_time: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        match *date_time {
+            Some(ref date_time) => super::serialize(date_time, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error> where D: Deserializer<'de> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(value) => super::parse(&value).map(Some).map_err(de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
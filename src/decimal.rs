@@ -0,0 +1,449 @@
+use std::fmt;
+use std::error::Error as StdError;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg};
+use std::str::FromStr;
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser;
+use serde_json;
+
+/// Number of decimal places every `Decimal` is stored with (1 satoshi = 0.00000001).
+pub const DECIMAL_PLACES: u32 = 8;
+
+const SCALE: i64 = 100_000_000;
+
+/// Key under which serde_json passes the text of a number with `arbitrary_precision`.
+///
+/// The key is not part of serde_json's public API, but `serde_json::Number` itself serializes through it and it has
+/// not changed within serde_json 1.x. If it ever does, `visit_map` rejects numbers instead of decoding them inexactly.
+const NUMBER_TOKEN: &str = "$serde_json::private::Number";
+
+/// Fixed-point decimal number with 8 decimal places.
+///
+/// All prices, quantities and balances returned by or sent to the Bittrex API
+/// are represented as `Decimal`, so amounts are stored as an exact number of
+/// satoshis and arithmetic on them never drifts like `f32`/`f64` would.
+///
+/// # Examples
+///
+/// ```rust
+/// use bittrex_api::Decimal;
+///
+/// let price: Decimal = "0.00023".parse().unwrap();
+/// let quantity = Decimal::new(15, 1);
+///
+/// assert_eq!((price * quantity).to_string(), "0.000345");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decimal(i64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    Empty,
+    InvalidDigit,
+    TooManyDecimalPlaces,
+    Overflow,
+}
+
+impl Decimal {
+    /// Creates a decimal from a mantissa and a scale, i.e. `Decimal::new(123, 2)` is `1.23`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is greater than 8 or if the value does not fit.
+    pub fn new(mantissa: i64, scale: u32) -> Self {
+        assert!(scale <= DECIMAL_PLACES, "Decimal supports at most {} decimal places", DECIMAL_PLACES);
+        let factor = 10i64.pow(DECIMAL_PLACES - scale);
+        Decimal(mantissa.checked_mul(factor).expect("Decimal overflow"))
+    }
+
+    pub fn zero() -> Self {
+        Decimal(0)
+    }
+
+    /// Creates a decimal from a number of satoshis (units of 0.00000001).
+    pub fn from_satoshis(satoshis: i64) -> Self {
+        Decimal(satoshis)
+    }
+
+    /// Returns the value as a number of satoshis (units of 0.00000001).
+    pub fn satoshis(&self) -> i64 {
+        self.0
+    }
+
+    /// Converts a float to the nearest decimal. Returns `None` for NaN, infinity or out of range values.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        // The shortest representation of a float is the one that was parsed from the JSON,
+        // so going through it keeps values like 0.1 exact instead of 0.1000000000000000055...
+        Self::parse(&value.to_string(), true).ok()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.0 as f64 / SCALE as f64
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.0 > 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn abs(&self) -> Self {
+        Decimal(self.0.checked_abs().expect("Decimal overflow"))
+    }
+
+    pub fn checked_add(&self, other: Decimal) -> Option<Decimal> {
+        self.0.checked_add(other.0).map(Decimal)
+    }
+
+    pub fn checked_sub(&self, other: Decimal) -> Option<Decimal> {
+        self.0.checked_sub(other.0).map(Decimal)
+    }
+
+    /// Multiplies two decimals, rounding the result half away from zero to 8 decimal places.
+    pub fn checked_mul(&self, other: Decimal) -> Option<Decimal> {
+        let product = i128::from(self.0) * i128::from(other.0);
+        to_i64(div_round(product, i128::from(SCALE))).map(Decimal)
+    }
+
+    /// Divides two decimals, rounding the result half away from zero to 8 decimal places.
+    /// Returns `None` if `other` is zero.
+    pub fn checked_div(&self, other: Decimal) -> Option<Decimal> {
+        if other.0 == 0 {
+            return None;
+        }
+        let dividend = i128::from(self.0) * i128::from(SCALE);
+        to_i64(div_round(dividend, i128::from(other.0))).map(Decimal)
+    }
+
+    /// Rounds half away from zero to the given number of decimal places.
+    pub fn round_dp(&self, places: u32) -> Self {
+        if places >= DECIMAL_PLACES {
+            return *self;
+        }
+        let factor = i128::from(10i64.pow(DECIMAL_PLACES - places));
+        let rounded = div_round(i128::from(self.0), factor) * factor;
+        Decimal(to_i64(rounded).expect("Decimal overflow"))
+    }
+
+    /// Truncates towards zero to the given number of decimal places.
+    pub fn trunc_dp(&self, places: u32) -> Self {
+        if places >= DECIMAL_PLACES {
+            return *self;
+        }
+        let factor = 10i64.pow(DECIMAL_PLACES - places);
+        Decimal(self.0 / factor * factor)
+    }
+
+    fn parse(value: &str, round: bool) -> Result<Self, ParseDecimalError> {
+        let value = value.trim();
+        let (negative, digits) = match value.chars().next() {
+            Some('-') => (true, &value[1..]),
+            Some('+') => (false, &value[1..]),
+            Some(_) => (false, value),
+            None => return Err(ParseDecimalError::Empty),
+        };
+        let (mantissa, exponent) = match digits.find(&['e', 'E'][..]) {
+            Some(index) => (&digits[..index], digits[index + 1..].parse::<i32>().map_err(|_| ParseDecimalError::InvalidDigit)?),
+            None => (digits, 0),
+        };
+        let (integer, fraction) = match mantissa.find('.') {
+            Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+            None => (mantissa, ""),
+        };
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(ParseDecimalError::Empty);
+        }
+        if !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+            return Err(ParseDecimalError::InvalidDigit);
+        }
+        let (integer, fraction) = shift_point(integer, fraction, exponent, round)?;
+        let (integer, fraction) = (integer.as_str(), fraction.as_str());
+        if fraction.len() > DECIMAL_PLACES as usize && !round {
+            return Err(ParseDecimalError::TooManyDecimalPlaces);
+        }
+
+        let mut units: i128 = 0;
+        for c in integer.chars() {
+            units = units * 10 + i128::from(digit(c));
+            if units > i128::from(i64::max_value()) {
+                return Err(ParseDecimalError::Overflow);
+            }
+        }
+        for (index, c) in fraction.chars().enumerate() {
+            if index < DECIMAL_PLACES as usize {
+                units = units * 10 + i128::from(digit(c));
+            } else {
+                if index == DECIMAL_PLACES as usize && digit(c) >= 5 {
+                    units += 1;
+                }
+                break;
+            }
+        }
+        for _ in fraction.len()..DECIMAL_PLACES as usize {
+            units *= 10;
+        }
+
+        if negative {
+            units = -units;
+        }
+        to_i64(units).map(Decimal).ok_or(ParseDecimalError::Overflow)
+    }
+}
+
+/// Moves the decimal point of `integer.fraction` by `exponent` places, e.g. `1.5` and `-8` (`1.5E-8`) to `0.000000015`.
+/// Trailing zeros of the fraction are dropped, as they don't count as decimal places in scientific notation.
+fn shift_point(integer: &str, fraction: &str, exponent: i32, round: bool) -> Result<(String, String), ParseDecimalError> {
+    if exponent == 0 {
+        return Ok((integer.to_string(), fraction.to_string()));
+    }
+    let digits = format!("{}{}", integer, fraction.trim_end_matches('0'));
+    let significant = digits.trim_start_matches('0');
+    if significant.is_empty() {
+        return Ok(("0".to_string(), String::new()));
+    }
+
+    // Position of the decimal point relative to the first significant digit.
+    let point = integer.len() as i64 - (digits.len() - significant.len()) as i64 + i64::from(exponent);
+    if point > 20 {
+        return Err(ParseDecimalError::Overflow);
+    }
+    if point < -(DECIMAL_PLACES as i64) {
+        return if round { Ok(("0".to_string(), String::new())) } else { Err(ParseDecimalError::TooManyDecimalPlaces) };
+    }
+
+    Ok(if point <= 0 {
+        (String::new(), format!("{}{}", "0".repeat(-point as usize), significant))
+    } else if point as usize >= significant.len() {
+        (format!("{}{}", significant, "0".repeat(point as usize - significant.len())), String::new())
+    } else {
+        (significant[..point as usize].to_string(), significant[point as usize..].to_string())
+    })
+}
+
+fn digit(c: char) -> u32 {
+    c.to_digit(10).unwrap_or(0)
+}
+
+fn to_i64(value: i128) -> Option<i64> {
+    if value > i128::from(i64::max_value()) || value < i128::from(i64::min_value()) {
+        return None;
+    }
+    Some(value as i64)
+}
+
+fn div_round(dividend: i128, divisor: i128) -> i128 {
+    let quotient = dividend / divisor;
+    let remainder = dividend % divisor;
+    if remainder.abs() * 2 >= divisor.abs() {
+        if (dividend < 0) != (divisor < 0) { quotient - 1 } else { quotient + 1 }
+    } else {
+        quotient
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Decimal::parse(value, false)
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Decimal::new(value, 0)
+    }
+}
+
+impl From<u32> for Decimal {
+    fn from(value: u32) -> Self {
+        Decimal::new(i64::from(value), 0)
+    }
+}
+
+impl Add for Decimal {
+    type Output = Decimal;
+
+    fn add(self, other: Decimal) -> Decimal {
+        self.checked_add(other).expect("Decimal overflow")
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Decimal;
+
+    fn sub(self, other: Decimal) -> Decimal {
+        self.checked_sub(other).expect("Decimal overflow")
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Decimal;
+
+    fn mul(self, other: Decimal) -> Decimal {
+        self.checked_mul(other).expect("Decimal overflow")
+    }
+}
+
+impl Div for Decimal {
+    type Output = Decimal;
+
+    fn div(self, other: Decimal) -> Decimal {
+        assert!(!other.is_zero(), "Decimal division by zero");
+        self.checked_div(other).expect("Decimal overflow")
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal(self.0.checked_neg().expect("Decimal overflow"))
+    }
+}
+
+impl AddAssign for Decimal {
+    fn add_assign(&mut self, other: Decimal) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Decimal {
+    fn sub_assign(&mut self, other: Decimal) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for Decimal {
+    fn mul_assign(&mut self, other: Decimal) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for Decimal {
+    fn div_assign(&mut self, other: Decimal) {
+        *self = *self / other;
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Decimal>>(iter: I) -> Decimal {
+        iter.fold(Decimal::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Decimal> for Decimal {
+    fn sum<I: Iterator<Item = &'a Decimal>>(iter: I) -> Decimal {
+        iter.fold(Decimal::zero(), |sum, value| sum + *value)
+    }
+}
+
+/// Formats without trailing zeros (`1.5`), or with exactly the requested precision (`{:.8}` gives `1.50000000`).
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match f.precision() {
+            Some(precision) => self.round_dp(precision as u32),
+            None => *self,
+        };
+        let units = i128::from(value.0).abs();
+        let integer = units / i128::from(SCALE);
+        let mut fraction = format!("{:08}", units % i128::from(SCALE));
+
+        match f.precision() {
+            Some(precision) if precision <= DECIMAL_PLACES as usize => fraction.truncate(precision),
+            Some(precision) => fraction.extend((DECIMAL_PLACES as usize..precision).map(|_| '0')),
+            None => {
+                let trimmed = fraction.trim_end_matches('0').len();
+                fraction.truncate(trimmed);
+            }
+        }
+
+        let digits = if fraction.is_empty() { integer.to_string() } else { format!("{}.{}", integer, fraction) };
+        f.pad_integral(value.0 >= 0, "", &digits)
+    }
+}
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseDecimalError::Empty => write!(f, "Cannot parse decimal from empty string"),
+            ParseDecimalError::InvalidDigit => write!(f, "Invalid digit found in decimal"),
+            ParseDecimalError::TooManyDecimalPlaces => write!(f, "Decimal has more than {} decimal places", DECIMAL_PLACES),
+            ParseDecimalError::Overflow => write!(f, "Decimal is too large"),
+        }
+    }
+}
+
+impl StdError for ParseDecimalError {
+    fn description(&self) -> &str {
+        "Error while parsing decimal"
+    }
+}
+
+/// Serialized as a JSON number with all decimal places, e.g. `12345678.12345678`.
+impl Serialize for Decimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let number: serde_json::Number = self.to_string().parse().map_err(ser::Error::custom)?;
+        number.serialize(serializer)
+    }
+}
+
+/// Deserialized from the text of a JSON number or from a numeric string, rounded to 8 decimal places.
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
+struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal number")
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Decimal, E> where E: de::Error {
+        value.checked_mul(SCALE).map(Decimal).ok_or_else(|| E::custom(ParseDecimalError::Overflow))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Decimal, E> where E: de::Error {
+        if value > i64::max_value() as u64 {
+            return Err(E::custom(ParseDecimalError::Overflow));
+        }
+        self.visit_i64(value as i64)
+    }
+
+    /// Only reached from a `serde_json::Value` whose number text is the shortest form of the float, which `from_f64` keeps exact.
+    fn visit_f64<E>(self, value: f64) -> Result<Decimal, E> where E: de::Error {
+        Decimal::from_f64(value).ok_or_else(|| E::custom(format!("Invalid decimal value {}", value)))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Decimal, E> where E: de::Error {
+        Decimal::parse(value, true).map_err(E::custom)
+    }
+
+    /// JSON numbers arrive as their text, wrapped in a map with a single private key (`arbitrary_precision`).
+    fn visit_map<A>(self, mut map: A) -> Result<Decimal, A::Error> where A: MapAccess<'de> {
+        match map.next_key::<String>()? {
+            Some(ref key) if key == NUMBER_TOKEN => {
+                let value: String = map.next_value()?;
+                Decimal::parse(&value, true).map_err(de::Error::custom)
+            },
+            _ => Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Requests of the API endpoints, shared by `BittrexClient` and `AsyncBittrexClient`.
+//!
+//! Parameter values are percent-encoded and kept in the order the endpoint adds them, so the url of a call
+//! is always the same. Private calls append the api key and nonce as the last parameters and are signed over
+//! exactly the url which is sent.
+use std::fmt::{Display, Write};
+
+use decimal::Decimal;
+use market::{Currency, Market};
+use values::{BittrexOrderType, TickInterval};
+
+/// Url of an endpoint with its query parameters.
+#[derive(Clone, Debug)]
+pub struct Request {
+    url: String,
+    params: Vec<(&'static str, String)>,
+}
+
+impl Request {
+    pub fn new(api_url: &str, path: &str) -> Self {
+        Request { url: format!("{}{}", api_url, path), params: Vec::new() }
+    }
+
+    pub fn param<T: Display>(mut self, name: &'static str, value: T) -> Self {
+        self.params.push((name, value.to_string()));
+        self
+    }
+
+    /// The url with the encoded query, for public endpoints.
+    pub fn url(&self) -> String {
+        self.build(&self.params)
+    }
+
+    /// The url with the api key and nonce appended, for private endpoints.
+    pub fn signed_url(&self, api_key: &str, nonce: u64) -> String {
+        let mut params = self.params.clone();
+        params.push(("apikey", api_key.to_string()));
+        params.push(("nonce", nonce.to_string()));
+        self.build(&params)
+    }
+
+    fn build(&self, params: &[(&'static str, String)]) -> String {
+        let mut url = self.url.clone();
+        for (index, &(name, ref value)) in params.iter().enumerate() {
+            url.push(if index == 0 { '?' } else { '&' });
+            url.push_str(name);
+            url.push('=');
+            encode(&mut url, value);
+        }
+        url
+    }
+}
+
+/// Appends `value` to `url`, percent-encoding all characters except the unreserved ones of RFC 3986.
+pub fn encode(url: &mut String, value: &str) {
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => url.push(byte as char),
+            _ => write!(url, "%{:02X}", byte).expect("Writing to a String should not fail"),
+        }
+    }
+}
+
+pub fn get_markets(api_url: &str) -> Request {
+    Request::new(api_url, "/public/getmarkets")
+}
+
+pub fn get_currencies(api_url: &str) -> Request {
+    Request::new(api_url, "/public/getcurrencies")
+}
+
+pub fn get_ticker(api_url: &str, market: &Market) -> Request {
+    Request::new(api_url, "/public/getticker")
+        .param("market", market)
+}
+
+pub fn get_market_summaries(api_url: &str) -> Request {
+    Request::new(api_url, "/public/getmarketsummaries")
+}
+
+pub fn get_market_summary(api_url: &str, market: &Market) -> Request {
+    Request::new(api_url, "/public/getmarketsummary")
+        .param("market", market)
+}
+
+pub fn get_order_book(api_url: &str, market: &Market, book_type: BittrexOrderType) -> Request {
+    Request::new(api_url, "/public/getorderbook")
+        .param("market", market)
+        .param("type", book_type)
+}
+
+pub fn get_market_history(api_url: &str, market: &Market) -> Request {
+    Request::new(api_url, "/public/getmarkethistory")
+        .param("market", market)
+}
+
+pub fn get_ticks(v2_api_url: &str, market: &Market, tick_interval: TickInterval) -> Request {
+    Request::new(v2_api_url, "/pub/market/GetTicks")
+        .param("marketName", market)
+        .param("tickInterval", tick_interval)
+}
+
+pub fn get_latest_tick(v2_api_url: &str, market: &Market, tick_interval: TickInterval) -> Request {
+    Request::new(v2_api_url, "/pub/market/GetLatestTick")
+        .param("marketName", market)
+        .param("tickInterval", tick_interval)
+}
+
+pub fn get_open_orders(api_url: &str) -> Request {
+    Request::new(api_url, "/market/getopenorders")
+}
+
+pub fn get_open_orders_by_market(api_url: &str, market: &Market) -> Request {
+    Request::new(api_url, "/market/getopenorders")
+        .param("market", market)
+}
+
+pub fn get_order(api_url: &str, order_id: &str) -> Request {
+    Request::new(api_url, "/account/getorder")
+        .param("uuid", order_id)
+}
+
+pub fn get_order_history(api_url: &str) -> Request {
+    Request::new(api_url, "/account/getorderhistory")
+}
+
+pub fn get_order_history_by_market(api_url: &str, market: &Market) -> Request {
+    Request::new(api_url, "/account/getorderhistory")
+        .param("market", market)
+}
+
+pub fn get_withdrawal_history(api_url: &str) -> Request {
+    Request::new(api_url, "/account/getwithdrawalhistory")
+}
+
+pub fn get_withdrawal_history_by_currency(api_url: &str, currency: &Currency) -> Request {
+    Request::new(api_url, "/account/getwithdrawalhistory")
+        .param("currency", currency)
+}
+
+pub fn get_deposit_history(api_url: &str) -> Request {
+    Request::new(api_url, "/account/getdeposithistory")
+}
+
+pub fn get_deposit_history_by_currency(api_url: &str, currency: &Currency) -> Request {
+    Request::new(api_url, "/account/getdeposithistory")
+        .param("currency", currency)
+}
+
+pub fn get_balances(api_url: &str) -> Request {
+    Request::new(api_url, "/account/getbalances")
+}
+
+pub fn get_balance(api_url: &str, currency: &Currency) -> Request {
+    Request::new(api_url, "/account/getbalance")
+        .param("currency", currency)
+}
+
+pub fn get_deposit_address(api_url: &str, currency: &Currency) -> Request {
+    Request::new(api_url, "/account/getdepositaddress")
+        .param("currency", currency)
+}
+
+pub fn withdraw(api_url: &str, currency: &Currency, quantity: Decimal, address: &str, payment_id: &str) -> Request {
+    Request::new(api_url, "/account/withdraw")
+        .param("currency", currency)
+        .param("quantity", quantity)
+        .param("address", address)
+        .param("paymentid", payment_id)
+}
+
+pub fn buy_limit(api_url: &str, market: &Market, quantity: Decimal, rate: Decimal) -> Request {
+    Request::new(api_url, "/market/buylimit")
+        .param("market", market)
+        .param("quantity", quantity)
+        .param("rate", rate)
+}
+
+pub fn sell_limit(api_url: &str, market: &Market, quantity: Decimal, rate: Decimal) -> Request {
+    Request::new(api_url, "/market/selllimit")
+        .param("market", market)
+        .param("quantity", quantity)
+        .param("rate", rate)
+}
+
+pub fn cancel_order(api_url: &str, order_id: &str) -> Request {
+    Request::new(api_url, "/market/cancel")
+        .param("uuid", order_id)
+}
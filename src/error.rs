@@ -4,92 +4,317 @@ use std::error::Error as StdError;
 use reqwest::Error as ReqwestError;
 use serde_json;
 
+/// Maximum number of characters of a response body kept in an error.
+const MAX_BODY_LENGTH: usize = 1024;
+
+/// Error of a failed call.
+///
+/// Besides its type and message it carries, where available, the HTTP status and the url of the call
+/// (with the api key redacted), a truncated copy of the response body and the underlying error as `source`.
 #[derive(Debug)]
 pub struct BittrexError {
     pub error_type: BittrexErrorType,
     pub message: String,
+    status: Option<u16>,
+    url: Option<String>,
+    body: Option<String>,
+    source: Option<Box<dyn StdError + Send + Sync>>,
 }
 
-#[derive(Debug)]
+/// Kind of a failed call.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BittrexErrorType {
-    APIError,
-    JsonError,
+    /// The request could not be sent or no response was received (connection errors, timeouts, redirect loops).
+    Transport,
+    /// Bittrex responded with an HTTP status which carries no API result.
+    HttpStatus(u16),
+    /// The response could not be decoded.
+    Decoding,
+    /// Bittrex rejected the call (`success: false`) with the given message code.
+    Api(ApiErrorCode),
+    /// The call succeeded, but returned no result.
     NoResults,
+    /// The client-side rate limiter had no free slot.
+    RateLimited,
+    /// The client is misconfigured, e.g. with an invalid default header.
+    Configuration,
+    /// The hub of the websocket API rejected an invocation, e.g. a subscription.
+    Hub,
+}
+
+/// Message codes Bittrex returns for rejected calls.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiErrorCode {
+    InvalidMarket,
+    InvalidCurrency,
+    InvalidOrder,
+    InvalidOrderType,
+    InvalidPermission,
+    InvalidSignature,
+    InvalidAddress,
+    UuidInvalid,
+    ApikeyInvalid,
+    ApikeyNotProvided,
+    ApisignNotProvided,
+    NonceNotProvided,
+    NonceUsed,
+    MarketNotProvided,
+    CurrencyNotProvided,
+    QuantityNotProvided,
+    RateNotProvided,
+    InsufficientFunds,
+    MinTradeRequirementNotMet,
+    DustTradeDisallowed,
+    RatePrecisionNotAllowed,
+    ZeroOrNegativeNotAllowed,
+    OrderNotOpen,
+    WithdrawalTooSmall,
+    AddressGenerating,
+    WhitelistViolationIp,
+    MarketOffline,
+    Unknown(String),
+}
+
+const API_ERROR_CODES: &[(&str, ApiErrorCode)] = &[
+    ("INVALID_MARKET", ApiErrorCode::InvalidMarket),
+    ("INVALID_CURRENCY", ApiErrorCode::InvalidCurrency),
+    ("INVALID_ORDER", ApiErrorCode::InvalidOrder),
+    ("INVALID_ORDER_TYPE", ApiErrorCode::InvalidOrderType),
+    ("INVALID_PERMISSION", ApiErrorCode::InvalidPermission),
+    ("INVALID_SIGNATURE", ApiErrorCode::InvalidSignature),
+    ("INVALID_ADDRESS", ApiErrorCode::InvalidAddress),
+    ("UUID_INVALID", ApiErrorCode::UuidInvalid),
+    ("APIKEY_INVALID", ApiErrorCode::ApikeyInvalid),
+    ("APIKEY_NOT_PROVIDED", ApiErrorCode::ApikeyNotProvided),
+    ("APISIGN_NOT_PROVIDED", ApiErrorCode::ApisignNotProvided),
+    ("NONCE_NOT_PROVIDED", ApiErrorCode::NonceNotProvided),
+    ("NONCE_USED", ApiErrorCode::NonceUsed),
+    ("MARKET_NOT_PROVIDED", ApiErrorCode::MarketNotProvided),
+    ("CURRENCY_NOT_PROVIDED", ApiErrorCode::CurrencyNotProvided),
+    ("QUANTITY_NOT_PROVIDED", ApiErrorCode::QuantityNotProvided),
+    ("RATE_NOT_PROVIDED", ApiErrorCode::RateNotProvided),
+    ("INSUFFICIENT_FUNDS", ApiErrorCode::InsufficientFunds),
+    ("MIN_TRADE_REQUIREMENT_NOT_MET", ApiErrorCode::MinTradeRequirementNotMet),
+    ("DUST_TRADE_DISALLOWED_MIN_VALUE_50K_SAT", ApiErrorCode::DustTradeDisallowed),
+    ("RATE_PRECISION_NOT_ALLOWED", ApiErrorCode::RatePrecisionNotAllowed),
+    ("ZERO_OR_NEGATIVE_NOT_ALLOWED", ApiErrorCode::ZeroOrNegativeNotAllowed),
+    ("ORDER_NOT_OPEN", ApiErrorCode::OrderNotOpen),
+    ("WITHDRAWAL_TOO_SMALL", ApiErrorCode::WithdrawalTooSmall),
+    ("ADDRESS_GENERATING", ApiErrorCode::AddressGenerating),
+    ("WHITELIST_VIOLATION_IP", ApiErrorCode::WhitelistViolationIp),
+    ("MARKET_OFFLINE", ApiErrorCode::MarketOffline),
+];
+
+impl ApiErrorCode {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            ApiErrorCode::Unknown(ref message) => message,
+            ref code => API_ERROR_CODES.iter()
+                .find(|&&(_, ref known)| known == code)
+                .map(|&(message, _)| message)
+                .expect("Known code should have a message!"),
+        }
+    }
+
+    /// Whether the same call may succeed when repeated later.
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            ApiErrorCode::NonceUsed | ApiErrorCode::AddressGenerating | ApiErrorCode::MarketOffline => true,
+            _ => false,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for ApiErrorCode {
+    fn from(message: &'a str) -> Self {
+        API_ERROR_CODES.iter()
+            .find(|&&(known, _)| known == message)
+            .map(|&(_, ref code)| code.clone())
+            .unwrap_or_else(|| ApiErrorCode::Unknown(message.to_string()))
+    }
+}
+
+impl fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl BittrexErrorType {
+    /// Whether the failed call may succeed when repeated later.
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            BittrexErrorType::Transport | BittrexErrorType::RateLimited => true,
+            BittrexErrorType::HttpStatus(status) => status >= 500 || status == 429,
+            BittrexErrorType::Api(ref code) => code.is_retryable(),
+            BittrexErrorType::Decoding | BittrexErrorType::NoResults | BittrexErrorType::Configuration | BittrexErrorType::Hub => false,
+        }
+    }
+}
+
+impl BittrexError {
+    pub fn new(error_type: BittrexErrorType, message: String) -> Self {
+        BittrexError { error_type: error_type, message: message, status: None, url: None, body: None, source: None }
+    }
+
+    /// Creates the error for a call Bittrex rejected with the given message.
+    pub fn api(message: String) -> Self {
+        BittrexError::new(BittrexErrorType::Api(ApiErrorCode::from(message.as_str())), message)
+    }
+
+    /// Adds the HTTP status of the response.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Adds the url of the call. The api key is redacted.
+    pub fn with_url(mut self, url: &str) -> Self {
+        self.url = Some(redact_url(url));
+        self
+    }
+
+    /// Adds the response body, truncated to 1024 characters.
+    pub fn with_body(mut self, body: &str) -> Self {
+        self.body = Some(truncate(body));
+        self
+    }
+
+    pub fn with_source<E>(mut self, source: E) -> Self where E: Into<Box<dyn StdError + Send + Sync>> {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Returns the HTTP status of the response, if one was received.
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    /// Returns the url of the call with the api key redacted.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_ref().map(|url| url.as_str())
+    }
+
+    /// Returns the (truncated) response body, if one was received.
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_ref().map(|body| body.as_str())
+    }
+
+    /// Whether the failed call may succeed when repeated later.
+    ///
+    /// Note that repeating a call which places an order or withdraws funds after a transport error
+    /// may execute it twice, if the first attempt reached Bittrex.
+    pub fn is_retryable(&self) -> bool {
+        self.error_type.is_retryable()
+    }
+
+    /// Returns the Bittrex message code, if Bittrex rejected the call.
+    pub fn api_code(&self) -> Option<&ApiErrorCode> {
+        match self.error_type {
+            BittrexErrorType::Api(ref code) => Some(code),
+            _ => None,
+        }
+    }
 }
 
 impl StdError for BittrexError {
     fn description(&self) -> &str {
         match self.error_type {
-            BittrexErrorType::APIError => "Error while calling Bittrex API",
-            BittrexErrorType::JsonError => "Error while converting response to Json Value",
+            BittrexErrorType::Transport => "Error while sending request to Bittrex API",
+            BittrexErrorType::HttpStatus(_) => "Unexpected HTTP status from Bittrex API",
+            BittrexErrorType::Decoding => "Error while decoding response of Bittrex API",
+            BittrexErrorType::Api(_) => "Bittrex API rejected the call",
             BittrexErrorType::NoResults => "No results found",
+            BittrexErrorType::RateLimited => "Client-side rate limit exceeded",
+            BittrexErrorType::Configuration => "Invalid client configuration",
+            BittrexErrorType::Hub => "Websocket hub rejected the invocation",
         }
     }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source.as_ref().map(|source| &**source as &(dyn StdError + 'static))
+    }
 }
 
 impl fmt::Display for BittrexError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.error_type {
-            BittrexErrorType::APIError => write!(f, "{}: {}", self.description(), self.message),
-            BittrexErrorType::JsonError => write!(f, "{}: {}", self.description(), self.message),
-            BittrexErrorType::NoResults => write!(f, "{} ({})!", self.description(), self.message),
+            BittrexErrorType::NoResults => write!(f, "{} ({})!", self.description(), self.message)?,
+            _ => write!(f, "{}: {}", self.description(), self.message)?,
         }
+        if let Some(ref url) = self.url {
+            write!(f, " [{}]", url)?;
+        }
+        Ok(())
     }
 }
 
 impl From<serde_json::Error> for BittrexError {
     fn from(error: serde_json::Error) -> Self {
-        BittrexError {
-            error_type: BittrexErrorType::JsonError,
-            message: error.description().to_string(),
-        }
+        BittrexError::new(BittrexErrorType::Decoding, error.to_string()).with_source(error)
     }
 }
 
 impl From<ReqwestError> for BittrexError {
     fn from(error: ReqwestError) -> Self {
-        let mut err: Option<BittrexError> = None;
-
-        if error.is_http() {
-            err = match error.url() {
-                Some(url) => Some(BittrexError {
-                    error_type: BittrexErrorType::APIError,
-                    message: format!("Problem making request to: {}", url),
-                }),
-                None => Some(BittrexError {
-                    error_type: BittrexErrorType::APIError,
-                    message: "No Url given".to_string(),
-                }),
-            }
-        }
+        let error_type = if error.is_serialization() {
+            BittrexErrorType::Decoding
+        } else if let Some(status) = error.status() {
+            BittrexErrorType::HttpStatus(status.as_u16())
+        } else {
+            BittrexErrorType::Transport
+        };
 
-        if error.is_serialization() {
-            err = match error.get_ref() {
-                Some(err) => Some(BittrexError {
-                    error_type: BittrexErrorType::APIError,
-                    message: format!("Problem parsing information {}", err),
-                }),
-                None => Some(BittrexError {
-                    error_type: BittrexErrorType::APIError,
-                    message: "Problem parsing information (no info given)".to_string(),
-                }),
-            }
+        let mut err = BittrexError::new(error_type, redact_url(&error.to_string()));
+        if let Some(status) = error.status() {
+            err = err.with_status(status.as_u16());
         }
-
-        if error.is_redirect() {
-            err = Some(BittrexError {
-                error_type: BittrexErrorType::APIError,
-                message: "Server redirecting too many times or making loop".to_string(),
-            });
+        if let Some(url) = error.url() {
+            err = err.with_url(url.as_str());
         }
+        err.with_source(RedactedReqwestError(error))
+    }
+}
 
-        if err.is_none() {
-            err = Some(BittrexError {
-                error_type: BittrexErrorType::APIError,
-                message: "Error undefined!".to_string(),
-            });
-        }
+/// Wraps a reqwest error, whose message contains the url of the call, to redact the api key.
+#[derive(Debug)]
+struct RedactedReqwestError(ReqwestError);
+
+impl fmt::Display for RedactedReqwestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&redact_url(&self.0.to_string()))
+    }
+}
+
+impl StdError for RedactedReqwestError {
+    fn description(&self) -> &str {
+        "Error while sending request"
+    }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.get_ref().map(|source| source as &(dyn StdError + 'static))
+    }
+}
+
+/// Replaces the value of the `apikey` parameter in a url (or any text containing one) with `REDACTED`.
+pub fn redact_url(url: &str) -> String {
+    let mut redacted = String::with_capacity(url.len());
+    let mut rest = url;
+    while let Some(index) = rest.find("apikey=") {
+        let value_start = index + "apikey=".len();
+        redacted.push_str(&rest[..value_start]);
+        redacted.push_str("REDACTED");
+        rest = &rest[value_start..];
+        let value_end = rest.find(|c: char| c == '&' || c == '#' || c.is_whitespace()).unwrap_or(rest.len());
+        rest = &rest[value_end..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
 
-        err.expect("Error should exist!")
+fn truncate(body: &str) -> String {
+    match body.char_indices().nth(MAX_BODY_LENGTH) {
+        Some((index, _)) => format!("{}...", &body[..index]),
+        None => body.to_string(),
     }
 }
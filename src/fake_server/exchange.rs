@@ -0,0 +1,321 @@
+//! In-memory state of the fake exchange: markets with their order books, accounts and orders.
+use std::cmp::{self, Reverse};
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+
+use decimal::Decimal;
+use market::{Currency, Market};
+use simulation::{self, Balances, Order};
+use values::*;
+
+pub type ApiResult<T> = Result<T, &'static str>;
+
+pub struct Exchange {
+    markets: BTreeMap<Market, MarketState>,
+    accounts: HashMap<String, Account>,
+    orders: HashMap<String, Order>,
+    /// Api key of the account owning each order.
+    owners: HashMap<String, String>,
+    next_id: u64,
+}
+
+struct MarketState {
+    min_trade_size: Decimal,
+    created: DateTime<Utc>,
+    bids: BTreeMap<(Reverse<Decimal>, u64), String>,
+    asks: BTreeMap<(Decimal, u64), String>,
+    trades: Vec<BittrexTrade>,
+}
+
+pub struct Account {
+    pub api_secret: String,
+    pub last_nonce: Option<u64>,
+    balances: Balances,
+    order_uuids: Vec<String>,
+}
+
+impl Exchange {
+    pub fn new() -> Self {
+        Exchange { markets: BTreeMap::new(), accounts: HashMap::new(), orders: HashMap::new(), owners: HashMap::new(), next_id: 1 }
+    }
+
+    pub fn add_market(&mut self, market: Market, min_trade_size: Decimal) {
+        self.markets.entry(market).or_insert_with(|| MarketState {
+            min_trade_size: min_trade_size,
+            created: Utc::now(),
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            trades: Vec::new(),
+        });
+    }
+
+    pub fn add_account(&mut self, api_key: &str, api_secret: &str) {
+        self.accounts.insert(api_key.to_string(), Account {
+            api_secret: api_secret.to_string(),
+            last_nonce: None,
+            balances: Balances::new(),
+            order_uuids: Vec::new(),
+        });
+    }
+
+    pub fn account_mut(&mut self, api_key: &str) -> Option<&mut Account> {
+        self.accounts.get_mut(api_key)
+    }
+
+    pub fn deposit(&mut self, api_key: &str, currency: &Currency, amount: Decimal) {
+        let account = self.accounts.get_mut(api_key).expect("Account should exist");
+        let balance = account.balances.entry(currency.clone()).or_default();
+        balance.total += amount;
+        balance.available += amount;
+    }
+
+    pub fn get_markets(&self) -> Vec<BittrexMarket> {
+        self.markets.iter()
+            .map(|(market, state)| BittrexMarket {
+                market_currency: market.quote.clone(),
+                base_currency: market.base.clone(),
+                market_currency_long: market.quote.to_string(),
+                base_currency_long: market.base.to_string(),
+                min_trade_size: state.min_trade_size,
+                market_name: market.clone(),
+                is_active: true,
+                created: state.created,
+            })
+            .collect()
+    }
+
+    pub fn get_ticker(&self, market: &Market) -> ApiResult<BittrexTicker> {
+        let state = self.market(market)?;
+        Ok(BittrexTicker {
+            bid: state.bids.values().next().map(|uuid| self.orders[uuid].limit).unwrap_or_default(),
+            ask: state.asks.values().next().map(|uuid| self.orders[uuid].limit).unwrap_or_default(),
+            last: state.trades.last().map(|trade| trade.price).unwrap_or_default(),
+        })
+    }
+
+    pub fn get_order_book(&self, market: &Market) -> ApiResult<BittrexPublicOrderBook> {
+        let state = self.market(market)?;
+        Ok(BittrexPublicOrderBook {
+            buy: self.aggregate(state.bids.values()),
+            sell: self.aggregate(state.asks.values()),
+        })
+    }
+
+    pub fn get_market_history(&self, market: &Market) -> ApiResult<Vec<BittrexTrade>> {
+        let state = self.market(market)?;
+        Ok(state.trades.iter().rev().take(100).map(copy_trade).collect())
+    }
+
+    pub fn get_balances(&self, api_key: &str) -> Vec<BittrexBalance> {
+        self.accounts[api_key].balances.iter()
+            .map(|(currency, balance)| simulation::to_bittrex_balance(currency, *balance))
+            .collect()
+    }
+
+    pub fn get_balance(&self, api_key: &str, currency: &Currency) -> BittrexBalance {
+        let balance = self.accounts.get(api_key)
+            .and_then(|account| account.balances.get(currency))
+            .cloned()
+            .unwrap_or_default();
+        simulation::to_bittrex_balance(currency, balance)
+    }
+
+    pub fn get_open_orders(&self, api_key: &str, market: Option<&Market>) -> Vec<BittrexOpenOrder> {
+        self.account_orders(api_key)
+            .filter(|order| order.is_open() && market.map(|market| order.market == *market).unwrap_or(true))
+            .map(Order::to_open_order)
+            .collect()
+    }
+
+    pub fn get_order_history(&self, api_key: &str, market: Option<&Market>) -> Vec<BittrexHistoryOrder> {
+        simulation::order_history(self.account_orders(api_key), market)
+    }
+
+    pub fn get_order(&self, api_key: &str, uuid: &str) -> ApiResult<BittrexOrder> {
+        match self.orders.get(uuid) {
+            Some(order) if self.owners[uuid] == api_key => Ok(order.to_order()),
+            _ => Err("INVALID_ORDER"),
+        }
+    }
+
+    /// Places a limit order, matches it against the book and rests the remaining quantity.
+    pub fn place_limit(&mut self, api_key: &str, market: &Market, side: BittrexOrderSide, quantity: Decimal, rate: Decimal) -> ApiResult<String> {
+        simulation::validate_order(quantity, rate, self.market(market)?.min_trade_size)?;
+
+        let order = Order::new(self.next_id, market, side, quantity, rate);
+        let account = self.accounts.get_mut(api_key).expect("Account should exist");
+        order.reserve(&mut account.balances)?;
+        self.next_id += 1;
+
+        let uuid = order.uuid.clone();
+        account.order_uuids.push(uuid.clone());
+        self.owners.insert(uuid.clone(), api_key.to_string());
+        self.orders.insert(uuid.clone(), order);
+
+        self.match_order(&uuid);
+        Ok(uuid)
+    }
+
+    pub fn cancel(&mut self, api_key: &str, uuid: &str) -> ApiResult<()> {
+        let (market, sequence, limit, is_buy) = match self.orders.get(uuid) {
+            Some(order) if self.owners[uuid] == api_key && order.is_open() => (order.market.clone(), order.sequence, order.limit, order.is_buy()),
+            Some(_) if self.owners[uuid] == api_key => return Err("ORDER_NOT_OPEN"),
+            _ => return Err("INVALID_ORDER"),
+        };
+
+        let state = self.markets.get_mut(&market).expect("Market of an order should exist");
+        if is_buy {
+            state.bids.remove(&(Reverse(limit), sequence));
+        } else {
+            state.asks.remove(&(limit, sequence));
+        }
+        self.orders.get_mut(uuid).expect("Order should exist").cancel_initiated = true;
+        self.close(uuid);
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, api_key: &str, currency: &Currency, quantity: Decimal, address: &str) -> ApiResult<String> {
+        if address.is_empty() {
+            return Err("INVALID_ADDRESS");
+        }
+        if !quantity.is_positive() {
+            return Err("ZERO_OR_NEGATIVE_NOT_ALLOWED");
+        }
+        {
+            let account = self.accounts.get_mut(api_key).expect("Account should exist");
+            let balance = match account.balances.get_mut(currency) {
+                Some(balance) if balance.available >= quantity => balance,
+                _ => return Err("INSUFFICIENT_FUNDS"),
+            };
+            balance.total -= quantity;
+            balance.available -= quantity;
+        }
+        Ok(format!("00000000-0000-4000-9000-{:012x}", self.next_id()))
+    }
+
+    pub fn is_known_currency(&self, currency: &Currency) -> bool {
+        self.markets.keys().any(|market| market.base == *currency || market.quote == *currency)
+    }
+
+    fn market(&self, market: &Market) -> ApiResult<&MarketState> {
+        self.markets.get(market).ok_or("INVALID_MARKET")
+    }
+
+    fn account_orders<'a>(&'a self, api_key: &str) -> Box<dyn Iterator<Item = &'a Order> + 'a> {
+        match self.accounts.get(api_key) {
+            Some(account) => Box::new(account.order_uuids.iter().map(move |uuid| &self.orders[uuid])),
+            None => Box::new(None.into_iter()),
+        }
+    }
+
+    /// Sums up the remaining quantities of orders with the same rate.
+    fn aggregate<'a, I>(&self, uuids: I) -> Vec<BittrexPublicOrder> where I: Iterator<Item = &'a String> {
+        let mut levels: Vec<BittrexPublicOrder> = Vec::new();
+        for uuid in uuids {
+            let order = &self.orders[uuid];
+            match levels.last_mut() {
+                Some(ref mut level) if level.rate == order.limit => {
+                    level.quantity += order.quantity_remaining;
+                    continue;
+                },
+                _ => {},
+            }
+            levels.push(BittrexPublicOrder { quantity: order.quantity_remaining, rate: order.limit });
+        }
+        levels
+    }
+
+    /// Fills the order against the opposite side of the book at the rates of the resting orders,
+    /// best rate first and older orders first within a rate. The remaining quantity rests in the book.
+    fn match_order(&mut self, uuid: &str) {
+        let (market, is_buy, limit, sequence) = {
+            let order = &self.orders[uuid];
+            (order.market.clone(), order.is_buy(), order.limit, order.sequence)
+        };
+
+        while self.orders[uuid].quantity_remaining.is_positive() {
+            let maker_uuid = {
+                let state = &self.markets[&market];
+                let best = if is_buy { state.asks.values().next() } else { state.bids.values().next() };
+                match best {
+                    Some(maker_uuid) if self.orders[uuid].accepts(self.orders[maker_uuid].limit) => maker_uuid.clone(),
+                    _ => break,
+                }
+            };
+
+            let (maker_remaining, maker_rate, maker_sequence) = {
+                let maker = &self.orders[&maker_uuid];
+                (maker.quantity_remaining, maker.limit, maker.sequence)
+            };
+            let quantity = cmp::min(self.orders[uuid].quantity_remaining, maker_remaining);
+            self.fill(uuid, quantity, maker_rate);
+            self.fill(&maker_uuid, quantity, maker_rate);
+
+            let maker_filled = !self.orders[&maker_uuid].quantity_remaining.is_positive();
+            self.record_trade(&market, quantity, maker_rate, maker_filled, if is_buy { BittrexOrderSide::Buy } else { BittrexOrderSide::Sell });
+            if maker_filled {
+                let state = self.markets.get_mut(&market).expect("Market of an order should exist");
+                if is_buy {
+                    state.asks.remove(&(maker_rate, maker_sequence));
+                } else {
+                    state.bids.remove(&(Reverse(maker_rate), maker_sequence));
+                }
+                self.close(&maker_uuid);
+            }
+        }
+
+        if self.orders[uuid].quantity_remaining.is_positive() {
+            let state = self.markets.get_mut(&market).expect("Market of an order should exist");
+            if is_buy {
+                state.bids.insert((Reverse(limit), sequence), uuid.to_string());
+            } else {
+                state.asks.insert((limit, sequence), uuid.to_string());
+            }
+        } else {
+            self.close(uuid);
+        }
+    }
+
+    fn fill(&mut self, uuid: &str, quantity: Decimal, rate: Decimal) {
+        let account = self.accounts.get_mut(&self.owners[uuid]).expect("Account should exist");
+        self.orders.get_mut(uuid).expect("Order should exist").fill(&mut account.balances, quantity, rate);
+    }
+
+    fn close(&mut self, uuid: &str) {
+        let account = self.accounts.get_mut(&self.owners[uuid]).expect("Account should exist");
+        self.orders.get_mut(uuid).expect("Order should exist").close(&mut account.balances);
+    }
+
+    fn record_trade(&mut self, market: &Market, quantity: Decimal, rate: Decimal, maker_filled: bool, side: BittrexOrderSide) {
+        let id = self.next_id();
+        let state = self.markets.get_mut(market).expect("Market of an order should exist");
+        state.trades.push(BittrexTrade {
+            id: id as u32,
+            time_stamp: Utc::now(),
+            quantity: quantity,
+            price: rate,
+            total: quantity * rate,
+            fill_type: if maker_filled { BittrexFillType::Fill } else { BittrexFillType::PartialFill },
+            order_type: side,
+        });
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+}
+
+fn copy_trade(trade: &BittrexTrade) -> BittrexTrade {
+    BittrexTrade {
+        id: trade.id,
+        time_stamp: trade.time_stamp,
+        quantity: trade.quantity,
+        price: trade.price,
+        total: trade.total,
+        fill_type: trade.fill_type.clone(),
+        order_type: trade.order_type.clone(),
+    }
+}
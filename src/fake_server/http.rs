@@ -0,0 +1,166 @@
+//! Minimal HTTP/1.1 server for the GET requests of the client, with keep-alive connections.
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+
+pub struct Request {
+    pub method: String,
+    /// Path and query as sent in the request line.
+    pub target: String,
+    /// Headers with lower case names.
+    pub headers: Vec<(String, String)>,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+pub type Handler = Arc<dyn Fn(&Request) -> Response + Send + Sync>;
+
+/// Open connections with the threads serving them.
+type Connections = Arc<Mutex<Vec<(TcpStream, JoinHandle<()>)>>>;
+
+/// Accepts connections on a background thread and serves each one on its own thread until `stop` is called.
+pub struct Server {
+    address: SocketAddr,
+    running: Arc<AtomicBool>,
+    /// Finished connections are dropped with each accepted connection.
+    connections: Connections,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|&&(ref header_name, _)| header_name == name)
+            .map(|&(_, ref value)| value.as_str())
+    }
+}
+
+impl Server {
+    pub fn start(handler: Handler) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let server = Server {
+            address: listener.local_addr()?,
+            running: Arc::new(AtomicBool::new(true)),
+            connections: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        let running = server.running.clone();
+        let connections = server.connections.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if !running.load(Ordering::SeqCst) {
+                    break;
+                }
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let clone = match stream.try_clone() {
+                    Ok(clone) => clone,
+                    Err(_) => continue,
+                };
+                let handler = handler.clone();
+                let handle = thread::spawn(move || serve_connection(stream, &handler));
+
+                let mut connections = connections.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                connections.retain(|&(_, ref handle)| !handle.is_finished());
+                connections.push((clone, handle));
+            }
+        });
+        Ok(server)
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Stops accepting connections and closes the open ones.
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        // Wakes up the accept loop, which then sees that it has to stop.
+        let _ = TcpStream::connect(self.address);
+
+        for (connection, _) in self.connections.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).drain(..) {
+            let _ = connection.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+fn serve_connection(stream: TcpStream, handler: &Handler) {
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(_) => return,
+    };
+    let mut reader = BufReader::new(stream);
+
+    while let Ok(Some(request)) = read_request(&mut reader) {
+        let response = handler(&request);
+        if write_response(&mut writer, &response).is_err() {
+            return;
+        }
+        if request.header("connection").map(|value| value.eq_ignore_ascii_case("close")).unwrap_or(false) {
+            return;
+        }
+    }
+}
+
+/// Reads the next request of a connection, or `None` once the client closed it.
+fn read_request<R: BufRead>(reader: &mut R) -> io::Result<Option<Request>> {
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(None);
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method.to_string(), target.to_string()),
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid request line")),
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(index) = line.find(':') {
+            headers.push((line[..index].trim().to_ascii_lowercase(), line[index + 1..].trim().to_string()));
+        }
+    }
+
+    let request = Request { method: method, target: target, headers: headers };
+    let content_length = request.header("content-length").and_then(|value| value.parse::<u64>().ok()).unwrap_or(0);
+    io::copy(&mut reader.take(content_length), &mut io::sink())?;
+
+    Ok(Some(request))
+}
+
+fn write_response<W: Write>(writer: &mut W, response: &Response) -> io::Result<()> {
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        response.body.len(),
+        response.body
+    )?;
+    writer.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Unknown",
+    }
+}
@@ -0,0 +1,253 @@
+//! In-process fake of the Bittrex v1.1 API for integration tests, enabled with the `fake-server` feature.
+//!
+//! `FakeBittrex` runs a local HTTP server with markets, order books and accounts held in memory.
+//! Limit orders are matched like on Bittrex (best rate first, then oldest first, at the rate of the resting order)
+//! and every trade is charged a commission of 0.25% of its base currency amount on both sides.
+//! Private calls have to carry a valid `apisign` header and a nonce greater than the last one of their api key,
+//! otherwise they are rejected with the message Bittrex uses.
+//!
+//! Implemented endpoints:
+//!
+//! * `/public/getmarkets`, `/public/getcurrencies`, `/public/getticker`, `/public/getorderbook`, `/public/getmarkethistory`
+//! * `/market/buylimit`, `/market/selllimit`, `/market/cancel`, `/market/getopenorders`
+//! * `/account/getbalances`, `/account/getbalance`, `/account/getorder`, `/account/getorderhistory`, `/account/withdraw`
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bittrex_api::{BittrexClient, Currency, Decimal};
+//! use bittrex_api::fake_server::FakeBittrex;
+//!
+//! let fake_bittrex = FakeBittrex::start().unwrap();
+//! fake_bittrex.add_market(&"BTC-LTC".parse().unwrap(), Decimal::new(1, 2));
+//! fake_bittrex.add_account("KEY", "SECRET");
+//! fake_bittrex.deposit("KEY", &Currency::new("BTC").unwrap(), Decimal::new(1, 0));
+//!
+//! let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), fake_bittrex.api_url());
+//! bittrex_client.buy_limit(&"BTC-LTC".parse().unwrap(), Decimal::new(10, 0), Decimal::new(1, 2)).unwrap();
+//!
+//! let balance = fake_bittrex.balance("KEY", &Currency::new("BTC").unwrap());
+//! assert_eq!(balance.available.to_string(), "0.89975");
+//! ```
+mod exchange;
+mod http;
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use reqwest::Url;
+use serde::Serialize;
+use serde_json;
+
+use client;
+use decimal::Decimal;
+use market::{Currency, Market};
+use values::{BittrexAPIResult, BittrexBalance, BittrexCurrency, BittrexOrderSide, BittrexUuid};
+use self::exchange::{ApiResult, Exchange};
+use self::http::{Request, Response, Server};
+
+/// A fake Bittrex exchange serving the v1.1 API on a local port. The server stops when it is dropped.
+pub struct FakeBittrex {
+    server: Server,
+    exchange: Arc<Mutex<Exchange>>,
+}
+
+impl FakeBittrex {
+    /// Starts the server on a free port of 127.0.0.1.
+    pub fn start() -> io::Result<Self> {
+        let exchange = Arc::new(Mutex::new(Exchange::new()));
+        let handler_exchange = exchange.clone();
+        let server = Server::start(Arc::new(move |request: &Request| handle(&handler_exchange, request)))?;
+
+        Ok(FakeBittrex { server: server, exchange: exchange })
+    }
+
+    /// Returns the url to pass to `BittrexClientBuilder::api_url`.
+    pub fn api_url(&self) -> String {
+        format!("http://{}", self.server.address())
+    }
+
+    /// Opens a market, in which orders have to be at least `min_trade_size` (in the quote currency).
+    pub fn add_market(&self, market: &Market, min_trade_size: Decimal) {
+        self.lock().add_market(market.clone(), min_trade_size);
+    }
+
+    /// Adds an account without funds.
+    pub fn add_account(&self, api_key: &str, api_secret: &str) {
+        self.lock().add_account(api_key, api_secret);
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the account does not exist.
+    pub fn deposit(&self, api_key: &str, currency: &Currency, amount: Decimal) {
+        self.lock().deposit(api_key, currency, amount);
+    }
+
+    pub fn balance(&self, api_key: &str, currency: &Currency) -> BittrexBalance {
+        self.lock().get_balance(api_key, currency)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Exchange> {
+        lock(&self.exchange)
+    }
+}
+
+impl Drop for FakeBittrex {
+    fn drop(&mut self) {
+        self.server.stop();
+    }
+}
+
+fn lock(exchange: &Mutex<Exchange>) -> MutexGuard<'_, Exchange> {
+    exchange.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn handle(exchange: &Mutex<Exchange>, request: &Request) -> Response {
+    if request.method != "GET" {
+        return Response { status: 405, body: "Method Not Allowed".to_string() };
+    }
+    let url = match Url::parse(&format!("http://{}{}", request.header("host").unwrap_or("localhost"), request.target)) {
+        Ok(url) => url,
+        Err(_) => return Response { status: 400, body: "Bad Request".to_string() },
+    };
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let mut exchange = lock(exchange);
+
+    let path = url.path().to_string();
+    if path.starts_with("/market/") || path.starts_with("/account/") {
+        match authenticate(&mut exchange, &url, &params, request) {
+            Ok(api_key) => handle_private(&mut exchange, &path, &params, &api_key),
+            Err(message) => envelope::<()>(Err(message)),
+        }
+    } else {
+        handle_public(&exchange, &path, &params)
+    }
+}
+
+fn handle_public(exchange: &Exchange, path: &str, params: &HashMap<String, String>) -> Response {
+    match path {
+        "/public/getmarkets" => envelope(Ok(exchange.get_markets())),
+        "/public/getcurrencies" => envelope(Ok(currencies(exchange))),
+        "/public/getticker" => envelope(market_param(params).and_then(|market| exchange.get_ticker(&market))),
+        "/public/getorderbook" => envelope(market_param(params).and_then(|market| exchange.get_order_book(&market))),
+        "/public/getmarkethistory" => envelope(market_param(params).and_then(|market| exchange.get_market_history(&market))),
+        _ => not_found(),
+    }
+}
+
+fn handle_private(exchange: &mut Exchange, path: &str, params: &HashMap<String, String>, api_key: &str) -> Response {
+    match path {
+        "/market/buylimit" => envelope(place_limit(exchange, params, api_key, BittrexOrderSide::Buy)),
+        "/market/selllimit" => envelope(place_limit(exchange, params, api_key, BittrexOrderSide::Sell)),
+        "/market/cancel" => envelope(required(params, "uuid", "UUID_INVALID")
+            .and_then(|uuid| exchange.cancel(api_key, &uuid))
+            .map(|_| None::<()>)),
+        "/market/getopenorders" => envelope(optional_market_param(params)
+            .map(|market| exchange.get_open_orders(api_key, market.as_ref()))),
+        "/account/getbalances" => envelope(Ok(exchange.get_balances(api_key))),
+        "/account/getbalance" => envelope(currency_param(exchange, params).map(|currency| exchange.get_balance(api_key, &currency))),
+        "/account/getorder" => envelope(required(params, "uuid", "UUID_INVALID").and_then(|uuid| exchange.get_order(api_key, &uuid))),
+        "/account/getorderhistory" => envelope(optional_market_param(params)
+            .map(|market| exchange.get_order_history(api_key, market.as_ref()))),
+        "/account/withdraw" => envelope(withdraw(exchange, params, api_key)),
+        _ => not_found(),
+    }
+}
+
+/// Checks the api key, the signature of the whole url and the nonce like Bittrex does and returns the api key.
+fn authenticate(exchange: &mut Exchange, url: &Url, params: &HashMap<String, String>, request: &Request) -> ApiResult<String> {
+    let api_key = required(params, "apikey", "APIKEY_NOT_PROVIDED")?;
+    let nonce = required(params, "nonce", "NONCE_NOT_PROVIDED")?;
+    let apisign = request.header("apisign").ok_or("APISIGN_NOT_PROVIDED")?;
+    let nonce: u64 = nonce.parse().map_err(|_| "NONCE_NOT_PROVIDED")?;
+
+    let account = exchange.account_mut(&api_key).ok_or("APIKEY_INVALID")?;
+    let signed_url = format!("{}://{}{}", url.scheme(), request.header("host").unwrap_or(""), request.target);
+    if !client::signature(&account.api_secret, &signed_url).eq_ignore_ascii_case(apisign) {
+        return Err("INVALID_SIGNATURE");
+    }
+    if account.last_nonce.map(|last_nonce| nonce <= last_nonce).unwrap_or(false) {
+        return Err("NONCE_USED");
+    }
+    account.last_nonce = Some(nonce);
+    Ok(api_key)
+}
+
+fn place_limit(exchange: &mut Exchange, params: &HashMap<String, String>, api_key: &str, side: BittrexOrderSide) -> ApiResult<BittrexUuid> {
+    let market = market_param(params)?;
+    let quantity = decimal_param(params, "quantity", "QUANTITY_NOT_PROVIDED")?;
+    let rate = decimal_param(params, "rate", "RATE_NOT_PROVIDED")?;
+
+    exchange.place_limit(api_key, &market, side, quantity, rate).map(|uuid| BittrexUuid { uuid: uuid })
+}
+
+fn withdraw(exchange: &mut Exchange, params: &HashMap<String, String>, api_key: &str) -> ApiResult<BittrexUuid> {
+    let currency = currency_param(exchange, params)?;
+    let quantity = decimal_param(params, "quantity", "QUANTITY_NOT_PROVIDED")?;
+    let address = params.get("address").map(|address| address.as_str()).unwrap_or("");
+
+    exchange.withdraw(api_key, &currency, quantity, address).map(|uuid| BittrexUuid { uuid: uuid })
+}
+
+fn currencies(exchange: &Exchange) -> Vec<BittrexCurrency> {
+    let mut currencies: Vec<Currency> = exchange.get_markets().into_iter()
+        .flat_map(|market| vec![market.base_currency, market.market_currency])
+        .collect();
+    currencies.sort();
+    currencies.dedup();
+
+    currencies.into_iter()
+        .map(|currency| BittrexCurrency {
+            currency_long: currency.to_string(),
+            currency: currency,
+            min_confirmation: 6,
+            tx_fee: Decimal::zero(),
+            is_active: true,
+            coin_type: Some("BITCOIN".to_string()),
+            base_address: None,
+            notice: None,
+        })
+        .collect()
+}
+
+fn required(params: &HashMap<String, String>, name: &str, message: &'static str) -> ApiResult<String> {
+    params.get(name).filter(|value| !value.is_empty()).cloned().ok_or(message)
+}
+
+fn market_param(params: &HashMap<String, String>) -> ApiResult<Market> {
+    required(params, "market", "MARKET_NOT_PROVIDED")?.parse().map_err(|_| "INVALID_MARKET")
+}
+
+fn optional_market_param(params: &HashMap<String, String>) -> ApiResult<Option<Market>> {
+    match params.get("market") {
+        Some(market) if !market.is_empty() => market.parse().map(Some).map_err(|_| "INVALID_MARKET"),
+        _ => Ok(None),
+    }
+}
+
+fn currency_param(exchange: &Exchange, params: &HashMap<String, String>) -> ApiResult<Currency> {
+    let currency: Currency = required(params, "currency", "CURRENCY_NOT_PROVIDED")?.parse().map_err(|_| "INVALID_CURRENCY")?;
+    if !exchange.is_known_currency(&currency) {
+        return Err("INVALID_CURRENCY");
+    }
+    Ok(currency)
+}
+
+fn decimal_param(params: &HashMap<String, String>, name: &str, message: &'static str) -> ApiResult<Decimal> {
+    required(params, name, message)?.parse().map_err(|_| message)
+}
+
+/// Wraps a result into the envelope of Bittrex, with HTTP status 200 also for rejected calls.
+fn envelope<T: Serialize>(result: ApiResult<T>) -> Response {
+    let envelope = match result {
+        Ok(result) => BittrexAPIResult { success: true, message: String::new(), result: Some(result) },
+        Err(message) => BittrexAPIResult { success: false, message: message.to_string(), result: None },
+    };
+    Response { status: 200, body: serde_json::to_string(&envelope).expect("Response should serialize") }
+}
+
+fn not_found() -> Response {
+    Response { status: 404, body: "Not Found".to_string() }
+}
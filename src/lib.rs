@@ -14,20 +14,53 @@
 //! let markets = bittrex_client.get_markets().unwrap(); //Get all available markets of Bittrex
 //! # }
 //! ```
-extern crate time;
+extern crate chrono;
 extern crate hmac;
 extern crate sha2;
+extern crate sha_1 as sha1;
 extern crate generic_array;
+extern crate rand;
 
 extern crate reqwest;
+extern crate futures;
+extern crate tokio_timer;
+extern crate base64;
+extern crate flate2;
+extern crate native_tls;
 
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde_json;
 
+pub mod api;
+pub mod candles;
+pub mod cassette;
 pub mod error;
+pub mod datetime;
+pub mod decimal;
+pub mod market;
+pub mod nonce;
+pub mod paper_trading;
+pub mod rate_limit;
+pub mod retry;
+pub mod socket;
+pub mod trade_feed;
+pub mod transport;
+#[macro_use]
 pub mod values;
+pub mod v3;
+#[cfg(feature = "fake-server")]
+pub mod fake_server;
 
+mod endpoint;
+mod simulation;
 mod client;
-pub use client::BittrexClient;
+mod async_client;
+pub use client::{BittrexClient, BittrexClientBuilder, BittrexPublicClient, Public, Authenticated};
+pub use api::{Account, MarketData, Trading};
+pub use async_client::{AsyncBittrexClient, BittrexFuture};
+pub use decimal::Decimal;
+pub use market::{Currency, Market};
+pub use paper_trading::PaperTradingClient;
+pub use retry::RetryPolicy;
@@ -0,0 +1,167 @@
+use std::fmt;
+use std::error::Error as StdError;
+use std::str::FromStr;
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de;
+
+/// Currency code like `BTC` or `LTC`.
+///
+/// Codes are validated to be non-empty and to consist of letters, digits, `.` and `_` only, and are normalized
+/// to upper case, so they can be put into request urls as they are. Responses are decoded with the same rules.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Currency(String);
+
+/// Market like `BTC-LTC`, in which `quote` (LTC) is traded for `base` (BTC).
+///
+/// # Examples
+///
+/// ```rust
+/// use bittrex_api::{Currency, Market};
+///
+/// let market: Market = "BTC-LTC".parse().unwrap();
+///
+/// assert_eq!(market.base, Currency::new("BTC").unwrap());
+/// assert_eq!(market.quote, "LTC");
+/// assert!("BTCLTC".parse::<Market>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Market {
+    pub base: Currency,
+    pub quote: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMarketError {
+    InvalidCurrency(String),
+    InvalidMarket(String),
+}
+
+impl Currency {
+    pub fn new(code: &str) -> Result<Currency, ParseMarketError> {
+        if code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_') {
+            return Err(ParseMarketError::InvalidCurrency(code.to_string()));
+        }
+        Ok(Currency(code.to_ascii_uppercase()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Market {
+    pub fn new(base: Currency, quote: Currency) -> Self {
+        Market { base: base, quote: quote }
+    }
+}
+
+impl FromStr for Currency {
+    type Err = ParseMarketError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Currency::new(code)
+    }
+}
+
+impl FromStr for Market {
+    type Err = ParseMarketError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let mut parts = name.split('-');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(base), Some(quote), None) => {
+                let base = Currency::new(base).map_err(|_| ParseMarketError::InvalidMarket(name.to_string()))?;
+                let quote = Currency::new(quote).map_err(|_| ParseMarketError::InvalidMarket(name.to_string()))?;
+                Ok(Market::new(base, quote))
+            },
+            _ => Err(ParseMarketError::InvalidMarket(name.to_string()))
+        }
+    }
+}
+
+impl AsRef<str> for Currency {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Currency {
+    fn eq(&self, other: &str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Currency {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<str> for Market {
+    fn eq(&self, other: &str) -> bool {
+        other.parse::<Market>().map(|market| *self == market).unwrap_or(false)
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Market {
+    fn eq(&self, other: &&'a str) -> bool {
+        *self == **other
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+impl fmt::Display for Market {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&format!("{}-{}", self.base, self.quote))
+    }
+}
+
+impl fmt::Display for ParseMarketError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseMarketError::InvalidCurrency(ref code) => write!(f, "Invalid currency '{}'", code),
+            ParseMarketError::InvalidMarket(ref name) => write!(f, "Invalid market '{}', expected BASE-QUOTE", name),
+        }
+    }
+}
+
+impl StdError for ParseMarketError {
+    fn description(&self) -> &str {
+        match *self {
+            ParseMarketError::InvalidCurrency(_) => "Invalid currency",
+            ParseMarketError::InvalidMarket(_) => "Invalid market",
+        }
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        let code = String::deserialize(deserializer)?;
+        Currency::new(&code).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Market {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Market {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,206 @@
+//! Sources of the nonces of private calls.
+//!
+//! Bittrex rejects a private call with `NONCE_USED` unless its nonce is greater than the nonce of every earlier call
+//! with the same api key. The nonces are nanoseconds since the UNIX epoch, raised where necessary to stay strictly increasing:
+//!
+//! * `MonotonicNonce` (the default) is strictly increasing across all threads and clones of a client.
+//! * `FileNonce` also persists the last nonce in a file, so nonces keep increasing across restarts even if the clock goes back.
+//! * `SharedFileNonce` coordinates several processes on one host which share an api key through a common file.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bittrex_api::BittrexClient;
+//! use bittrex_api::nonce::SharedFileNonce;
+//!
+//! let bittrex_client = BittrexClient::builder()
+//!     .credentials("APIKEY".to_string(), "APISECRET".to_string())
+//!     .nonce_source(SharedFileNonce::new("/var/run/bittrex-bot/nonce"))
+//!     .build()
+//!     .unwrap();
+//! ```
+use std::cmp;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use error::{BittrexError, BittrexErrorType};
+
+/// Age after which the lock file of a `SharedFileNonce` is considered left over by a crashed process.
+const STALE_LOCK_AGE: Duration = Duration::from_secs(10);
+
+/// Time to wait before trying again to take the lock of a `SharedFileNonce`.
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(1);
+
+/// Generates the nonces of private calls, each greater than all nonces generated before for the same api key.
+pub trait NonceSource: Send + Sync {
+    fn next_nonce(&self) -> Result<u64, BittrexError>;
+}
+
+impl<T: NonceSource + ?Sized> NonceSource for Arc<T> {
+    fn next_nonce(&self) -> Result<u64, BittrexError> {
+        (**self).next_nonce()
+    }
+}
+
+/// Strictly increasing nonces within the process.
+#[derive(Debug, Default)]
+pub struct MonotonicNonce {
+    last: Mutex<u64>,
+}
+
+/// Strictly increasing nonces within the process, which continue after the last nonce stored in a file.
+///
+/// The file is read once and written with every nonce, so only one process may use it at a time.
+/// Use `SharedFileNonce` for several processes.
+#[derive(Debug)]
+pub struct FileNonce {
+    path: PathBuf,
+    last: Mutex<Option<u64>>,
+}
+
+/// Strictly increasing nonces across all processes on a host using the same file.
+///
+/// Every nonce is read from and written to the file while holding a lock file (the path with `.lock` appended).
+/// A lock file older than 10 seconds is considered left over by a crashed process and removed.
+#[derive(Debug)]
+pub struct SharedFileNonce {
+    path: PathBuf,
+    lock_path: PathBuf,
+}
+
+impl MonotonicNonce {
+    pub fn new() -> Self {
+        MonotonicNonce::default()
+    }
+}
+
+impl NonceSource for MonotonicNonce {
+    fn next_nonce(&self) -> Result<u64, BittrexError> {
+        let mut last = self.last.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        *last = next_after(*last);
+        Ok(*last)
+    }
+}
+
+impl FileNonce {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        FileNonce { path: path.as_ref().to_path_buf(), last: Mutex::new(None) }
+    }
+}
+
+impl NonceSource for FileNonce {
+    fn next_nonce(&self) -> Result<u64, BittrexError> {
+        let mut last = self.last.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let previous = match *last {
+            Some(previous) => previous,
+            None => read_nonce(&self.path).map_err(|error| nonce_error("read", &self.path, error))?,
+        };
+
+        let nonce = next_after(previous);
+        write_nonce(&self.path, nonce).map_err(|error| nonce_error("write", &self.path, error))?;
+        *last = Some(nonce);
+        Ok(nonce)
+    }
+}
+
+impl SharedFileNonce {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut lock_path = path.clone().into_os_string();
+        lock_path.push(".lock");
+        SharedFileNonce { path: path, lock_path: PathBuf::from(lock_path) }
+    }
+
+    fn lock(&self) -> io::Result<LockFile<'_>> {
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&self.lock_path) {
+                Ok(_) => return Ok(LockFile { path: &self.lock_path }),
+                Err(ref error) if error.kind() == io::ErrorKind::AlreadyExists => {
+                    if is_stale(&self.lock_path) {
+                        let _ = fs::remove_file(&self.lock_path);
+                    } else {
+                        thread::sleep(LOCK_RETRY_DELAY);
+                    }
+                },
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl NonceSource for SharedFileNonce {
+    fn next_nonce(&self) -> Result<u64, BittrexError> {
+        let _lock = self.lock().map_err(|error| nonce_error("lock", &self.lock_path, error))?;
+        let previous = read_nonce(&self.path).map_err(|error| nonce_error("read", &self.path, error))?;
+
+        let nonce = next_after(previous);
+        write_nonce(&self.path, nonce).map_err(|error| nonce_error("write", &self.path, error))?;
+        Ok(nonce)
+    }
+}
+
+/// Removes the lock file when dropped.
+struct LockFile<'a> {
+    path: &'a Path,
+}
+
+impl<'a> Drop for LockFile<'a> {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(self.path);
+    }
+}
+
+/// The current time in nanoseconds since the UNIX epoch, or `previous + 1` if that is not greater.
+fn next_after(previous: u64) -> u64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs() * 1_000_000_000 + u64::from(since_epoch.subsec_nanos()))
+        .unwrap_or(0);
+    cmp::max(now, previous + 1)
+}
+
+/// Reads the nonce stored in `path`, or 0 if the file does not exist or is empty.
+fn read_nonce(path: &Path) -> io::Result<u64> {
+    let mut content = String::new();
+    match File::open(path) {
+        Ok(mut file) => file.read_to_string(&mut content)?,
+        Err(ref error) if error.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(error) => return Err(error),
+    };
+
+    let content = content.trim();
+    if content.is_empty() {
+        return Ok(0);
+    }
+    content.parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid nonce '{}'", content)))
+}
+
+/// Writes the nonce to a temporary file which then replaces `path`, so a crash never leaves a truncated file behind.
+fn write_nonce(path: &Path, nonce: u64) -> io::Result<()> {
+    let mut temporary_path = path.to_path_buf().into_os_string();
+    temporary_path.push(".tmp");
+
+    {
+        let mut file = File::create(&temporary_path)?;
+        write!(file, "{}", nonce)?;
+        file.sync_all()?;
+    }
+    fs::rename(&temporary_path, path)
+}
+
+fn is_stale(lock_path: &Path) -> bool {
+    fs::metadata(lock_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .map(|age| age > STALE_LOCK_AGE)
+        .unwrap_or(false)
+}
+
+fn nonce_error(action: &str, path: &Path, error: io::Error) -> BittrexError {
+    BittrexError::new(BittrexErrorType::Configuration, format!("Could not {} nonce file {}: {}", action, path.display(), error))
+        .with_source(error)
+}
@@ -0,0 +1,242 @@
+//! Paper trading: the trading and account methods of `BittrexClient` against a simulated local account.
+//!
+//! Orders are filled against order book snapshots, either supplied with `PaperTradingClient::set_order_book`
+//! or fetched with `get_order_book` of the wrapped client. A limit order takes the levels of the opposite side
+//! at their rates as long as they are within its limit, pays a commission of 0.25% of the base currency amount
+//! like on Bittrex and rests as an open order with the remaining quantity. Open orders are filled further
+//! against newer snapshots by `PaperTradingClient::refresh`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bittrex_api::{Currency, Decimal, PaperTradingClient};
+//! use bittrex_api::values::{BittrexPublicOrder, BittrexPublicOrderBook};
+//!
+//! let paper_client = PaperTradingClient::offline();
+//! paper_client.deposit(&Currency::new("BTC").unwrap(), Decimal::new(1, 0));
+//! paper_client.set_order_book(&"BTC-LTC".parse().unwrap(), BittrexPublicOrderBook {
+//!     buy: vec![],
+//!     sell: vec![BittrexPublicOrder { quantity: Decimal::new(10, 0), rate: Decimal::new(1, 2) }],
+//! });
+//!
+//! paper_client.buy_limit(&"BTC-LTC".parse().unwrap(), Decimal::new(10, 0), Decimal::new(1, 2)).unwrap();
+//!
+//! let btc = paper_client.get_balance(&Currency::new("BTC").unwrap()).unwrap();
+//! assert_eq!(btc.balance.to_string(), "0.89975");
+//! ```
+use std::cmp;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+use client::{BittrexClient, Public, Result};
+use decimal::Decimal;
+use error::{BittrexError, BittrexErrorType};
+use market::{Currency, Market};
+use simulation::{self, Balances, Order};
+use values::*;
+
+/// A client which simulates trading with a local account instead of placing orders on Bittrex.
+///
+/// `PaperTradingClient<Public>` works with a public client, as it only needs the market data endpoints.
+pub struct PaperTradingClient<A = Public> {
+    market_data: Option<BittrexClient<A>>,
+    account: Mutex<PaperAccount>,
+}
+
+struct PaperAccount {
+    balances: Balances,
+    order_books: BTreeMap<Market, BittrexPublicOrderBook>,
+    orders: Vec<Order>,
+    next_id: u64,
+}
+
+impl PaperTradingClient<Public> {
+    /// Creates a client without market data, which only fills orders against order books supplied with `set_order_book`.
+    pub fn offline() -> Self {
+        PaperTradingClient { market_data: None, account: Mutex::new(PaperAccount::new()) }
+    }
+}
+
+impl<A> PaperTradingClient<A> {
+    /// Creates a client which fetches the order book of a market with `market_data` unless one was supplied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bittrex_api::{BittrexClient, PaperTradingClient};
+    ///
+    /// let paper_client = PaperTradingClient::new(BittrexClient::new_public());
+    /// ```
+    pub fn new(market_data: BittrexClient<A>) -> Self {
+        PaperTradingClient { market_data: Some(market_data), account: Mutex::new(PaperAccount::new()) }
+    }
+
+    /// Adds funds to the simulated account.
+    pub fn deposit(&self, currency: &Currency, amount: Decimal) {
+        let mut account = self.lock();
+        let balance = account.balances.entry(currency.clone()).or_default();
+        balance.total += amount;
+        balance.available += amount;
+    }
+
+    /// Supplies the order book to fill orders of `market` against, instead of fetching it.
+    /// Fills take the liquidity they use out of the supplied order book.
+    pub fn set_order_book(&self, market: &Market, order_book: BittrexPublicOrderBook) {
+        self.lock().order_books.insert(market.clone(), order_book);
+    }
+
+    /// Places a simulated buy order and fills it as far as the order book allows.
+    pub fn buy_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid> {
+        self.place_limit(market, BittrexOrderSide::Buy, quantity, rate)
+    }
+
+    /// Places a simulated sell order and fills it as far as the order book allows.
+    pub fn sell_limit(&self, market: &Market, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid> {
+        self.place_limit(market, BittrexOrderSide::Sell, quantity, rate)
+    }
+
+    /// Cancels an open order and releases the funds it reserved.
+    pub fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let mut account = self.lock();
+        let account = &mut *account;
+        match account.orders.iter_mut().find(|order| order.uuid == order_id) {
+            Some(ref mut order) if order.is_open() => {
+                order.cancel_initiated = true;
+                order.close(&mut account.balances);
+                Ok(())
+            },
+            Some(_) => Err(BittrexError::api("ORDER_NOT_OPEN".to_string())),
+            None => Err(BittrexError::api("INVALID_ORDER".to_string())),
+        }
+    }
+
+    /// Fills the open orders against the current order books, fetching those which were not supplied.
+    pub fn refresh(&self) -> Result<()> {
+        let mut markets: Vec<Market> = self.lock().orders.iter()
+            .filter(|order| order.is_open())
+            .map(|order| order.market.clone())
+            .collect();
+        markets.sort();
+        markets.dedup();
+
+        for market in markets {
+            let mut fetched = self.fetch_order_book_unless_supplied(&market)?;
+            let mut account = self.lock();
+            let account = &mut *account;
+            let order_book = match fetched {
+                Some(ref mut order_book) => order_book,
+                None => account.order_books.get_mut(&market).expect("Supplied order book should exist"),
+            };
+            for order in account.orders.iter_mut().filter(|order| order.is_open() && order.market == market) {
+                fill(order, &mut account.balances, order_book);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_open_orders(&self) -> Result<Vec<BittrexOpenOrder>> {
+        Ok(self.lock().orders.iter().filter(|order| order.is_open()).map(Order::to_open_order).collect())
+    }
+
+    pub fn get_open_orders_by_market(&self, market: &Market) -> Result<Vec<BittrexOpenOrder>> {
+        Ok(self.lock().orders.iter()
+            .filter(|order| order.is_open() && order.market == *market)
+            .map(Order::to_open_order)
+            .collect())
+    }
+
+    pub fn get_order(&self, order_id: &str) -> Result<BittrexOrder> {
+        self.lock().orders.iter()
+            .find(|order| order.uuid == order_id)
+            .map(Order::to_order)
+            .ok_or_else(|| BittrexError::api("INVALID_ORDER".to_string()))
+    }
+
+    pub fn get_order_history(&self) -> Result<Vec<BittrexHistoryOrder>> {
+        Ok(simulation::order_history(self.lock().orders.iter(), None))
+    }
+
+    pub fn get_order_history_by_market(&self, market: &Market) -> Result<Vec<BittrexHistoryOrder>> {
+        Ok(simulation::order_history(self.lock().orders.iter(), Some(market)))
+    }
+
+    pub fn get_balances(&self) -> Result<Vec<BittrexBalance>> {
+        Ok(self.lock().balances.iter()
+            .map(|(currency, balance)| simulation::to_bittrex_balance(currency, *balance))
+            .collect())
+    }
+
+    pub fn get_balance(&self, currency: &Currency) -> Result<BittrexBalance> {
+        let balance = self.lock().balances.get(currency).cloned().unwrap_or_default();
+        Ok(simulation::to_bittrex_balance(currency, balance))
+    }
+
+    fn place_limit(&self, market: &Market, side: BittrexOrderSide, quantity: Decimal, rate: Decimal) -> Result<BittrexUuid> {
+        simulation::validate_order(quantity, rate, Decimal::zero()).map_err(|code| BittrexError::api(code.to_string()))?;
+        let mut fetched = self.fetch_order_book_unless_supplied(market)?;
+
+        let mut account = self.lock();
+        let account = &mut *account;
+        let mut order = Order::new(account.next_id, market, side, quantity, rate);
+        order.reserve(&mut account.balances).map_err(|code| BittrexError::api(code.to_string()))?;
+        account.next_id += 1;
+
+        let order_book = match fetched {
+            Some(ref mut order_book) => order_book,
+            None => account.order_books.get_mut(market).expect("Supplied order book should exist"),
+        };
+        fill(&mut order, &mut account.balances, order_book);
+
+        let uuid = order.uuid.clone();
+        account.orders.push(order);
+        Ok(BittrexUuid { uuid: uuid })
+    }
+
+    /// Fetches the order book of `market` if none was supplied. The lock is not held while fetching.
+    fn fetch_order_book_unless_supplied(&self, market: &Market) -> Result<Option<BittrexPublicOrderBook>> {
+        if self.lock().order_books.contains_key(market) {
+            return Ok(None);
+        }
+        match self.market_data {
+            Some(ref market_data) => market_data.get_order_book(market, BittrexOrderType::Both).map(Some),
+            None => Err(BittrexError::new(BittrexErrorType::Configuration, format!("No order book supplied for market {}", market))),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PaperAccount> {
+        self.account.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl PaperAccount {
+    fn new() -> Self {
+        PaperAccount { balances: Balances::new(), order_books: BTreeMap::new(), orders: Vec::new(), next_id: 1 }
+    }
+}
+
+/// Fills an open order against the opposite side of the order book, best rate first, and closes it once it is filled.
+fn fill(order: &mut Order, balances: &mut Balances, order_book: &mut BittrexPublicOrderBook) {
+    let levels = if order.is_buy() {
+        order_book.sell.sort_by_key(|level| level.rate);
+        &mut order_book.sell
+    } else {
+        order_book.buy.sort_by_key(|level| cmp::Reverse(level.rate));
+        &mut order_book.buy
+    };
+
+    for level in levels.iter_mut() {
+        if !order.quantity_remaining.is_positive() || !order.accepts(level.rate) {
+            break;
+        }
+        let quantity = cmp::min(order.quantity_remaining, level.quantity);
+        if quantity.is_positive() {
+            order.fill(balances, quantity, level.rate);
+            level.quantity -= quantity;
+        }
+    }
+    levels.retain(|level| level.quantity.is_positive());
+
+    if !order.quantity_remaining.is_positive() {
+        order.close(balances);
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use error::{BittrexError, BittrexErrorType};
+
+/// Class of an endpoint, each of which has its own rate limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndpointClass {
+    /// The `/public/*` endpoints, limited per IP address.
+    Public,
+    /// The `/market/*` and `/account/*` endpoints, limited per API key.
+    Private,
+}
+
+/// What to do with a request when its rate limit is exhausted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Wait until the request may be sent (the default).
+    Block,
+    /// Fail immediately with `BittrexErrorType::RateLimited`.
+    FailFast,
+}
+
+/// Allows `requests` requests per `per`, with bursts of up to `requests` requests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+/// Token-bucket rate limiter with one bucket per endpoint class.
+///
+/// Clones share their buckets, so a limiter passed to several clients (e.g. a blocking and an async
+/// client using the same API key) limits all of them together. Clones of a client share its limiter as well.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use bittrex_api::BittrexClient;
+/// use bittrex_api::rate_limit::{EndpointClass, RateLimit, RateLimiter, RateLimitMode};
+///
+/// let rate_limiter = RateLimiter::new()
+///     .limit(EndpointClass::Public, RateLimit::new(60, Duration::from_secs(60)))
+///     .limit(EndpointClass::Private, RateLimit::new(30, Duration::from_secs(60)))
+///     .mode(RateLimitMode::FailFast);
+///
+/// let bittrex_client = BittrexClient::builder()
+///     .rate_limiter(rate_limiter)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    public: Option<Arc<Mutex<TokenBucket>>>,
+    private: Option<Arc<Mutex<TokenBucket>>>,
+    mode: RateLimitMode,
+}
+
+struct TokenBucket {
+    capacity: f64,
+    tokens_per_sec: f64,
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl RateLimit {
+    /// # Panics
+    ///
+    /// Panics if `requests` is zero.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        assert!(requests > 0, "A rate limit has to allow at least one request");
+        RateLimit { requests: requests, per: per }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        RateLimit::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        RateLimit::new(requests, Duration::from_secs(60))
+    }
+}
+
+impl RateLimiter {
+    /// Creates a limiter without limits, which has to be configured with `limit`.
+    pub fn new() -> Self {
+        RateLimiter { public: None, private: None, mode: RateLimitMode::Block }
+    }
+
+    /// Creates a limiter with the limits of Bittrex: 60 requests per minute for each endpoint class.
+    pub fn bittrex() -> Self {
+        RateLimiter::new()
+            .limit(EndpointClass::Public, RateLimit::per_minute(60))
+            .limit(EndpointClass::Private, RateLimit::per_minute(60))
+    }
+
+    /// Sets the limit for an endpoint class. Clones created before are not affected.
+    pub fn limit(mut self, class: EndpointClass, rate_limit: RateLimit) -> Self {
+        let bucket = Some(Arc::new(Mutex::new(TokenBucket::new(rate_limit))));
+        match class {
+            EndpointClass::Public => self.public = bucket,
+            EndpointClass::Private => self.private = bucket,
+        }
+        self
+    }
+
+    pub fn mode(mut self, mode: RateLimitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Takes a slot for a request of the given class and returns how long to wait before sending it.
+    /// Fails if the mode is `FailFast` and no slot is free.
+    pub fn acquire(&self, class: EndpointClass) -> Result<Duration, BittrexError> {
+        let bucket = match class {
+            EndpointClass::Public => &self.public,
+            EndpointClass::Private => &self.private,
+        };
+        let mut bucket = match *bucket {
+            Some(ref bucket) => bucket.lock().unwrap_or_else(|poisoned| poisoned.into_inner()),
+            None => return Ok(Duration::from_secs(0)),
+        };
+
+        match self.mode {
+            RateLimitMode::Block => Ok(bucket.reserve(Instant::now())),
+            RateLimitMode::FailFast if bucket.try_take(Instant::now()) => Ok(Duration::from_secs(0)),
+            RateLimitMode::FailFast => Err(BittrexError::new(BittrexErrorType::RateLimited, format!("No free slot for {:?} endpoints", class))),
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter::new()
+    }
+}
+
+impl TokenBucket {
+    fn new(rate_limit: RateLimit) -> Self {
+        let capacity = f64::from(rate_limit.requests);
+        TokenBucket {
+            capacity: capacity,
+            tokens_per_sec: capacity / duration_secs(rate_limit.per),
+            tokens: capacity,
+            refilled: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        if now > self.refilled {
+            let elapsed = duration_secs(now - self.refilled);
+            self.tokens = (self.tokens + elapsed * self.tokens_per_sec).min(self.capacity);
+            self.refilled = now;
+        }
+    }
+
+    /// Takes a token if one is available.
+    fn try_take(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return true;
+        }
+        false
+    }
+
+    /// Takes a token, going into debt if none is available, and returns how long to wait until it is covered.
+    /// Later callers queue up behind the debt, so waiting requests are sent in order and never exceed the rate.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 || self.tokens_per_sec <= 0.0 {
+            return Duration::from_secs(0);
+        }
+        Duration::from_millis((-self.tokens / self.tokens_per_sec * 1000.0).ceil() as u64)
+    }
+}
+
+impl fmt::Debug for TokenBucket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TokenBucket {{ capacity: {}, tokens: {:.2} }}", self.capacity, self.tokens)
+    }
+}
+
+fn duration_secs(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) / 1e9
+}
@@ -0,0 +1,107 @@
+use std::cmp;
+use std::time::Duration;
+use rand;
+use reqwest::StatusCode;
+
+/// Policy for retrying calls which failed with a transient error: a failure before a response was received
+/// (e.g. connection resets or timeouts), an HTTP 5xx status or HTTP 429 (Too Many Requests).
+///
+/// The delay before a retry starts at `initial_delay` and doubles for every further retry up to `max_delay`.
+/// With `jitter` a random part of up to half the delay is subtracted, so clients don't retry in lockstep.
+///
+/// Only idempotent calls (all public calls and the read-only private calls) are retried.
+/// Calls that place or cancel orders or withdraw funds are only retried if `retry_trading` is set,
+/// as a retry may execute them twice if the first attempt reached Bittrex.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use bittrex_api::RetryPolicy;
+///
+/// let retry_policy = RetryPolicy::new(3, Duration::from_millis(500))
+///     .max_delay(Duration::from_secs(2))
+///     .jitter(false);
+///
+/// assert_eq!(retry_policy.delay(0), Duration::from_millis(500));
+/// assert_eq!(retry_policy.delay(1), Duration::from_millis(1000));
+/// assert_eq!(retry_policy.delay(2), Duration::from_millis(2000));
+/// assert_eq!(retry_policy.delay(3), Duration::from_millis(2000));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_delay: Duration,
+    /// Upper bound for the delay between two attempts.
+    pub max_delay: Duration,
+    /// Whether the delays are randomized.
+    pub jitter: bool,
+    /// Whether `buy_limit`, `sell_limit`, `cancel_order` and `withdraw` are retried as well.
+    pub retry_trading: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a policy with jitter and a maximum delay of 30 seconds, which doesn't retry trading calls.
+    pub fn new(max_retries: u32, initial_delay: Duration) -> Self {
+        RetryPolicy {
+            max_retries: max_retries,
+            initial_delay: initial_delay,
+            max_delay: cmp::max(initial_delay, Duration::from_secs(30)),
+            jitter: true,
+            retry_trading: false,
+        }
+    }
+
+    /// Policy which never retries (the default).
+    pub fn none() -> Self {
+        RetryPolicy::new(0, Duration::from_secs(0))
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Opts in to retrying the calls that place or cancel orders or withdraw funds.
+    pub fn retry_trading(mut self, retry_trading: bool) -> Self {
+        self.retry_trading = retry_trading;
+        self
+    }
+
+    /// Returns the delay before the given retry (starting at 0).
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry).unwrap_or(u32::max_value());
+        let delay = self.initial_delay.checked_mul(factor)
+            .map(|delay| cmp::min(delay, self.max_delay))
+            .unwrap_or(self.max_delay);
+
+        if self.jitter {
+            delay - delay.mul_f64(rand::random::<f64>() / 2.0)
+        } else {
+            delay
+        }
+    }
+
+    /// Returns how often a call is retried, depending on whether it places or cancels orders or withdraws funds.
+    pub(crate) fn retries_for(&self, trading: bool) -> u32 {
+        if trading && !self.retry_trading { 0 } else { self.max_retries }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::none()
+    }
+}
+
+/// Whether a response with the given status is worth retrying.
+pub(crate) fn is_transient_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
@@ -0,0 +1,268 @@
+//! Order and balance bookkeeping shared by the simulated exchanges (`PaperTradingClient` and the fake server).
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use decimal::Decimal;
+use market::{Currency, Market};
+use values::*;
+
+/// Commission Bittrex charges on the base currency amount of every trade, in satoshis per unit (0.25%).
+const COMMISSION_RATE: i64 = 250_000;
+
+/// Minimum base currency amount of an order in satoshis.
+const MIN_ORDER_VALUE: i64 = 50_000;
+
+/// Balances of a simulated account.
+pub(crate) type Balances = BTreeMap<Currency, Balance>;
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Balance {
+    pub total: Decimal,
+    pub available: Decimal,
+}
+
+/// A limit order with the funds it reserved. Buy orders reserve their value plus commission
+/// in the base currency, sell orders their quantity in the quote currency.
+pub(crate) struct Order {
+    pub uuid: String,
+    pub sequence: u64,
+    pub market: Market,
+    pub kind: BittrexOrderKind,
+    pub quantity: Decimal,
+    pub quantity_remaining: Decimal,
+    pub limit: Decimal,
+    pub reserved: Decimal,
+    pub reserve_remaining: Decimal,
+    pub commission_reserved: Decimal,
+    pub commission_reserve_remaining: Decimal,
+    pub commission_paid: Decimal,
+    pub price: Decimal,
+    pub opened: DateTime<Utc>,
+    pub closed: Option<DateTime<Utc>>,
+    pub cancel_initiated: bool,
+}
+
+pub(crate) fn commission(value: Decimal) -> Decimal {
+    value * Decimal::from_satoshis(COMMISSION_RATE)
+}
+
+/// Product rounded down to whole satoshis (`up` = false) or up.
+///
+/// Buy orders round their reservation up and the cost of each fill down, so their fills never take more than they reserved.
+fn mul_rounded(value: Decimal, factor: Decimal, up: bool) -> Decimal {
+    let product = i128::from(value.satoshis()) * i128::from(factor.satoshis());
+    let scale = i128::from(Decimal::new(1, 0).satoshis());
+    let mut satoshis = product / scale;
+    if up && product % scale > 0 {
+        satoshis += 1;
+    }
+    Decimal::from_satoshis(satoshis as i64)
+}
+
+/// Checks an order like Bittrex does and returns the message code it rejects invalid orders with.
+pub(crate) fn validate_order(quantity: Decimal, rate: Decimal, min_trade_size: Decimal) -> Result<(), &'static str> {
+    if !quantity.is_positive() || !rate.is_positive() {
+        return Err("ZERO_OR_NEGATIVE_NOT_ALLOWED");
+    }
+    if quantity < min_trade_size {
+        return Err("MIN_TRADE_REQUIREMENT_NOT_MET");
+    }
+    match quantity.checked_mul(rate) {
+        Some(value) if value < Decimal::from_satoshis(MIN_ORDER_VALUE) => Err("DUST_TRADE_DISALLOWED_MIN_VALUE_50K_SAT"),
+        Some(_) => Ok(()),
+        None => Err("INVALID_ORDER"),
+    }
+}
+
+pub(crate) fn to_bittrex_balance(currency: &Currency, balance: Balance) -> BittrexBalance {
+    BittrexBalance {
+        currency: currency.clone(),
+        balance: balance.total,
+        available: balance.available,
+        pending: Decimal::zero(),
+        crypto_address: None,
+    }
+}
+
+fn change_balance(balances: &mut Balances, currency: &Currency, total: Decimal, available: Decimal) {
+    let balance = balances.entry(currency.clone()).or_default();
+    balance.total += total;
+    balance.available += available;
+}
+
+impl Order {
+    /// Creates an order with an uuid derived from `sequence`. The order has to be validated before.
+    pub fn new(sequence: u64, market: &Market, side: BittrexOrderSide, quantity: Decimal, rate: Decimal) -> Self {
+        let (kind, reserved, commission_reserved) = match side {
+            BittrexOrderSide::Buy => {
+                let value = mul_rounded(quantity, rate, true);
+                (BittrexOrderKind::LimitBuy, value, mul_rounded(value, Decimal::from_satoshis(COMMISSION_RATE), true))
+            },
+            _ => (BittrexOrderKind::LimitSell, quantity, Decimal::zero()),
+        };
+        Order {
+            uuid: format!("00000000-0000-4000-8000-{:012x}", sequence),
+            sequence: sequence,
+            market: market.clone(),
+            kind: kind,
+            quantity: quantity,
+            quantity_remaining: quantity,
+            limit: rate,
+            reserved: reserved,
+            reserve_remaining: reserved,
+            commission_reserved: commission_reserved,
+            commission_reserve_remaining: commission_reserved,
+            commission_paid: Decimal::zero(),
+            price: Decimal::zero(),
+            opened: Utc::now(),
+            closed: None,
+            cancel_initiated: false,
+        }
+    }
+
+    pub fn is_buy(&self) -> bool {
+        self.kind == BittrexOrderKind::LimitBuy
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.closed.is_none()
+    }
+
+    /// Whether the order would trade at `rate`.
+    pub fn accepts(&self, rate: Decimal) -> bool {
+        if self.is_buy() { rate <= self.limit } else { rate >= self.limit }
+    }
+
+    fn reserved_currency(&self) -> &Currency {
+        if self.is_buy() { &self.market.base } else { &self.market.quote }
+    }
+
+    /// Takes the reservation of a new order from the available balance.
+    pub fn reserve(&self, balances: &mut Balances) -> Result<(), &'static str> {
+        let reservation = self.reserved + self.commission_reserved;
+        let balance = balances.entry(self.reserved_currency().clone()).or_default();
+        if balance.available < reservation {
+            return Err("INSUFFICIENT_FUNDS");
+        }
+        balance.available -= reservation;
+        Ok(())
+    }
+
+    /// Fills `quantity` at `rate` and settles it with the balances of the owner, who pays the commission.
+    ///
+    /// The commission is computed on the value filled so far and each fill pays what is not paid yet,
+    /// so partial fills add up to the commission of a single fill.
+    pub fn fill(&mut self, balances: &mut Balances, quantity: Decimal, rate: Decimal) {
+        let cost = if self.is_buy() { mul_rounded(quantity, rate, false) } else { quantity * rate };
+        self.quantity_remaining -= quantity;
+        self.price += cost;
+        let commission = commission(self.price) - self.commission_paid;
+        self.commission_paid += commission;
+
+        if self.is_buy() {
+            self.reserve_remaining -= cost;
+            self.commission_reserve_remaining -= commission;
+            change_balance(balances, &self.market.base, -(cost + commission), Decimal::zero());
+            change_balance(balances, &self.market.quote, quantity, quantity);
+        } else {
+            self.reserve_remaining -= quantity;
+            change_balance(balances, &self.market.quote, -quantity, Decimal::zero());
+            change_balance(balances, &self.market.base, cost - commission, cost - commission);
+        }
+    }
+
+    /// Closes the order and releases what is left of its reservation.
+    pub fn close(&mut self, balances: &mut Balances) {
+        let released = self.reserve_remaining + self.commission_reserve_remaining;
+        self.reserve_remaining = Decimal::zero();
+        self.commission_reserve_remaining = Decimal::zero();
+        self.closed = Some(Utc::now());
+
+        let currency = self.reserved_currency().clone();
+        change_balance(balances, &currency, Decimal::zero(), released);
+    }
+
+    /// Average rate of the filled quantity, if anything was filled.
+    fn price_per_unit(&self) -> Option<Decimal> {
+        self.price.checked_div(self.quantity - self.quantity_remaining)
+    }
+
+    pub fn to_open_order(&self) -> BittrexOpenOrder {
+        BittrexOpenOrder {
+            uuid: None,
+            order_uuid: self.uuid.clone(),
+            exchange: self.market.clone(),
+            order_type: self.kind.clone(),
+            quantity: self.quantity,
+            quantity_remaining: self.quantity_remaining,
+            limit: self.limit,
+            comission_paid: self.commission_paid,
+            price: self.price,
+            price_per_unit: self.price_per_unit(),
+            opened: self.opened,
+            closed: self.closed,
+            cancel_initiated: self.cancel_initiated,
+            immediate_or_cancel: false,
+            is_conditional: false,
+            condition: Some(BittrexCondition::None),
+            conditional_target: None,
+        }
+    }
+
+    pub fn to_history_order(&self) -> BittrexHistoryOrder {
+        BittrexHistoryOrder {
+            order_uuid: self.uuid.clone(),
+            exchange: self.market.clone(),
+            time_stamp: self.opened,
+            order_type: self.kind.clone(),
+            quantity: self.quantity,
+            quantity_remaining: self.quantity_remaining,
+            limit: self.limit,
+            comission: self.commission_paid,
+            price: self.price,
+            price_per_unit: self.price_per_unit(),
+            immediate_or_cancel: false,
+            is_conditional: false,
+            condition: Some(BittrexCondition::None),
+            conditional_target: None,
+        }
+    }
+
+    pub fn to_order(&self) -> BittrexOrder {
+        BittrexOrder {
+            account_id: None,
+            order_uuid: self.uuid.clone(),
+            exchange: self.market.clone(),
+            order_type: self.kind.clone(),
+            quantity: self.quantity,
+            quantity_remaining: self.quantity_remaining,
+            limit: self.limit,
+            reserved: self.reserved,
+            reserve_remaining: self.reserve_remaining,
+            commission_reserved: self.commission_reserved,
+            commission_reserve_remaining: self.commission_reserve_remaining,
+            comission_paid: self.commission_paid,
+            price: self.price,
+            price_per_unit: self.price_per_unit(),
+            opened: self.opened,
+            closed: self.closed,
+            is_open: self.is_open(),
+            sentinel: self.uuid.clone(),
+            cancel_initiated: self.cancel_initiated,
+            immediate_or_cancel: false,
+            is_conditional: false,
+            condition: Some(BittrexCondition::None),
+            conditional_target: None,
+        }
+    }
+}
+
+/// Orders of an account, closed ones newest first, for the order history.
+pub(crate) fn order_history<'a, I>(orders: I, market: Option<&Market>) -> Vec<BittrexHistoryOrder> where I: Iterator<Item = &'a Order> {
+    let mut closed: Vec<&Order> = orders
+        .filter(|order| !order.is_open() && market.map(|market| order.market == *market).unwrap_or(true))
+        .collect();
+    closed.sort_by(|a, b| b.closed.cmp(&a.closed).then(b.sequence.cmp(&a.sequence)));
+    closed.into_iter().map(Order::to_history_order).collect()
+}
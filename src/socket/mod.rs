@@ -0,0 +1,340 @@
+//! Live market data from the Bittrex websocket API (SignalR hub `c2` at `https://socket.bittrex.com/signalr`).
+//!
+//! A `SocketClient` negotiates a SignalR connection, opens its websocket and subscribes to the exchange deltas
+//! (order book changes and fills) of the configured markets and optionally to the summary deltas of all markets.
+//! The payloads of the hub are base64 encoded, deflate compressed JSON; they are decoded into `SocketEvent`s
+//! which reuse the types of `values` where possible.
+//!
+//! After every (re-)connect the client queries the exchange state of each market, so a local order book can be
+//! rebuilt from `SocketEvent::ExchangeState` and the `SocketEvent::ExchangeDelta`s with a higher nonce.
+//! If the connection is lost, a `SocketEvent::Disconnected` is emitted and the client reconnects after the
+//! reconnect delay.
+//!
+//! `BittrexClientBuilder::build_socket` creates a client with the proxies, headers (including the user agent),
+//! timeout and rate limiter of the builder. The negotiate, connect and start requests of every connection count
+//! against the `Public` limit of the rate limiter.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bittrex_api::socket::{SocketClient, SocketEvent};
+//!
+//! let events = SocketClient::new()
+//!     .exchange_deltas(vec!["BTC-LTC".parse().unwrap()])
+//!     .summary_deltas()
+//!     .spawn();
+//!
+//! for event in events {
+//!     match event {
+//!         SocketEvent::ExchangeState(state) => println!("{}: {} bids", state.market, state.order_book.buy.len()),
+//!         SocketEvent::ExchangeDelta(delta) => println!("{}: {} fills", delta.market, delta.fills.len()),
+//!         SocketEvent::SummaryDeltas(deltas) => println!("{} summaries changed", deltas.summaries.len()),
+//!         SocketEvent::Disconnected(error) => println!("reconnecting: {}", error),
+//!         _ => {},
+//!     }
+//! }
+//! ```
+mod values;
+mod websocket;
+
+pub use self::values::*;
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use reqwest::{self, Proxy};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use serde_json;
+
+use client::{Result, DEFAULT_USER_AGENT, decode_response};
+use endpoint;
+use error::{BittrexError, BittrexErrorType};
+use market::Market;
+use rate_limit::{EndpointClass, RateLimiter};
+use self::websocket::WebSocket;
+
+pub(crate) const SOCKET_URL: &str = "https://socket.bittrex.com/signalr";
+const CLIENT_PROTOCOL: &str = "1.5";
+const HUB: &str = "c2";
+const CONNECTION_DATA: &str = "[{\"name\":\"c2\"}]";
+const DEFAULT_RECONNECT_DELAY: u64 = 5;
+const DEFAULT_TIMEOUT: u64 = 30;
+
+/// Client for the Bittrex websocket API. See the module documentation.
+#[derive(Clone)]
+pub struct SocketClient {
+    url: String,
+    markets: Vec<Market>,
+    summary_deltas: bool,
+    reconnect_delay: Duration,
+    timeout: Duration,
+    http_proxy: Option<String>,
+    https_proxy: Option<String>,
+    headers: HeaderMap,
+    rate_limiter: RateLimiter,
+}
+
+#[derive(Deserialize)]
+struct Negotiation {
+    #[serde(rename = "ConnectionToken")]
+    connection_token: String,
+}
+
+#[derive(Serialize)]
+struct Invocation<'a> {
+    #[serde(rename = "H")]
+    hub: &'a str,
+    #[serde(rename = "M")]
+    method: &'a str,
+    #[serde(rename = "A")]
+    args: Vec<String>,
+    #[serde(rename = "I")]
+    id: String,
+}
+
+/// Message of the server: a response to an invocation (`I`, `R`, `E`), hub messages (`M`) or a keep alive (`{}`).
+#[derive(Deserialize)]
+struct ServerMessage {
+    #[serde(rename = "I")]
+    id: Option<String>,
+    #[serde(rename = "R")]
+    result: Option<serde_json::Value>,
+    #[serde(rename = "E")]
+    error: Option<String>,
+    #[serde(rename = "M", default)]
+    messages: Vec<HubMessage>,
+}
+
+#[derive(Deserialize)]
+struct HubMessage {
+    #[serde(rename = "M")]
+    method: String,
+    #[serde(rename = "A", default)]
+    args: Vec<serde_json::Value>,
+}
+
+struct Connection {
+    websocket: WebSocket,
+    next_id: u32,
+}
+
+impl SocketClient {
+    /// Creates a client for the Bittrex websocket API without subscriptions.
+    pub fn new() -> Self {
+        SocketClient::with_url(SOCKET_URL)
+    }
+
+    /// Creates a client for the SignalR endpoint at `url`, e.g. a local stand-in server.
+    pub fn with_url(url: &str) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+        SocketClient::from_parts(url, None, None, headers, RateLimiter::new())
+    }
+
+    pub(crate) fn from_parts(url: &str, http_proxy: Option<String>, https_proxy: Option<String>, headers: HeaderMap, rate_limiter: RateLimiter) -> Self {
+        SocketClient {
+            url: url.trim_end_matches('/').to_string(),
+            markets: Vec::new(),
+            summary_deltas: false,
+            reconnect_delay: Duration::from_secs(DEFAULT_RECONNECT_DELAY),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+            http_proxy: http_proxy,
+            https_proxy: https_proxy,
+            headers: headers,
+            rate_limiter: rate_limiter,
+        }
+    }
+
+    /// Subscribes to the exchange deltas of the markets and queries their exchange state after every connect.
+    pub fn exchange_deltas(mut self, markets: Vec<Market>) -> Self {
+        self.markets = markets;
+        self
+    }
+
+    /// Subscribes to the summary deltas of all markets.
+    pub fn summary_deltas(mut self) -> Self {
+        self.summary_deltas = true;
+        self
+    }
+
+    /// Sets how long to wait before reconnecting (default: 5 seconds).
+    pub fn reconnect_delay(mut self, reconnect_delay: Duration) -> Self {
+        self.reconnect_delay = reconnect_delay;
+        self
+    }
+
+    /// Sets after how long without any message (the server sends keep alives) the connection is considered lost
+    /// (default: 30 seconds). Also the timeout of the HTTP requests.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Connects on a new thread and sends the events to the returned receiver. The thread ends with the first
+    /// event after the receiver was dropped.
+    pub fn spawn(self) -> Receiver<SocketEvent> {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            while let Err(error) = self.run(&sender) {
+                if sender.send(SocketEvent::Disconnected(error)).is_err() {
+                    break;
+                }
+                thread::sleep(self.reconnect_delay);
+            }
+        });
+        receiver
+    }
+
+    /// Runs one connection. Returns `Ok` when the receiver was dropped and the error which ended the connection otherwise.
+    fn run(&self, sender: &Sender<SocketEvent>) -> Result<()> {
+        let mut connection = self.connect()?;
+        for market in &self.markets {
+            connection.invoke("SubscribeToExchangeDeltas", vec![market.to_string()])?;
+        }
+        if self.summary_deltas {
+            connection.invoke("SubscribeToSummaryDeltas", Vec::new())?;
+        }
+        let mut queries = HashMap::new();
+        for market in &self.markets {
+            let id = connection.invoke("QueryExchangeState", vec![market.to_string()])?;
+            queries.insert(id, market.clone());
+        }
+        if sender.send(SocketEvent::Connected).is_err() {
+            return Ok(());
+        }
+
+        loop {
+            let text = match connection.websocket.read_text()? {
+                Some(text) => text,
+                None => return Err(BittrexError::new(BittrexErrorType::Transport, "Connection closed by the server".to_string())),
+            };
+            for event in events(&text, &mut queries) {
+                if sender.send(event).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Negotiates a connection token, opens the websocket and starts the connection.
+    fn connect(&self) -> Result<Connection> {
+        let mut client_builder = reqwest::Client::builder()
+            .default_headers(self.headers.clone())
+            .timeout(self.timeout);
+        if let Some(ref http_proxy) = self.http_proxy {
+            client_builder = client_builder.proxy(Proxy::http(http_proxy)?);
+        }
+        if let Some(ref https_proxy) = self.https_proxy {
+            client_builder = client_builder.proxy(Proxy::https(https_proxy)?);
+        }
+        let http_client = client_builder.build()?;
+
+        let url = self.request("/negotiate").url();
+        self.acquire_rate_limit()?;
+        let negotiation: Negotiation = get(&http_client, &url)?;
+
+        let url = self.request("/connect")
+            .param("transport", "webSockets")
+            .param("connectionToken", &negotiation.connection_token)
+            .param("tid", 10)
+            .url();
+        let (websocket_url, proxy) = if url.starts_with("https://") {
+            (url.replacen("https://", "wss://", 1), self.https_proxy.as_ref())
+        } else {
+            (url.replacen("http://", "ws://", 1), self.http_proxy.as_ref())
+        };
+        self.acquire_rate_limit()?;
+        let websocket = WebSocket::connect(&websocket_url, self.timeout, proxy.map(String::as_str), &self.headers)?;
+
+        let url = self.request("/start")
+            .param("transport", "webSockets")
+            .param("connectionToken", &negotiation.connection_token)
+            .url();
+        self.acquire_rate_limit()?;
+        let _: serde_json::Value = get(&http_client, &url)?;
+
+        Ok(Connection { websocket: websocket, next_id: 0 })
+    }
+
+    /// Waits until the rate limiter allows another request of the `Public` class.
+    fn acquire_rate_limit(&self) -> Result<()> {
+        let delay = self.rate_limiter.acquire(EndpointClass::Public)?;
+        if delay > Duration::from_secs(0) {
+            thread::sleep(delay);
+        }
+        Ok(())
+    }
+
+    fn request(&self, path: &str) -> endpoint::Request {
+        endpoint::Request::new(&self.url, path)
+            .param("clientProtocol", CLIENT_PROTOCOL)
+            .param("connectionData", CONNECTION_DATA)
+    }
+}
+
+impl Default for SocketClient {
+    fn default() -> Self {
+        SocketClient::new()
+    }
+}
+
+impl Connection {
+    /// Invokes a method of the hub and returns the id of the invocation, which the response refers to.
+    fn invoke(&mut self, method: &str, args: Vec<String>) -> Result<String> {
+        let id = self.next_id.to_string();
+        self.next_id += 1;
+        let invocation = Invocation { hub: HUB, method: method, args: args, id: id.clone() };
+        self.websocket.send_text(&serde_json::to_string(&invocation)?)?;
+        Ok(id)
+    }
+}
+
+fn get<T>(http_client: &reqwest::Client, url: &str) -> Result<T> where for<'de> T: ::serde::Deserialize<'de> {
+    let mut response = http_client.get(url).send()?;
+    let status = response.status();
+    let body = response.text().map_err(|error| BittrexError::from(error).with_status(status.as_u16()).with_url(url))?;
+    decode_response(status, url, &body)
+}
+
+/// Decodes a message of the server into events. Exchange states are matched to their market by the id of the query.
+fn events(text: &str, queries: &mut HashMap<String, Market>) -> Vec<SocketEvent> {
+    let message: ServerMessage = match serde_json::from_str(text) {
+        Ok(message) => message,
+        Err(error) => return vec![SocketEvent::Error(BittrexError::from(error).with_body(text))],
+    };
+
+    let mut events = Vec::new();
+    if let Some(id) = message.id {
+        let market = queries.remove(&id);
+        if let Some(error) = message.error {
+            events.push(SocketEvent::Error(BittrexError::new(BittrexErrorType::Hub, error)));
+        } else if let Some(market) = market {
+            let state = match message.result {
+                Some(serde_json::Value::String(ref payload)) => decode::<ExchangeStateMessage>(payload),
+                _ => Err(BittrexError::new(BittrexErrorType::NoResults, format!("No exchange state for {}", market))),
+            };
+            events.push(match state {
+                Ok(state) => SocketEvent::ExchangeState(state.into_state(market)),
+                Err(error) => SocketEvent::Error(error),
+            });
+        }
+    }
+
+    for hub_message in message.messages {
+        for arg in hub_message.args {
+            let payload = match arg {
+                serde_json::Value::String(payload) => payload,
+                _ => continue,
+            };
+            let event = match hub_message.method.as_str() {
+                "uE" => decode::<ExchangeDelta>(&payload).map(SocketEvent::ExchangeDelta),
+                "uS" => decode::<SummaryDeltasMessage>(&payload).map(|message| SocketEvent::SummaryDeltas(message.into())),
+                _ => continue,
+            };
+            events.push(event.unwrap_or_else(SocketEvent::Error));
+        }
+    }
+    events
+}
@@ -0,0 +1,247 @@
+use std::io::Read;
+
+use base64;
+use chrono::{DateTime, TimeZone, Utc};
+use flate2::read::DeflateDecoder;
+use serde::{Deserialize, Deserializer};
+use serde::de;
+use serde_json;
+
+use decimal::Decimal;
+use error::{BittrexError, BittrexErrorType};
+use market::Market;
+use values::{BittrexFillType, BittrexMarketSummary, BittrexOrderSide, BittrexPublicOrder, BittrexPublicOrderBook, BittrexTrade};
+
+/// Event of a `SocketClient`.
+#[derive(Debug)]
+pub enum SocketEvent {
+    /// The connection was (re-)established and all subscriptions were made.
+    Connected,
+    /// Snapshot of the order book and the latest fills of a market, queried after every (re-)connect.
+    ExchangeState(ExchangeState),
+    /// Changes of the order book and new fills of a subscribed market.
+    ExchangeDelta(ExchangeDelta),
+    /// Changed market summaries.
+    SummaryDeltas(SummaryDeltas),
+    /// A message could not be decoded or the hub rejected an invocation (`BittrexErrorType::Hub`). The connection stays open.
+    Error(BittrexError),
+    /// The connection was lost. The client reconnects after the reconnect delay.
+    Disconnected(BittrexError),
+}
+
+/// Snapshot of a market. Deltas with a nonce up to `nonce` are already contained in it.
+#[derive(Debug)]
+pub struct ExchangeState {
+    pub market: Market,
+    pub nonce: u64,
+    pub order_book: BittrexPublicOrderBook,
+    pub fills: Vec<BittrexTrade>,
+}
+
+/// Changes of a market since the previous delta (`nonce - 1`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ExchangeDelta {
+    #[serde(rename = "M")]
+    pub market: Market,
+    #[serde(rename = "N")]
+    pub nonce: u64,
+    #[serde(rename = "Z")]
+    pub buys: Vec<OrderBookDelta>,
+    #[serde(rename = "S")]
+    pub sells: Vec<OrderBookDelta>,
+    #[serde(rename = "f")]
+    pub fills: Vec<Fill>,
+}
+
+/// Change of the quantity at a rate of the order book. `quantity` is the new total quantity at the rate.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderBookDelta {
+    #[serde(rename = "TY")]
+    pub delta_type: DeltaType,
+    #[serde(rename = "R")]
+    pub rate: Decimal,
+    #[serde(rename = "Q")]
+    pub quantity: Decimal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaType {
+    Add,
+    Remove,
+    Update,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Fill {
+    #[serde(rename = "FI")]
+    pub id: Option<u64>,
+    #[serde(rename = "OT")]
+    pub order_type: BittrexOrderSide,
+    #[serde(rename = "R")]
+    pub rate: Decimal,
+    #[serde(rename = "Q")]
+    pub quantity: Decimal,
+    #[serde(rename = "T", deserialize_with = "millis")]
+    pub time_stamp: DateTime<Utc>,
+}
+
+/// Summaries of the markets which changed since the previous update.
+#[derive(Debug)]
+pub struct SummaryDeltas {
+    pub nonce: u64,
+    pub summaries: Vec<BittrexMarketSummary>,
+}
+
+impl<'de> Deserialize<'de> for DeltaType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(DeltaType::Add),
+            1 => Ok(DeltaType::Remove),
+            2 => Ok(DeltaType::Update),
+            value => Err(de::Error::custom(format!("Unknown delta type {}", value))),
+        }
+    }
+}
+
+/// Response of `QueryExchangeState`.
+#[derive(Deserialize)]
+pub(crate) struct ExchangeStateMessage {
+    #[serde(rename = "N")]
+    nonce: u64,
+    #[serde(rename = "Z")]
+    buys: Vec<StateOrder>,
+    #[serde(rename = "S")]
+    sells: Vec<StateOrder>,
+    #[serde(rename = "f")]
+    fills: Vec<StateFill>,
+}
+
+#[derive(Deserialize)]
+struct StateOrder {
+    #[serde(rename = "Q")]
+    quantity: Decimal,
+    #[serde(rename = "R")]
+    rate: Decimal,
+}
+
+#[derive(Deserialize)]
+struct StateFill {
+    #[serde(rename = "I")]
+    id: u32,
+    #[serde(rename = "T", deserialize_with = "millis")]
+    time_stamp: DateTime<Utc>,
+    #[serde(rename = "Q")]
+    quantity: Decimal,
+    #[serde(rename = "P")]
+    price: Decimal,
+    #[serde(rename = "t")]
+    total: Decimal,
+    #[serde(rename = "F")]
+    fill_type: BittrexFillType,
+    #[serde(rename = "OT")]
+    order_type: BittrexOrderSide,
+}
+
+/// Payload of `uS` messages.
+#[derive(Deserialize)]
+pub(crate) struct SummaryDeltasMessage {
+    #[serde(rename = "N")]
+    nonce: u64,
+    #[serde(rename = "D")]
+    deltas: Vec<SummaryDelta>,
+}
+
+#[derive(Deserialize)]
+struct SummaryDelta {
+    #[serde(rename = "M")]
+    market_name: Market,
+    #[serde(rename = "H")]
+    high: Decimal,
+    #[serde(rename = "L")]
+    low: Decimal,
+    #[serde(rename = "V")]
+    volume: Decimal,
+    #[serde(rename = "l")]
+    last: Decimal,
+    #[serde(rename = "m")]
+    base_volume: Decimal,
+    #[serde(rename = "T", deserialize_with = "millis")]
+    time_stamp: DateTime<Utc>,
+    #[serde(rename = "B")]
+    bid: Decimal,
+    #[serde(rename = "A")]
+    ask: Decimal,
+    #[serde(rename = "G")]
+    open_buy_orders: u32,
+    #[serde(rename = "g")]
+    open_sell_orders: u32,
+    #[serde(rename = "PD")]
+    prev_day: Decimal,
+    #[serde(rename = "x", deserialize_with = "millis")]
+    created: DateTime<Utc>,
+}
+
+impl ExchangeStateMessage {
+    pub(crate) fn into_state(self, market: Market) -> ExchangeState {
+        let order = |order: StateOrder| BittrexPublicOrder { quantity: order.quantity, rate: order.rate };
+        ExchangeState {
+            market: market,
+            nonce: self.nonce,
+            order_book: BittrexPublicOrderBook {
+                buy: self.buys.into_iter().map(order).collect(),
+                sell: self.sells.into_iter().map(order).collect(),
+            },
+            fills: self.fills.into_iter().map(|fill| BittrexTrade {
+                id: fill.id,
+                time_stamp: fill.time_stamp,
+                quantity: fill.quantity,
+                price: fill.price,
+                total: fill.total,
+                fill_type: fill.fill_type,
+                order_type: fill.order_type,
+            }).collect(),
+        }
+    }
+}
+
+impl From<SummaryDeltasMessage> for SummaryDeltas {
+    fn from(message: SummaryDeltasMessage) -> Self {
+        SummaryDeltas {
+            nonce: message.nonce,
+            summaries: message.deltas.into_iter().map(|delta| BittrexMarketSummary {
+                market_name: delta.market_name,
+                high: delta.high,
+                low: delta.low,
+                volume: delta.volume,
+                last: delta.last,
+                base_volume: delta.base_volume,
+                time_stamp: delta.time_stamp,
+                bid: delta.bid,
+                ask: delta.ask,
+                open_buy_orders: delta.open_buy_orders,
+                open_sell_orders: delta.open_sell_orders,
+                prev_day: delta.prev_day,
+                created: delta.created,
+                display_market_name: None,
+            }).collect(),
+        }
+    }
+}
+
+/// Decodes a payload of the `c2` hub: base64 encoded, raw deflate compressed JSON.
+pub(crate) fn decode<T>(payload: &str) -> Result<T, BittrexError> where for<'de> T: Deserialize<'de> {
+    let compressed = base64::decode(payload)
+        .map_err(|error| BittrexError::new(BittrexErrorType::Decoding, error.to_string()).with_source(error))?;
+    let mut json = String::new();
+    DeflateDecoder::new(&compressed[..]).read_to_string(&mut json)
+        .map_err(|error| BittrexError::new(BittrexErrorType::Decoding, error.to_string()).with_source(error))?;
+    serde_json::from_str(&json)
+        .map_err(|error| BittrexError::new(BittrexErrorType::Decoding, error.to_string()).with_body(&json).with_source(error))
+}
+
+/// Timestamps of the hub are milliseconds since the UNIX epoch.
+fn millis<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error> where D: Deserializer<'de> {
+    let millis = i64::deserialize(deserializer)?;
+    Utc.timestamp_millis_opt(millis).single()
+        .ok_or_else(|| de::Error::custom(format!("Invalid timestamp {}", millis)))
+}
@@ -0,0 +1,295 @@
+//! Minimal websocket client (RFC 6455) for the SignalR connection: text messages, ping/pong and close,
+//! optionally tunneled through an HTTP proxy.
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use base64;
+use native_tls::{TlsConnector, TlsStream};
+use rand;
+use reqwest::header::HeaderMap;
+use sha1::{Digest, Sha1};
+
+use error::{BittrexError, BittrexErrorType};
+
+/// Largest message accepted from the server.
+const MAX_MESSAGE_LENGTH: u64 = 16 * 1024 * 1024;
+
+/// Appended to the key of the handshake before hashing it into `Sec-WebSocket-Accept` (RFC 6455, section 1.3).
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+pub struct WebSocket {
+    stream: Stream,
+}
+
+enum Stream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl WebSocket {
+    /// Connects to a `ws://` or `wss://` url, through the HTTP proxy at `proxy` if any, and sends `headers` with the handshake.
+    /// Connecting, reads and writes fail with a transport error after `timeout`.
+    pub fn connect(url: &str, timeout: Duration, proxy: Option<&str>, headers: &HeaderMap) -> Result<WebSocket, BittrexError> {
+        let (secure, rest) = match (url.strip_prefix("wss://"), url.strip_prefix("ws://")) {
+            (Some(rest), _) => (true, rest),
+            (None, Some(rest)) => (false, rest),
+            (None, None) => return Err(BittrexError::new(BittrexErrorType::Configuration, format!("Invalid websocket url '{}'", url))),
+        };
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+        let host = authority.split(':').next().unwrap_or(authority);
+        let address = if authority.contains(':') { authority.to_string() } else { format!("{}:{}", authority, if secure { 443 } else { 80 }) };
+
+        let tcp_stream = match proxy {
+            Some(proxy) => connect_proxy(proxy, &address, timeout)?,
+            None => connect_timeout(&address, timeout)?,
+        };
+        let stream = if secure {
+            let connector = TlsConnector::new()
+                .map_err(|error| BittrexError::new(BittrexErrorType::Configuration, error.to_string()).with_source(error))?;
+            let tls_stream = connector.connect(host, tcp_stream)
+                .map_err(|error| BittrexError::new(BittrexErrorType::Transport, error.to_string()))?;
+            Stream::Tls(Box::new(tls_stream))
+        } else {
+            Stream::Plain(tcp_stream)
+        };
+
+        let mut websocket = WebSocket { stream: stream };
+        websocket.handshake(authority, path, headers)?;
+        Ok(websocket)
+    }
+
+    pub fn send_text(&mut self, text: &str) -> Result<(), BittrexError> {
+        self.send_frame(OPCODE_TEXT, text.as_bytes())
+    }
+
+    /// Reads the next text message, answering pings on the way. Returns `None` when the server closed the connection.
+    pub fn read_text(&mut self) -> Result<Option<String>, BittrexError> {
+        let mut message = Vec::new();
+        loop {
+            let (fin, opcode, payload) = self.read_frame()?;
+            match opcode {
+                OPCODE_TEXT | OPCODE_BINARY | OPCODE_CONTINUATION => {
+                    if message.len() as u64 + payload.len() as u64 > MAX_MESSAGE_LENGTH {
+                        return Err(BittrexError::new(BittrexErrorType::Decoding, "Websocket message too long".to_string()));
+                    }
+                    message.extend(payload);
+                    if fin {
+                        return String::from_utf8(message)
+                            .map(Some)
+                            .map_err(|error| BittrexError::new(BittrexErrorType::Decoding, error.to_string()).with_source(error));
+                    }
+                },
+                OPCODE_PING => self.send_frame(OPCODE_PONG, &payload)?,
+                OPCODE_PONG => {},
+                OPCODE_CLOSE => {
+                    let _ = self.send_frame(OPCODE_CLOSE, &payload);
+                    return Ok(None);
+                },
+                _ => return Err(BittrexError::new(BittrexErrorType::Decoding, format!("Unknown websocket opcode {}", opcode))),
+            }
+        }
+    }
+
+    fn handshake(&mut self, authority: &str, path: &str, headers: &HeaderMap) -> Result<(), BittrexError> {
+        let key = base64::encode(&rand::random::<[u8; 16]>());
+        let mut request = format!(
+            "GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\n",
+            path, authority, key
+        ).into_bytes();
+        for (name, value) in headers {
+            request.extend_from_slice(name.as_str().as_bytes());
+            request.extend_from_slice(b": ");
+            request.extend_from_slice(value.as_bytes());
+            request.extend_from_slice(b"\r\n");
+        }
+        request.extend_from_slice(b"\r\n");
+        self.stream.write_all(&request).map_err(transport_error)?;
+
+        let head = read_head(&mut self.stream)?;
+        let status_line = head.lines().next().unwrap_or("");
+        if status_line.split_whitespace().nth(1) != Some("101") {
+            return Err(BittrexError::new(BittrexErrorType::Transport, format!("Websocket upgrade failed: {}", status_line)));
+        }
+
+        let accept = head.lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(name), Some(value)) if name.trim().eq_ignore_ascii_case("Sec-WebSocket-Accept") => Some(value.trim()),
+                    _ => None,
+                }
+            })
+            .next();
+        let expected = base64::encode(&Sha1::digest(format!("{}{}", key, ACCEPT_GUID).as_bytes()));
+        if accept != Some(expected.as_str()) {
+            return Err(BittrexError::new(BittrexErrorType::Transport, "Websocket upgrade failed: invalid Sec-WebSocket-Accept".to_string()));
+        }
+        Ok(())
+    }
+
+    fn read_frame(&mut self) -> Result<(bool, u8, Vec<u8>), BittrexError> {
+        let mut header = [0u8; 2];
+        self.stream.read_exact(&mut header).map_err(transport_error)?;
+        let fin = header[0] & 0x80 != 0;
+        let opcode = header[0] & 0x0F;
+        let masked = header[1] & 0x80 != 0;
+
+        let length = match header[1] & 0x7F {
+            126 => {
+                let mut length = [0u8; 2];
+                self.stream.read_exact(&mut length).map_err(transport_error)?;
+                u64::from(u16::from_be_bytes(length))
+            },
+            127 => {
+                let mut length = [0u8; 8];
+                self.stream.read_exact(&mut length).map_err(transport_error)?;
+                u64::from_be_bytes(length)
+            },
+            length => u64::from(length),
+        };
+        if length > MAX_MESSAGE_LENGTH {
+            return Err(BittrexError::new(BittrexErrorType::Decoding, "Websocket frame too long".to_string()));
+        }
+
+        let mut mask = [0u8; 4];
+        if masked {
+            self.stream.read_exact(&mut mask).map_err(transport_error)?;
+        }
+        let mut payload = vec![0u8; length as usize];
+        self.stream.read_exact(&mut payload).map_err(transport_error)?;
+        if masked {
+            apply_mask(&mut payload, mask);
+        }
+        Ok((fin, opcode, payload))
+    }
+
+    /// Sends a single frame, masked as required for clients.
+    fn send_frame(&mut self, opcode: u8, payload: &[u8]) -> Result<(), BittrexError> {
+        let mut frame = vec![0x80 | opcode];
+        let length = payload.len();
+        if length < 126 {
+            frame.push(0x80 | length as u8);
+        } else if length <= 0xFFFF {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(length as u16).to_be_bytes());
+        } else {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&(length as u64).to_be_bytes());
+        }
+
+        let mask = rand::random::<[u8; 4]>();
+        frame.extend_from_slice(&mask);
+        let start = frame.len();
+        frame.extend_from_slice(payload);
+        apply_mask(&mut frame[start..], mask);
+
+        self.stream.write_all(&frame).and_then(|_| self.stream.flush()).map_err(transport_error)
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        match *self {
+            Stream::Plain(ref mut stream) => stream.read(buffer),
+            Stream::Tls(ref mut stream) => stream.read(buffer),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        match *self {
+            Stream::Plain(ref mut stream) => stream.write(buffer),
+            Stream::Tls(ref mut stream) => stream.write(buffer),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {
+            Stream::Plain(ref mut stream) => stream.flush(),
+            Stream::Tls(ref mut stream) => stream.flush(),
+        }
+    }
+}
+
+/// Connects to the first address `address` resolves to which accepts the connection within `timeout`,
+/// and lets reads and writes of the connection time out after `timeout` as well.
+fn connect_timeout(address: &str, timeout: Duration) -> Result<TcpStream, BittrexError> {
+    let mut last_error = None;
+    for socket_address in address.to_socket_addrs().map_err(transport_error)? {
+        match TcpStream::connect_timeout(&socket_address, timeout) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(timeout)).map_err(transport_error)?;
+                stream.set_write_timeout(Some(timeout)).map_err(transport_error)?;
+                return Ok(stream);
+            },
+            Err(error) => last_error = Some(error),
+        }
+    }
+    Err(match last_error {
+        Some(error) => transport_error(error),
+        None => BittrexError::new(BittrexErrorType::Transport, format!("Could not resolve {}", address)),
+    })
+}
+
+/// Opens a tunnel to `address` with a `CONNECT` request to the HTTP proxy at `proxy` (`http://[user:password@]host[:port]`).
+fn connect_proxy(proxy: &str, address: &str, timeout: Duration) -> Result<TcpStream, BittrexError> {
+    let rest = match proxy.find("://") {
+        Some(index) if proxy[..index].eq_ignore_ascii_case("http") => &proxy[index + 3..],
+        Some(_) => return Err(BittrexError::new(BittrexErrorType::Configuration, format!("Unsupported proxy url '{}'", proxy))),
+        None => proxy,
+    };
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let (credentials, host) = match authority.rfind('@') {
+        Some(index) => (Some(&authority[..index]), &authority[index + 1..]),
+        None => (None, authority),
+    };
+    let proxy_address = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+
+    let mut stream = connect_timeout(&proxy_address, timeout)?;
+    let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", address);
+    if let Some(credentials) = credentials {
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", base64::encode(credentials)));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).map_err(transport_error)?;
+
+    let head = read_head(&mut stream)?;
+    let status_line = head.lines().next().unwrap_or("");
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(BittrexError::new(BittrexErrorType::Transport, format!("Proxy tunnel failed: {}", status_line)));
+    }
+    Ok(stream)
+}
+
+/// Reads the head of an HTTP response, up to and including the empty line.
+fn read_head<R: Read>(stream: &mut R) -> Result<String, BittrexError> {
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        stream.read_exact(&mut byte).map_err(transport_error)?;
+        head.push(byte[0]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+fn apply_mask(payload: &mut [u8], mask: [u8; 4]) {
+    for (index, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[index % 4];
+    }
+}
+
+fn transport_error(error: io::Error) -> BittrexError {
+    BittrexError::new(BittrexErrorType::Transport, error.to_string()).with_source(error)
+}
@@ -0,0 +1,182 @@
+//! Continuous feed of the trades of several markets, polled from `get_market_history`.
+//!
+//! Every poll returns a window of the most recent trades (about 100), which overlaps with the window of the
+//! previous poll. A `TradeFeed` remembers the highest trade id it has seen per market and only emits newer trades,
+//! oldest first. If a window does not reach back to the last seen trade, more trades happened between two polls
+//! than fit into the window and a `TradeEvent::Gap` is emitted before the trades of the window.
+//!
+//! The markets are polled in turn, each at most once per poll interval. A `RateLimiter` shares one budget of
+//! requests between all markets (and all clients which use clones of the same limiter).
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use bittrex_api::BittrexClient;
+//! use bittrex_api::rate_limit::{EndpointClass, RateLimit, RateLimiter};
+//! use bittrex_api::trade_feed::{TradeEvent, TradeFeed};
+//!
+//! let markets = vec!["BTC-LTC".parse().unwrap(), "BTC-ETH".parse().unwrap()];
+//! let trades = TradeFeed::new(BittrexClient::new_public(), markets)
+//!     .poll_interval(Duration::from_secs(5))
+//!     .rate_limiter(RateLimiter::new().limit(EndpointClass::Public, RateLimit::per_minute(30)))
+//!     .spawn();
+//!
+//! for event in trades {
+//!     match event {
+//!         TradeEvent::Trade(market, trade) => println!("{} {} @ {}", market, trade.quantity, trade.price),
+//!         TradeEvent::Gap { market, .. } => println!("{}: trades missed", market),
+//!         TradeEvent::Error(market, error) => println!("{}: {}", market, error),
+//!     }
+//! }
+//! ```
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use api::MarketData;
+use error::BittrexError;
+use market::Market;
+use rate_limit::{EndpointClass, RateLimiter};
+use values::BittrexTrade;
+
+const DEFAULT_POLL_INTERVAL: u64 = 10;
+
+/// Event of a `TradeFeed`.
+#[derive(Debug)]
+pub enum TradeEvent {
+    /// A trade which was not emitted before. The trades of a market are emitted in the order of their ids.
+    Trade(Market, BittrexTrade),
+    /// The window of a poll did not reach back to the last seen trade (its oldest trade is newer),
+    /// so trades between `last_id` and `next_id` may have been missed.
+    Gap { market: Market, last_id: u32, next_id: u32 },
+    /// Polling the market failed. It is polled again after the poll interval.
+    Error(Market, BittrexError),
+}
+
+/// Polls the trades of several markets. See the module documentation.
+pub struct TradeFeed<M> {
+    market_data: M,
+    markets: Vec<MarketFeed>,
+    poll_interval: Duration,
+    rate_limiter: RateLimiter,
+    events: VecDeque<TradeEvent>,
+}
+
+struct MarketFeed {
+    market: Market,
+    last_id: Option<u32>,
+    next_poll: Instant,
+}
+
+impl<M: MarketData> TradeFeed<M> {
+    /// Creates a feed which polls each of the markets right away and then every 10 seconds.
+    pub fn new(market_data: M, markets: Vec<Market>) -> Self {
+        let now = Instant::now();
+        TradeFeed {
+            market_data: market_data,
+            markets: markets.into_iter().map(|market| MarketFeed { market: market, last_id: None, next_poll: now }).collect(),
+            poll_interval: Duration::from_secs(DEFAULT_POLL_INTERVAL),
+            rate_limiter: RateLimiter::new(),
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Sets how often each market is polled (default: 10 seconds).
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Limits the polls of all markets together with the `Public` limit of the limiter (default: no limit).
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// The highest trade id emitted for the market.
+    pub fn last_id(&self, market: &Market) -> Option<u32> {
+        self.markets.iter().find(|feed| feed.market == *market).and_then(|feed| feed.last_id)
+    }
+
+    /// Waits until the next market is due, polls it and returns its events.
+    pub fn poll(&mut self) -> Vec<TradeEvent> {
+        let feed = match self.markets.iter_mut().min_by_key(|feed| feed.next_poll) {
+            Some(feed) => feed,
+            None => return Vec::new(),
+        };
+
+        let now = Instant::now();
+        if feed.next_poll > now {
+            thread::sleep(feed.next_poll - now);
+        }
+        let result = match self.rate_limiter.acquire(EndpointClass::Public) {
+            Ok(delay) => {
+                thread::sleep(delay);
+                self.market_data.get_market_history(&feed.market)
+            },
+            Err(error) => Err(error),
+        };
+        feed.next_poll = Instant::now() + self.poll_interval;
+
+        match result {
+            Ok(trades) => feed.accept(trades),
+            Err(error) => vec![TradeEvent::Error(feed.market.clone(), error)],
+        }
+    }
+
+    /// Polls the markets on a new thread and sends their events to the returned receiver,
+    /// until the receiver is dropped.
+    pub fn spawn(self) -> Receiver<TradeEvent> where M: Send + 'static {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for event in self {
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+        receiver
+    }
+}
+
+/// Blocks until the next event. Ends only if the feed has no markets.
+impl<M: MarketData> Iterator for TradeFeed<M> {
+    type Item = TradeEvent;
+
+    fn next(&mut self) -> Option<TradeEvent> {
+        while self.events.is_empty() {
+            if self.markets.is_empty() {
+                return None;
+            }
+            let events = self.poll();
+            self.events.extend(events);
+        }
+        self.events.pop_front()
+    }
+}
+
+impl MarketFeed {
+    /// Returns the trades of the window newer than the last seen trade, preceded by a gap if the window does not reach back to it.
+    fn accept(&mut self, mut trades: Vec<BittrexTrade>) -> Vec<TradeEvent> {
+        trades.sort_by_key(|trade| trade.id);
+
+        let mut events = Vec::new();
+        if let Some(last_id) = self.last_id {
+            if let Some(first) = trades.first() {
+                if first.id > last_id {
+                    events.push(TradeEvent::Gap { market: self.market.clone(), last_id: last_id, next_id: first.id });
+                }
+            }
+            trades.retain(|trade| trade.id > last_id);
+        }
+
+        if let Some(last) = trades.last() {
+            self.last_id = Some(last.id);
+        }
+        let market = &self.market;
+        events.extend(trades.into_iter().map(|trade| TradeEvent::Trade(market.clone(), trade)));
+        events
+    }
+}
@@ -0,0 +1,117 @@
+//! Pluggable HTTP backend of the blocking `BittrexClient`.
+//!
+//! The client builds, signs and decodes all calls itself and only hands the finished request to an
+//! `HttpTransport`. By default this is a `ReqwestTransport`; any other implementation (an instrumented
+//! HTTP stack, an in-memory fake for tests, ...) can be set with `BittrexClientBuilder::transport`.
+use std::fmt;
+use std::sync::Arc;
+
+use reqwest::Client;
+
+pub use reqwest::{Method, StatusCode};
+
+use error::BittrexError;
+
+/// A request to the Bittrex API, which is fully built and signed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// `GET` for all v1.1 calls, v3 also uses `POST` and `DELETE`.
+    pub method: Method,
+    /// The complete url including all parameters (and for private calls the api key and nonce).
+    pub url: String,
+    /// Headers of the call, e.g. the `apisign` header of private calls.
+    pub headers: Vec<(String, String)>,
+    /// JSON body of v3 `POST` calls.
+    pub body: Option<String>,
+}
+
+impl HttpRequest {
+    /// A `GET` request without body.
+    pub fn get(url: String, headers: Vec<(String, String)>) -> Self {
+        HttpRequest { method: Method::GET, url: url, headers: headers, body: None }
+    }
+}
+
+/// The response to an `HttpRequest`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    /// The url of the response, which differs from the url of the request after a redirect.
+    pub url: String,
+    pub body: String,
+}
+
+/// Sends requests to the Bittrex API.
+///
+/// A response with any HTTP status has to be returned as `Ok`, the client checks the status itself.
+/// Failures before a response was received should be returned as errors of type `BittrexErrorType::Transport`,
+/// which are retried according to the retry policy of the client.
+///
+/// # Examples
+///
+/// ```rust
+/// use bittrex_api::BittrexClient;
+/// use bittrex_api::error::BittrexError;
+/// use bittrex_api::transport::{HttpRequest, HttpResponse, HttpTransport, StatusCode};
+///
+/// struct NoMarkets;
+///
+/// impl HttpTransport for NoMarkets {
+///     fn send(&self, request: &HttpRequest) -> Result<HttpResponse, BittrexError> {
+///         Ok(HttpResponse {
+///             status: StatusCode::OK,
+///             url: request.url.clone(),
+///             body: r#"{"success":true,"message":"","result":[]}"#.to_string(),
+///         })
+///     }
+/// }
+///
+/// let bittrex_client = BittrexClient::builder().transport(NoMarkets).build().unwrap();
+/// assert!(bittrex_client.get_markets().unwrap().is_empty());
+/// ```
+pub trait HttpTransport: Send + Sync {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, BittrexError>;
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, BittrexError> {
+        (**self).send(request)
+    }
+}
+
+/// The default transport, which sends requests with a blocking reqwest `Client`.
+#[derive(Clone)]
+pub struct ReqwestTransport {
+    http_client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(http_client: Client) -> Self {
+        ReqwestTransport { http_client: http_client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, BittrexError> {
+        let mut request_builder = self.http_client.request(request.method.clone(), &request.url);
+        for &(ref name, ref value) in &request.headers {
+            request_builder = request_builder.header(name.as_str(), value.as_str());
+        }
+        if let Some(ref body) = request.body {
+            request_builder = request_builder.body(body.clone());
+        }
+
+        let mut resp = request_builder.send()?;
+        let status = resp.status();
+        let url = resp.url().to_string();
+        let body = resp.text()
+            .map_err(|error| BittrexError::from(error).with_status(status.as_u16()).with_url(&url))?;
+        Ok(HttpResponse { status: status, url: url, body: body })
+    }
+}
+
+impl fmt::Debug for ReqwestTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ReqwestTransport")
+    }
+}
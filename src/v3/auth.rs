@@ -0,0 +1,69 @@
+//! Header-based signing of private v3 calls.
+//!
+//! Instead of a nonce in the url, every private call carries four headers: the api key, a timestamp in milliseconds,
+//! the SHA-512 hash of the request body (`Api-Content-Hash`) and the HMAC-SHA512, keyed with the api secret, of
+//! timestamp, url, method and content hash concatenated (`Api-Signature`). Hashes are lower case hex.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bittrex_api::v3::auth;
+//!
+//! let headers = auth::headers("KEY", "SECRET", 1_577_836_800_000, "GET", "https://api.bittrex.com/v3/balances", "");
+//!
+//! assert_eq!(headers[0], ("Api-Key".to_string(), "KEY".to_string()));
+//! assert_eq!(headers[2].1, auth::content_hash(""));
+//! ```
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac, MacResult};
+use sha2::{Digest, Sha512};
+use generic_array::typenum::U64;
+
+/// The `Api-Key`, `Api-Timestamp`, `Api-Content-Hash` and `Api-Signature` headers of a call.
+pub fn headers(api_key: &str, api_secret: &str, timestamp: u64, method: &str, url: &str, body: &str) -> Vec<(String, String)> {
+    let timestamp = timestamp.to_string();
+    let content_hash = content_hash(body);
+    let signature = signature(api_secret, &timestamp, url, method, &content_hash);
+
+    vec![
+        ("Api-Key".to_string(), api_key.to_string()),
+        ("Api-Timestamp".to_string(), timestamp),
+        ("Api-Content-Hash".to_string(), content_hash),
+        ("Api-Signature".to_string(), signature),
+    ]
+}
+
+/// The SHA-512 hash of the body, which is empty for `GET` and `DELETE` calls.
+pub fn content_hash(body: &str) -> String {
+    let mut sha = Sha512::default();
+    sha.input(body.as_bytes());
+
+    to_lower_hex(&sha.result())
+}
+
+/// The HMAC-SHA512 of `timestamp + url + method + content_hash` keyed with the api secret.
+pub fn signature(api_secret: &str, timestamp: &str, url: &str, method: &str, content_hash: &str) -> String {
+    let mut hmac = Hmac::<Sha512>::new(api_secret.as_bytes());
+    hmac.input(timestamp.as_bytes());
+    hmac.input(url.as_bytes());
+    hmac.input(method.as_bytes());
+    hmac.input(content_hash.as_bytes());
+    let code: MacResult<U64> = hmac.result();
+
+    to_lower_hex(code.code())
+}
+
+/// The current time in milliseconds since the UNIX epoch, as sent in `Api-Timestamp`.
+pub fn timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs() * 1000 + u64::from(since_epoch.subsec_millis()))
+        .unwrap_or(0)
+}
+
+fn to_lower_hex(bytes: &[u8]) -> String {
+    let strs: Vec<String> = bytes.iter()
+                                .map(|b| format!("{:02x}", b))
+                                .collect();
+    strs.join("")
+}
@@ -0,0 +1,258 @@
+//! Client for the Bittrex v3 API.
+//!
+//! The v3 API is a REST API at `https://api.bittrex.com/v3` with header-based authentication (see `auth`).
+//! Markets are named by v3 symbols (`LTC-BTC`, see `symbol`), but the client takes and returns the same `Market`
+//! and `Currency` values as the v1.1 client. `BittrexClientBuilder::build_v3` creates a `BittrexV3Client`
+//! with the transport, retry policy and rate limiter of the builder.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use bittrex_api::{BittrexClient, Decimal};
+//! use bittrex_api::v3::{V3NewOrder, V3OrderDirection, V3TimeInForce};
+//!
+//! let bittrex_client = BittrexClient::builder()
+//!     .credentials("APIKEY".to_string(), "APISECRET".to_string())
+//!     .build_v3()
+//!     .unwrap();
+//! let market = "BTC-LTC".parse().unwrap();
+//! let ticker = bittrex_client.get_ticker(&market).unwrap();
+//! let order = V3NewOrder::limit(&market, V3OrderDirection::Buy, Decimal::new(1, 0), ticker.bid_rate, V3TimeInForce::GoodTilCancelled);
+//! let order = bittrex_client.place_order(&order).unwrap();
+//! ```
+pub mod auth;
+mod values;
+
+pub use self::values::*;
+
+use serde;
+use serde_json;
+
+use client::{Authenticated, BittrexClient, Public, Result, decode_response};
+use endpoint;
+use error::{BittrexError, BittrexErrorType};
+use market::{Currency, Market};
+use rate_limit::EndpointClass;
+use transport::{HttpRequest, HttpResponse, Method};
+use values::TickInterval;
+
+pub(crate) const API_URL: &str = "https://api.bittrex.com/v3";
+
+/// Client for the Bittrex v3 API.
+///
+/// Like `BittrexClient`, a `BittrexV3Client<Public>` only provides the public `/markets` endpoints.
+#[derive(Clone)]
+pub struct BittrexV3Client<A = Authenticated> {
+    api_url: String,
+    client: BittrexClient<A>,
+}
+
+impl BittrexV3Client<Public> {
+    /// Creates a client without credentials, which only provides the public endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized. Use `BittrexClientBuilder::build_v3` to handle this case.
+    pub fn new_public() -> Self {
+        BittrexClient::builder().build_v3().expect("HTTP client should be initialized!")
+    }
+}
+
+impl BittrexV3Client<Authenticated> {
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized. Use `BittrexClientBuilder::build_v3` to handle this case.
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        BittrexClient::builder().credentials(api_key, api_secret).build_v3().expect("HTTP client should be initialized!")
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be initialized. Use `BittrexClientBuilder::build_v3` to handle this case.
+    pub fn new_override_api_url(api_key: String, api_secret: String, api_url: String) -> Self {
+        BittrexClient::builder().credentials(api_key, api_secret).api_url(api_url).build_v3().expect("HTTP client should be initialized!")
+    }
+}
+
+impl<A> BittrexV3Client<A> {
+    pub(crate) fn from_parts(api_url: String, client: BittrexClient<A>) -> Self {
+        BittrexV3Client { api_url: api_url, client: client }
+    }
+
+    pub fn get_markets(&self) -> Result<Vec<V3Market>> {
+        self.call_public_api(&endpoint::Request::new(&self.api_url, "/markets"))
+    }
+
+    pub fn get_market(&self, market: &Market) -> Result<V3Market> {
+        self.call_public_api(&endpoint::Request::new(&self.api_url, &path(&["markets", &symbol::format(market)])))
+    }
+
+    pub fn get_tickers(&self) -> Result<Vec<V3Ticker>> {
+        self.call_public_api(&endpoint::Request::new(&self.api_url, "/markets/tickers"))
+    }
+
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use bittrex_api::v3::BittrexV3Client;
+    ///
+    /// let bittrex_client = BittrexV3Client::new_public();
+    /// let ticker = bittrex_client.get_ticker(&"BTC-LTC".parse().unwrap()).unwrap();
+    /// ```
+    pub fn get_ticker(&self, market: &Market) -> Result<V3Ticker> {
+        self.call_public_api(&endpoint::Request::new(&self.api_url, &path(&["markets", &symbol::format(market), "ticker"])))
+    }
+
+    /// Returns the order book of the market with `depth` entries per side (1, 25 or 500; default: 25).
+    pub fn get_order_book(&self, market: &Market, depth: Option<u32>) -> Result<V3OrderBook> {
+        let mut request = endpoint::Request::new(&self.api_url, &path(&["markets", &symbol::format(market), "orderbook"]));
+        if let Some(depth) = depth {
+            request = request.param("depth", depth);
+        }
+        self.call_public_api(&request)
+    }
+
+    /// Returns the recent candles of a market, oldest first. The v3 API has no `TickInterval::ThirtyMin` candles.
+    pub fn get_candles(&self, market: &Market, tick_interval: TickInterval) -> Result<Vec<V3Candle>> {
+        let interval = candle_interval(tick_interval).ok_or_else(|| {
+            BittrexError::new(BittrexErrorType::Configuration, format!("The v3 API has no {} candles", tick_interval))
+        })?;
+        self.call_public_api(&endpoint::Request::new(&self.api_url, &path(&["markets", &symbol::format(market), "candles", interval, "recent"])))
+    }
+
+    fn call_public_api<T>(&self, request: &endpoint::Request) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
+        let resp = self.client.send_with_retries(EndpointClass::Public, self.client.max_retries(false), || Ok(HttpRequest::get(request.url(), Vec::new())))?;
+        read_response(resp)
+    }
+}
+
+impl BittrexV3Client<Authenticated> {
+    pub fn get_balances(&self) -> Result<Vec<V3Balance>> {
+        self.call_private_api(Method::GET, &endpoint::Request::new(&self.api_url, "/balances"), None)
+    }
+
+    pub fn get_balance(&self, currency: &Currency) -> Result<V3Balance> {
+        self.call_private_api(Method::GET, &endpoint::Request::new(&self.api_url, &path(&["balances", currency.as_str()])), None)
+    }
+
+    /// Returns the open orders of all markets, or of `market` only.
+    pub fn get_open_orders(&self, market: Option<&Market>) -> Result<Vec<V3Order>> {
+        self.call_private_api(Method::GET, &with_market(endpoint::Request::new(&self.api_url, "/orders/open"), market), None)
+    }
+
+    /// Returns the most recently closed orders of all markets, or of `market` only.
+    pub fn get_closed_orders(&self, market: Option<&Market>) -> Result<Vec<V3Order>> {
+        self.call_private_api(Method::GET, &with_market(endpoint::Request::new(&self.api_url, "/orders/closed"), market), None)
+    }
+
+    pub fn get_order(&self, order_id: &str) -> Result<V3Order> {
+        self.call_private_api(Method::GET, &endpoint::Request::new(&self.api_url, &path(&["orders", order_id])), None)
+    }
+
+    /// Places an order, which is only retried if the retry policy opts in.
+    pub fn place_order(&self, order: &V3NewOrder) -> Result<V3Order> {
+        self.call_private_api(Method::POST, &endpoint::Request::new(&self.api_url, "/orders"), Some(serde_json::to_string(order)?))
+    }
+
+    /// Cancels an order and returns it.
+    pub fn cancel_order(&self, order_id: &str) -> Result<V3Order> {
+        self.call_private_api(Method::DELETE, &endpoint::Request::new(&self.api_url, &path(&["orders", order_id])), None)
+    }
+
+    pub fn get_open_deposits(&self, currency: Option<&Currency>) -> Result<Vec<V3Deposit>> {
+        self.call_private_api(Method::GET, &with_currency(endpoint::Request::new(&self.api_url, "/deposits/open"), currency), None)
+    }
+
+    pub fn get_closed_deposits(&self, currency: Option<&Currency>) -> Result<Vec<V3Deposit>> {
+        self.call_private_api(Method::GET, &with_currency(endpoint::Request::new(&self.api_url, "/deposits/closed"), currency), None)
+    }
+
+    pub fn get_deposit_address(&self, currency: &Currency) -> Result<V3Address> {
+        self.call_private_api(Method::GET, &endpoint::Request::new(&self.api_url, &path(&["addresses", currency.as_str()])), None)
+    }
+
+    pub fn get_open_withdrawals(&self, currency: Option<&Currency>) -> Result<Vec<V3Withdrawal>> {
+        self.call_private_api(Method::GET, &with_currency(endpoint::Request::new(&self.api_url, "/withdrawals/open"), currency), None)
+    }
+
+    pub fn get_closed_withdrawals(&self, currency: Option<&Currency>) -> Result<Vec<V3Withdrawal>> {
+        self.call_private_api(Method::GET, &with_currency(endpoint::Request::new(&self.api_url, "/withdrawals/closed"), currency), None)
+    }
+
+    /// Requests a withdrawal, which is only retried if the retry policy opts in.
+    pub fn withdraw(&self, withdrawal: &V3NewWithdrawal) -> Result<V3Withdrawal> {
+        self.call_private_api(Method::POST, &endpoint::Request::new(&self.api_url, "/withdrawals"), Some(serde_json::to_string(withdrawal)?))
+    }
+
+    /// Cancels a pending withdrawal and returns it.
+    pub fn cancel_withdrawal(&self, withdrawal_id: &str) -> Result<V3Withdrawal> {
+        self.call_private_api(Method::DELETE, &endpoint::Request::new(&self.api_url, &path(&["withdrawals", withdrawal_id])), None)
+    }
+
+    /// Calls a private endpoint. `GET` calls are retried according to the retry policy,
+    /// calls which change state (`POST`, `DELETE`) only if it opts in. Every attempt is signed with a new timestamp.
+    fn call_private_api<T>(&self, method: Method, request: &endpoint::Request, body: Option<String>) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
+        let url = request.url();
+        let max_retries = self.client.max_retries(method != Method::GET);
+        let resp = self.client.send_with_retries(EndpointClass::Private, max_retries, || Ok(self.signed_request(&method, &url, &body)))?;
+        read_response(resp)
+    }
+
+    fn signed_request(&self, method: &Method, url: &str, body: &Option<String>) -> HttpRequest {
+        let auth = self.client.auth();
+        let content = body.as_ref().map(|body| body.as_str()).unwrap_or("");
+        let mut headers = auth::headers(auth.api_key(), auth.api_secret(), auth::timestamp(), method.as_str(), url, content);
+        if body.is_some() {
+            headers.push(("Content-Type".to_string(), "application/json".to_string()));
+        }
+
+        HttpRequest { method: method.clone(), url: url.to_string(), headers: headers, body: body.clone() }
+    }
+}
+
+/// Builds the path of the segments, percent-encoding each of them (e.g. order ids).
+fn path(segments: &[&str]) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        path.push('/');
+        endpoint::encode(&mut path, segment);
+    }
+    path
+}
+
+fn candle_interval(tick_interval: TickInterval) -> Option<&'static str> {
+    match tick_interval {
+        TickInterval::OneMin => Some("MINUTE_1"),
+        TickInterval::FiveMin => Some("MINUTE_5"),
+        TickInterval::ThirtyMin => None,
+        TickInterval::Hour => Some("HOUR_1"),
+        TickInterval::Day => Some("DAY_1"),
+    }
+}
+
+fn with_market(request: endpoint::Request, market: Option<&Market>) -> endpoint::Request {
+    match market {
+        Some(market) => request.param("marketSymbol", symbol::format(market)),
+        None => request,
+    }
+}
+
+fn with_currency(request: endpoint::Request, currency: Option<&Currency>) -> endpoint::Request {
+    match currency {
+        Some(currency) => request.param("currencySymbol", currency),
+        None => request,
+    }
+}
+
+/// Rejected calls answer with an HTTP error status and a `V3Error` body, whose code becomes an `Api` error.
+fn read_response<T>(resp: HttpResponse) -> Result<T> where for<'de> T: serde::Deserialize<'de> {
+    if !resp.status.is_success() {
+        if let Ok(error) = serde_json::from_str::<V3Error>(&resp.body) {
+            return Err(BittrexError::api(error.code)
+                .with_status(resp.status.as_u16())
+                .with_url(&resp.url)
+                .with_body(&resp.body));
+        }
+    }
+    decode_response(resp.status, &resp.url, &resp.body)
+}
@@ -0,0 +1,435 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Serializer, Deserializer};
+use serde_json;
+
+use datetime;
+use decimal::Decimal;
+use market::{Currency, Market};
+use values::BittrexCandle;
+
+/// Side of an order (`BUY`, `SELL`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum V3OrderDirection {
+    Buy,
+    Sell,
+    Unknown(String),
+}
+
+/// Type of an order (`LIMIT`, `MARKET`, `CEILING_LIMIT`, `CEILING_MARKET`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum V3OrderType {
+    Limit,
+    Market,
+    CeilingLimit,
+    CeilingMarket,
+    Unknown(String),
+}
+
+/// How long an order stays open (`GOOD_TIL_CANCELLED`, `IMMEDIATE_OR_CANCEL`, ...).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum V3TimeInForce {
+    GoodTilCancelled,
+    ImmediateOrCancel,
+    FillOrKill,
+    PostOnlyGoodTilCancelled,
+    BuyNow,
+    Unknown(String),
+}
+
+/// Status of an order (`OPEN`, `CLOSED`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum V3OrderStatus {
+    Open,
+    Closed,
+    Unknown(String),
+}
+
+/// Error body of a rejected v3 call, e.g. `{"code":"INSUFFICIENT_FUNDS"}`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct V3Error {
+    pub code: String,
+    pub detail: Option<String>,
+    pub data: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3Market {
+    #[serde(with = "symbol")]
+    pub symbol: Market,
+    pub base_currency_symbol: Currency,
+    pub quote_currency_symbol: Currency,
+    pub min_trade_size: Decimal,
+    pub precision: u32,
+    pub status: String,
+    #[serde(with = "datetime")]
+    pub created_at: DateTime<Utc>,
+    pub notice: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3Ticker {
+    #[serde(with = "symbol")]
+    pub symbol: Market,
+    pub last_trade_rate: Decimal,
+    pub bid_rate: Decimal,
+    pub ask_rate: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct V3OrderBook {
+    pub bid: Vec<V3OrderBookEntry>,
+    pub ask: Vec<V3OrderBookEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct V3OrderBookEntry {
+    pub quantity: Decimal,
+    pub rate: Decimal,
+}
+
+/// Candle of the v3 API. `quote_volume` is in the currency a market is quoted in (BTC of `LTC-BTC`).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3Candle {
+    #[serde(with = "datetime")]
+    pub starts_at: DateTime<Utc>,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+    pub quote_volume: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3Order {
+    pub id: String,
+    #[serde(with = "symbol")]
+    pub market_symbol: Market,
+    pub direction: V3OrderDirection,
+    #[serde(rename = "type")]
+    pub order_type: V3OrderType,
+    pub quantity: Option<Decimal>,
+    pub limit: Option<Decimal>,
+    pub ceiling: Option<Decimal>,
+    pub time_in_force: V3TimeInForce,
+    pub client_order_id: Option<String>,
+    pub fill_quantity: Decimal,
+    pub commission: Decimal,
+    pub proceeds: Decimal,
+    pub status: V3OrderStatus,
+    #[serde(with = "datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "datetime::option", default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(with = "datetime::option", default)]
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// Order to place with `BittrexV3Client::place_order`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bittrex_api::Decimal;
+/// use bittrex_api::v3::{V3NewOrder, V3OrderDirection, V3TimeInForce};
+///
+/// let order = V3NewOrder::limit(&"BTC-LTC".parse().unwrap(), V3OrderDirection::Buy, Decimal::new(1, 0), "0.0123".parse().unwrap(), V3TimeInForce::GoodTilCancelled);
+/// ```
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3NewOrder {
+    #[serde(with = "symbol")]
+    pub market_symbol: Market,
+    pub direction: V3OrderDirection,
+    #[serde(rename = "type")]
+    pub order_type: V3OrderType,
+    #[serde(with = "decimal_string::option", skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<Decimal>,
+    #[serde(with = "decimal_string::option", skip_serializing_if = "Option::is_none")]
+    pub ceiling: Option<Decimal>,
+    #[serde(with = "decimal_string::option", skip_serializing_if = "Option::is_none")]
+    pub limit: Option<Decimal>,
+    pub time_in_force: V3TimeInForce,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3Balance {
+    pub currency_symbol: Currency,
+    pub total: Decimal,
+    pub available: Decimal,
+    #[serde(with = "datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3Deposit {
+    pub id: String,
+    pub currency_symbol: Currency,
+    pub quantity: Decimal,
+    pub crypto_address: Option<String>,
+    pub crypto_address_tag: Option<String>,
+    pub tx_id: Option<String>,
+    pub confirmations: u32,
+    #[serde(with = "datetime")]
+    pub updated_at: DateTime<Utc>,
+    #[serde(with = "datetime::option", default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    pub status: String,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3Withdrawal {
+    pub id: String,
+    pub currency_symbol: Currency,
+    pub quantity: Decimal,
+    pub crypto_address: String,
+    pub crypto_address_tag: Option<String>,
+    pub tx_cost: Option<Decimal>,
+    pub tx_id: Option<String>,
+    pub status: String,
+    #[serde(with = "datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "datetime::option", default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    pub client_withdrawal_id: Option<String>,
+}
+
+/// Withdrawal to request with `BittrexV3Client::withdraw`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3NewWithdrawal {
+    pub currency_symbol: Currency,
+    #[serde(with = "decimal_string")]
+    pub quantity: Decimal,
+    pub crypto_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crypto_address_tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_withdrawal_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V3Address {
+    pub status: String,
+    pub currency_symbol: Currency,
+    pub crypto_address: Option<String>,
+    pub crypto_address_tag: Option<String>,
+}
+
+impl From<V3Candle> for BittrexCandle {
+    fn from(candle: V3Candle) -> Self {
+        BittrexCandle {
+            time_stamp: candle.starts_at,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            volume: candle.volume,
+            base_volume: candle.quote_volume,
+        }
+    }
+}
+
+impl V3NewOrder {
+    /// A limit order for `quantity` at `limit` without client order id.
+    pub fn limit(market: &Market, direction: V3OrderDirection, quantity: Decimal, limit: Decimal, time_in_force: V3TimeInForce) -> Self {
+        V3NewOrder {
+            market_symbol: market.clone(),
+            direction: direction,
+            order_type: V3OrderType::Limit,
+            quantity: Some(quantity),
+            ceiling: None,
+            limit: Some(limit),
+            time_in_force: time_in_force,
+            client_order_id: None,
+        }
+    }
+}
+
+impl V3NewWithdrawal {
+    pub fn new(currency: &Currency, quantity: Decimal, crypto_address: &str) -> Self {
+        V3NewWithdrawal {
+            currency_symbol: currency.clone(),
+            quantity: quantity,
+            crypto_address: crypto_address.to_string(),
+            crypto_address_tag: None,
+            client_withdrawal_id: None,
+        }
+    }
+}
+
+impl V3OrderDirection {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            V3OrderDirection::Buy => "BUY",
+            V3OrderDirection::Sell => "SELL",
+            V3OrderDirection::Unknown(ref value) => value,
+        }
+    }
+}
+
+impl V3OrderType {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            V3OrderType::Limit => "LIMIT",
+            V3OrderType::Market => "MARKET",
+            V3OrderType::CeilingLimit => "CEILING_LIMIT",
+            V3OrderType::CeilingMarket => "CEILING_MARKET",
+            V3OrderType::Unknown(ref value) => value,
+        }
+    }
+}
+
+impl V3TimeInForce {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            V3TimeInForce::GoodTilCancelled => "GOOD_TIL_CANCELLED",
+            V3TimeInForce::ImmediateOrCancel => "IMMEDIATE_OR_CANCEL",
+            V3TimeInForce::FillOrKill => "FILL_OR_KILL",
+            V3TimeInForce::PostOnlyGoodTilCancelled => "POST_ONLY_GOOD_TIL_CANCELLED",
+            V3TimeInForce::BuyNow => "BUY_NOW",
+            V3TimeInForce::Unknown(ref value) => value,
+        }
+    }
+}
+
+impl V3OrderStatus {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            V3OrderStatus::Open => "OPEN",
+            V3OrderStatus::Closed => "CLOSED",
+            V3OrderStatus::Unknown(ref value) => value,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for V3OrderDirection {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "BUY" => V3OrderDirection::Buy,
+            "SELL" => V3OrderDirection::Sell,
+            _ => V3OrderDirection::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for V3OrderType {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "LIMIT" => V3OrderType::Limit,
+            "MARKET" => V3OrderType::Market,
+            "CEILING_LIMIT" => V3OrderType::CeilingLimit,
+            "CEILING_MARKET" => V3OrderType::CeilingMarket,
+            _ => V3OrderType::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for V3TimeInForce {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "GOOD_TIL_CANCELLED" => V3TimeInForce::GoodTilCancelled,
+            "IMMEDIATE_OR_CANCEL" => V3TimeInForce::ImmediateOrCancel,
+            "FILL_OR_KILL" => V3TimeInForce::FillOrKill,
+            "POST_ONLY_GOOD_TIL_CANCELLED" => V3TimeInForce::PostOnlyGoodTilCancelled,
+            "BUY_NOW" => V3TimeInForce::BuyNow,
+            _ => V3TimeInForce::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for V3OrderStatus {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "OPEN" => V3OrderStatus::Open,
+            "CLOSED" => V3OrderStatus::Closed,
+            _ => V3OrderStatus::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl_string_enum!(V3OrderDirection);
+impl_string_enum!(V3OrderType);
+impl_string_enum!(V3TimeInForce);
+impl_string_enum!(V3OrderStatus);
+
+/// Market symbols of the v3 API, which name the traded currency first (`LTC-BTC` for the v1.1 market `BTC-LTC`).
+///
+/// Used with `#[serde(with = "symbol")]` for `Market` fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use bittrex_api::Market;
+/// use bittrex_api::v3::symbol;
+///
+/// let market: Market = "BTC-LTC".parse().unwrap();
+///
+/// assert_eq!(symbol::format(&market), "LTC-BTC");
+/// assert_eq!(symbol::parse("LTC-BTC").unwrap(), market);
+/// ```
+pub mod symbol {
+    use serde::{Serializer, Deserialize, Deserializer};
+    use serde::de;
+
+    use market::{Market, ParseMarketError};
+
+    pub fn format(market: &Market) -> String {
+        format!("{}-{}", market.quote, market.base)
+    }
+
+    pub fn parse(symbol: &str) -> Result<Market, ParseMarketError> {
+        let mut parts = symbol.split('-');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(quote), Some(base), None) => format!("{}-{}", base, quote).parse()
+                .map_err(|_| ParseMarketError::InvalidMarket(symbol.to_string())),
+            _ => Err(ParseMarketError::InvalidMarket(symbol.to_string())),
+        }
+    }
+
+    pub fn serialize<S>(market: &Market, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_str(&format(market))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Market, D::Error> where D: Deserializer<'de> {
+        let symbol = String::deserialize(deserializer)?;
+        parse(&symbol).map_err(de::Error::custom)
+    }
+}
+
+/// Serializes decimals of request bodies as strings, which the v3 API takes without losing precision.
+mod decimal_string {
+    use serde::Serializer;
+
+    use decimal::Decimal;
+
+    pub fn serialize<S>(decimal: &Decimal, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.collect_str(decimal)
+    }
+
+    pub mod option {
+        use serde::Serializer;
+
+        use decimal::Decimal;
+
+        pub fn serialize<S>(decimal: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+            match *decimal {
+                Some(ref decimal) => super::serialize(decimal, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+    }
+}
@@ -1,4 +1,12 @@
 use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Serializer, Deserializer};
+
+use datetime;
+use decimal::Decimal;
+use market::{Currency, Market};
 
 #[derive(Debug)]
 pub enum BittrexOrderType {
@@ -7,6 +15,53 @@ pub enum BittrexOrderType {
     Both,
 }
 
+/// Side of a trade or an order (`BUY`, `SELL`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BittrexOrderSide {
+    Buy,
+    Sell,
+    Unknown(String),
+}
+
+/// Type of a placed order (`LIMIT_BUY`, `LIMIT_SELL`, ...).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BittrexOrderKind {
+    LimitBuy,
+    LimitSell,
+    MarketBuy,
+    MarketSell,
+    Unknown(String),
+}
+
+/// Whether a trade filled an order completely (`FILL`) or only partially (`PARTIAL_FILL`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BittrexFillType {
+    Fill,
+    PartialFill,
+    Unknown(String),
+}
+
+/// Condition of a conditional order (`NONE`, `GREATER_THAN`, ...).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BittrexCondition {
+    None,
+    GreaterThan,
+    LessThan,
+    StopLossFixed,
+    StopLossPercentage,
+    Unknown(String),
+}
+
+/// Interval of the candles of the v2 `GetTicks` and `GetLatestTick` endpoints and the v3 candles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TickInterval {
+    OneMin,
+    FiveMin,
+    ThirtyMin,
+    Hour,
+    Day,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct BittrexAPIResult<T> {
     pub success: bool,
@@ -30,7 +85,7 @@ pub struct BittrexUuid {
 #[derive(Serialize, Deserialize)]
 pub struct BittrexAddress {
     #[serde(rename = "Currency")]
-    pub currency: String,
+    pub currency: Currency,
     #[serde(rename = "Address")]
     pub address: String,
 }
@@ -38,13 +93,13 @@ pub struct BittrexAddress {
 #[derive(Serialize, Deserialize)]
 pub struct BittrexCurrency {
     #[serde(rename = "Currency")]
-    pub currency: String,
+    pub currency: Currency,
     #[serde(rename = "CurrencyLong")]
     pub currency_long: String,
     #[serde(rename = "MinConfirmation")]
     pub min_confirmation: u32,
     #[serde(rename = "TxFee")]
-    pub tx_fee: f32,
+    pub tx_fee: Decimal,
     #[serde(rename = "IsActive")]
     pub is_active: bool,
     #[serde(rename = "CoinType")]
@@ -58,51 +113,51 @@ pub struct BittrexCurrency {
 #[derive(Serialize, Deserialize)]
 pub struct BittrexMarket {
     #[serde(rename = "MarketCurrency")]
-    pub market_currency: String,
+    pub market_currency: Currency,
     #[serde(rename = "BaseCurrency")]
-    pub base_currency: String,
+    pub base_currency: Currency,
     #[serde(rename = "MarketCurrencyLong")]
     pub market_currency_long: String,
     #[serde(rename = "BaseCurrencyLong")]
     pub base_currency_long: String,
     #[serde(rename = "MinTradeSize")]
-    pub min_trade_size: f32,
+    pub min_trade_size: Decimal,
     #[serde(rename = "MarketName")]
-    pub market_name: String,
+    pub market_name: Market,
     #[serde(rename = "IsActive")]
     pub is_active: bool,
-    #[serde(rename = "Created")]
-    pub created: String,
+    #[serde(rename = "Created", with = "datetime")]
+    pub created: DateTime<Utc>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BittrexMarketSummary {
     #[serde(rename = "MarketName")]
-    pub market_name: String,
+    pub market_name: Market,
     #[serde(rename = "High")]
-    pub high: f32,
+    pub high: Decimal,
     #[serde(rename = "Low")]
-    pub low: f32,
+    pub low: Decimal,
     #[serde(rename = "Volume")]
-    pub volume: f64,
+    pub volume: Decimal,
     #[serde(rename = "Last")]
-    pub last: f64,
+    pub last: Decimal,
     #[serde(rename = "BaseVolume")]
-    pub base_volume: f64,
-    #[serde(rename = "TimeStamp")]
-    pub time_stamp: String,
+    pub base_volume: Decimal,
+    #[serde(rename = "TimeStamp", with = "datetime")]
+    pub time_stamp: DateTime<Utc>,
     #[serde(rename = "Bid")]
-    pub bid: f32,
+    pub bid: Decimal,
     #[serde(rename = "Ask")]
-    pub ask: f32,
+    pub ask: Decimal,
     #[serde(rename = "OpenBuyOrders")]
     pub open_buy_orders: u32,
     #[serde(rename = "OpenSellOrders")]
     pub open_sell_orders: u32,
     #[serde(rename = "PrevDay")]
-    pub prev_day: f32,
-    #[serde(rename = "Created")]
-    pub created: String,
+    pub prev_day: Decimal,
+    #[serde(rename = "Created", with = "datetime")]
+    pub created: DateTime<Utc>,
     #[serde(rename = "DisplayMarketName")]
     pub display_market_name: Option<String>,
 }
@@ -110,14 +165,14 @@ pub struct BittrexMarketSummary {
 #[derive(Serialize, Deserialize)]
 pub struct BittrexTicker {
     #[serde(rename = "Ask")]
-    pub ask: f32,
+    pub ask: Decimal,
     #[serde(rename = "Bid")]
-    pub bid: f32,
+    pub bid: Decimal,
     #[serde(rename = "Last")]
-    pub last: f32,
+    pub last: Decimal,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BittrexPublicOrderBook {
     #[serde(rename = "buy")]
     pub buy: Vec<BittrexPublicOrder>,
@@ -125,30 +180,51 @@ pub struct BittrexPublicOrderBook {
     pub sell: Vec<BittrexPublicOrder>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BittrexPublicOrder {
     #[serde(rename = "Quantity")]
-    pub quantity: f32,
+    pub quantity: Decimal,
     #[serde(rename = "Rate")]
-    pub rate: f32,
+    pub rate: Decimal,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BittrexTrade {
     #[serde(rename = "Id")]
     pub id: u32,
-    #[serde(rename = "TimeStamp")]
-    pub time_stamp: String,
+    #[serde(rename = "TimeStamp", with = "datetime")]
+    pub time_stamp: DateTime<Utc>,
     #[serde(rename = "Quantity")]
-    pub quantity: f32,
+    pub quantity: Decimal,
     #[serde(rename = "Price")]
-    pub price: f32,
+    pub price: Decimal,
     #[serde(rename = "Total")]
-    pub total: f32,
+    pub total: Decimal,
     #[serde(rename = "FillType")]
-    pub fill_type: String,
+    pub fill_type: BittrexFillType,
     #[serde(rename = "OrderType")]
-    pub order_type: String,
+    pub order_type: BittrexOrderSide,
+}
+
+/// Candle (OHLCV bar) of an interval starting at `time_stamp`.
+///
+/// `volume` is in the traded currency (LTC of `BTC-LTC`), `base_volume` in the base currency (BTC).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BittrexCandle {
+    #[serde(rename = "T", with = "datetime")]
+    pub time_stamp: DateTime<Utc>,
+    #[serde(rename = "O")]
+    pub open: Decimal,
+    #[serde(rename = "H")]
+    pub high: Decimal,
+    #[serde(rename = "L")]
+    pub low: Decimal,
+    #[serde(rename = "C")]
+    pub close: Decimal,
+    #[serde(rename = "V")]
+    pub volume: Decimal,
+    #[serde(rename = "BV")]
+    pub base_volume: Decimal,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -158,25 +234,25 @@ pub struct BittrexOpenOrder {
     #[serde(rename = "OrderUuid")]
     pub order_uuid: String,
     #[serde(rename = "Exchange")]
-    pub exchange: String,
+    pub exchange: Market,
     #[serde(rename = "OrderType")]
-    pub order_type: String,
+    pub order_type: BittrexOrderKind,
     #[serde(rename = "Quantity")]
-    pub quantity: f32,
+    pub quantity: Decimal,
     #[serde(rename = "QuantityRemaining")]
-    pub quantity_remaining: f32,
+    pub quantity_remaining: Decimal,
     #[serde(rename = "Limit")]
-    pub limit: f32,
+    pub limit: Decimal,
     #[serde(rename = "CommissionPaid")]
-    pub comission_paid: f32,
+    pub comission_paid: Decimal,
     #[serde(rename = "Price")]
-    pub price: f32,
+    pub price: Decimal,
     #[serde(rename = "PricePerUnit")]
-    pub price_per_unit: Option<f32>,
-    #[serde(rename = "Opened")]
-    pub opened: String,
-    #[serde(rename = "Closed")]
-    pub closed: Option<String>,
+    pub price_per_unit: Option<Decimal>,
+    #[serde(rename = "Opened", with = "datetime")]
+    pub opened: DateTime<Utc>,
+    #[serde(rename = "Closed", with = "datetime::option", default)]
+    pub closed: Option<DateTime<Utc>>,
     #[serde(rename = "CancelInitiated")]
     pub cancel_initiated: bool,
     #[serde(rename = "ImmediateOrCancel")]
@@ -184,9 +260,9 @@ pub struct BittrexOpenOrder {
     #[serde(rename = "IsConditional")]
     pub is_conditional: bool,
     #[serde(rename = "Condition")]
-    pub condition: Option<String>,
+    pub condition: Option<BittrexCondition>,
     #[serde(rename = "ConditionalTarget")]
-    pub conditional_target: Option<String>,
+    pub conditional_target: Option<Decimal>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -194,31 +270,31 @@ pub struct BittrexHistoryOrder {
     #[serde(rename = "OrderUuid")]
     pub order_uuid: String,
     #[serde(rename = "Exchange")]
-    pub exchange: String,
-    #[serde(rename = "TimeStamp")]
-    pub time_stamp: String,
+    pub exchange: Market,
+    #[serde(rename = "TimeStamp", with = "datetime")]
+    pub time_stamp: DateTime<Utc>,
     #[serde(rename = "OrderType")]
-    pub order_type: String,
+    pub order_type: BittrexOrderKind,
     #[serde(rename = "Quantity")]
-    pub quantity: f32,
+    pub quantity: Decimal,
     #[serde(rename = "QuantityRemaining")]
-    pub quantity_remaining: f32,
+    pub quantity_remaining: Decimal,
     #[serde(rename = "Limit")]
-    pub limit: f32,
+    pub limit: Decimal,
     #[serde(rename = "Commission")]
-    pub comission: f32,
+    pub comission: Decimal,
     #[serde(rename = "Price")]
-    pub price: f32,
+    pub price: Decimal,
     #[serde(rename = "PricePerUnit")]
-    pub price_per_unit: Option<f32>,
+    pub price_per_unit: Option<Decimal>,
     #[serde(rename = "ImmediateOrCancel")]
     pub immediate_or_cancel: bool,
     #[serde(rename = "IsConditional")]
     pub is_conditional: bool,
     #[serde(rename = "Condition")]
-    pub condition: Option<String>,
+    pub condition: Option<BittrexCondition>,
     #[serde(rename = "ConditionalTarget")]
-    pub conditional_target: Option<String>,
+    pub conditional_target: Option<Decimal>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -228,33 +304,33 @@ pub struct BittrexOrder {
     #[serde(rename = "OrderUuid")]
     pub order_uuid: String,
     #[serde(rename = "Exchange")]
-    pub exchange: String,
+    pub exchange: Market,
     #[serde(rename = "Type")]
-    pub order_type: String,
+    pub order_type: BittrexOrderKind,
     #[serde(rename = "Quantity")]
-    pub quantity: f32,
+    pub quantity: Decimal,
     #[serde(rename = "QuantityRemaining")]
-    pub quantity_remaining: f32,
+    pub quantity_remaining: Decimal,
     #[serde(rename = "Limit")]
-    pub limit: f32,
+    pub limit: Decimal,
     #[serde(rename = "Reserved")]
-    pub reserved: f32,
+    pub reserved: Decimal,
     #[serde(rename = "ReserveRemaining")]
-    pub reserve_remaining: f32,
+    pub reserve_remaining: Decimal,
     #[serde(rename = "CommissionReserved")]
-    pub commission_reserved: f32,
+    pub commission_reserved: Decimal,
     #[serde(rename = "CommissionReserveRemaining")]
-    pub commission_reserve_remaining: f32,
+    pub commission_reserve_remaining: Decimal,
     #[serde(rename = "CommissionPaid")]
-    pub comission_paid: f32,
+    pub comission_paid: Decimal,
     #[serde(rename = "Price")]
-    pub price: f32,
+    pub price: Decimal,
     #[serde(rename = "PricePerUnit")]
-    pub price_per_unit: Option<f32>,
-    #[serde(rename = "Opened")]
-    pub opened: String,
-    #[serde(rename = "Closed")]
-    pub closed: Option<String>,
+    pub price_per_unit: Option<Decimal>,
+    #[serde(rename = "Opened", with = "datetime")]
+    pub opened: DateTime<Utc>,
+    #[serde(rename = "Closed", with = "datetime::option", default)]
+    pub closed: Option<DateTime<Utc>>,
     #[serde(rename = "IsOpen")]
     pub is_open: bool,
     #[serde(rename = "Sentinel")]
@@ -266,9 +342,9 @@ pub struct BittrexOrder {
     #[serde(rename = "IsConditional")]
     pub is_conditional: bool,
     #[serde(rename = "Condition")]
-    pub condition: Option<String>,
+    pub condition: Option<BittrexCondition>,
     #[serde(rename = "ConditionalTarget")]
-    pub conditional_target: Option<String>,
+    pub conditional_target: Option<Decimal>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -276,19 +352,19 @@ pub struct BittrexTransaction {
     #[serde(rename = "PaymentUuid")]
     pub payment_uuid: String,
     #[serde(rename = "Currency")]
-    pub currency: String,
+    pub currency: Currency,
     #[serde(rename = "Amount")]
-    pub amount: f32,
+    pub amount: Decimal,
     #[serde(rename = "Address")]
     pub address: String,
-    #[serde(rename = "Opened")]
-    pub opened: String,
+    #[serde(rename = "Opened", with = "datetime")]
+    pub opened: DateTime<Utc>,
     #[serde(rename = "Authorized")]
     pub authorized: bool,
     #[serde(rename = "PendingPayment")]
     pub pending_payment: bool,
     #[serde(rename = "TxCost")]
-    pub tx_cost: f32,
+    pub tx_cost: Decimal,
     #[serde(rename = "TxId")]
     pub tx_id: Option<String>,
     #[serde(rename = "Canceled")]
@@ -300,13 +376,13 @@ pub struct BittrexTransaction {
 #[derive(Serialize, Deserialize)]
 pub struct BittrexBalance {
     #[serde(rename = "Currency")]
-    pub currency: String,
+    pub currency: Currency,
     #[serde(rename = "Balance")]
-    pub balance: f32,
+    pub balance: Decimal,
     #[serde(rename = "Available")]
-    pub available: f32,
+    pub available: Decimal,
     #[serde(rename = "Pending")]
-    pub pending: f32,
+    pub pending: Decimal,
     #[serde(rename = "CryptoAddress")]
     pub crypto_address: Option<String>,
 }
@@ -317,6 +393,209 @@ impl fmt::Display for BittrexOrderType {
     }
 }
 
+impl BittrexOrderSide {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            BittrexOrderSide::Buy => "BUY",
+            BittrexOrderSide::Sell => "SELL",
+            BittrexOrderSide::Unknown(ref value) => value,
+        }
+    }
+}
+
+impl BittrexOrderKind {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            BittrexOrderKind::LimitBuy => "LIMIT_BUY",
+            BittrexOrderKind::LimitSell => "LIMIT_SELL",
+            BittrexOrderKind::MarketBuy => "MARKET_BUY",
+            BittrexOrderKind::MarketSell => "MARKET_SELL",
+            BittrexOrderKind::Unknown(ref value) => value,
+        }
+    }
+
+    /// Returns the side of the order. Unknown order types are classified by their `_BUY`/`_SELL` suffix.
+    pub fn side(&self) -> BittrexOrderSide {
+        match *self {
+            BittrexOrderKind::LimitBuy | BittrexOrderKind::MarketBuy => BittrexOrderSide::Buy,
+            BittrexOrderKind::LimitSell | BittrexOrderKind::MarketSell => BittrexOrderSide::Sell,
+            BittrexOrderKind::Unknown(ref value) if value.ends_with("_BUY") => BittrexOrderSide::Buy,
+            BittrexOrderKind::Unknown(ref value) if value.ends_with("_SELL") => BittrexOrderSide::Sell,
+            BittrexOrderKind::Unknown(ref value) => BittrexOrderSide::Unknown(value.clone()),
+        }
+    }
+
+    pub fn is_limit(&self) -> bool {
+        match *self {
+            BittrexOrderKind::LimitBuy | BittrexOrderKind::LimitSell => true,
+            BittrexOrderKind::MarketBuy | BittrexOrderKind::MarketSell => false,
+            BittrexOrderKind::Unknown(ref value) => value.starts_with("LIMIT_"),
+        }
+    }
+}
+
+impl BittrexFillType {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            BittrexFillType::Fill => "FILL",
+            BittrexFillType::PartialFill => "PARTIAL_FILL",
+            BittrexFillType::Unknown(ref value) => value,
+        }
+    }
+}
+
+impl BittrexCondition {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            BittrexCondition::None => "NONE",
+            BittrexCondition::GreaterThan => "GREATER_THAN",
+            BittrexCondition::LessThan => "LESS_THAN",
+            BittrexCondition::StopLossFixed => "STOP_LOSS_FIXED",
+            BittrexCondition::StopLossPercentage => "STOP_LOSS_PERCENTAGE",
+            BittrexCondition::Unknown(ref value) => value,
+        }
+    }
+}
+
+impl TickInterval {
+    pub fn as_str(&self) -> &str {
+        match *self {
+            TickInterval::OneMin => "oneMin",
+            TickInterval::FiveMin => "fiveMin",
+            TickInterval::ThirtyMin => "thirtyMin",
+            TickInterval::Hour => "hour",
+            TickInterval::Day => "day",
+        }
+    }
+
+    /// Length of the interval.
+    pub fn duration(&self) -> Duration {
+        match *self {
+            TickInterval::OneMin => Duration::from_secs(60),
+            TickInterval::FiveMin => Duration::from_secs(5 * 60),
+            TickInterval::ThirtyMin => Duration::from_secs(30 * 60),
+            TickInterval::Hour => Duration::from_secs(60 * 60),
+            TickInterval::Day => Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+impl fmt::Display for TickInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl<'a> From<&'a str> for BittrexOrderSide {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "BUY" => BittrexOrderSide::Buy,
+            "SELL" => BittrexOrderSide::Sell,
+            _ => BittrexOrderSide::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for BittrexOrderKind {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "LIMIT_BUY" => BittrexOrderKind::LimitBuy,
+            "LIMIT_SELL" => BittrexOrderKind::LimitSell,
+            "MARKET_BUY" => BittrexOrderKind::MarketBuy,
+            "MARKET_SELL" => BittrexOrderKind::MarketSell,
+            _ => BittrexOrderKind::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for BittrexFillType {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "FILL" => BittrexFillType::Fill,
+            "PARTIAL_FILL" => BittrexFillType::PartialFill,
+            _ => BittrexFillType::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for BittrexCondition {
+    fn from(value: &'a str) -> Self {
+        match value {
+            "NONE" => BittrexCondition::None,
+            "GREATER_THAN" => BittrexCondition::GreaterThan,
+            "LESS_THAN" => BittrexCondition::LessThan,
+            "STOP_LOSS_FIXED" => BittrexCondition::StopLossFixed,
+            "STOP_LOSS_PERCENTAGE" => BittrexCondition::StopLossPercentage,
+            _ => BittrexCondition::Unknown(value.to_string()),
+        }
+    }
+}
+
+/// Implements Display and string based (de)serialization through `as_str` and `From<&str>`.
+macro_rules! impl_string_enum {
+    ($name:ident) => {
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.pad(self.as_str())
+            }
+        }
+
+        impl ::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+                let value: String = ::serde::Deserialize::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+    }
+}
+
+impl_string_enum!(BittrexOrderSide);
+impl_string_enum!(BittrexOrderKind);
+impl_string_enum!(BittrexFillType);
+impl_string_enum!(BittrexCondition);
+
+impl BittrexTrade {
+    pub fn side(&self) -> BittrexOrderSide {
+        self.order_type.clone()
+    }
+}
+
+impl BittrexOpenOrder {
+    pub fn side(&self) -> BittrexOrderSide {
+        self.order_type.side()
+    }
+
+    pub fn is_limit(&self) -> bool {
+        self.order_type.is_limit()
+    }
+}
+
+impl BittrexHistoryOrder {
+    pub fn side(&self) -> BittrexOrderSide {
+        self.order_type.side()
+    }
+
+    pub fn is_limit(&self) -> bool {
+        self.order_type.is_limit()
+    }
+}
+
+impl BittrexOrder {
+    pub fn side(&self) -> BittrexOrderSide {
+        self.order_type.side()
+    }
+
+    pub fn is_limit(&self) -> bool {
+        self.order_type.is_limit()
+    }
+}
+
 impl fmt::Display for BittrexUuid {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.uuid)
@@ -0,0 +1,134 @@
+extern crate mockito;
+extern crate bittrex_api;
+
+use mockito::{mock, Matcher};
+
+use bittrex_api::{Account, BittrexClient, Currency, Decimal, Market, MarketData, PaperTradingClient, Trading};
+use bittrex_api::error::{BittrexError, BittrexErrorType};
+use bittrex_api::values::*;
+
+/// Market data with a fixed ticker, as a strategy test would use.
+struct FixedTicker(BittrexTicker);
+
+impl MarketData for FixedTicker {
+    fn get_markets(&self) -> Result<Vec<BittrexMarket>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn get_currencies(&self) -> Result<Vec<BittrexCurrency>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn get_ticker(&self, _market: &Market) -> Result<BittrexTicker, BittrexError> {
+        Ok(BittrexTicker { bid: self.0.bid, ask: self.0.ask, last: self.0.last })
+    }
+
+    fn get_market_summaries(&self) -> Result<Vec<BittrexMarketSummary>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn get_market_summary(&self, _market: &Market) -> Result<BittrexMarketSummary, BittrexError> {
+        Err(BittrexError::new(BittrexErrorType::NoResults, "No market summary".to_string()))
+    }
+
+    fn get_order_book(&self, _market: &Market, _book_type: BittrexOrderType) -> Result<BittrexPublicOrderBook, BittrexError> {
+        Ok(BittrexPublicOrderBook { buy: Vec::new(), sell: Vec::new() })
+    }
+
+    fn get_market_history(&self, _market: &Market) -> Result<Vec<BittrexTrade>, BittrexError> {
+        Ok(Vec::new())
+    }
+}
+
+fn buy_at_ask<M: MarketData, T: Trading>(market_data: &M, trading: &T, market: &Market, quantity: Decimal) -> Result<BittrexUuid, BittrexError> {
+    let ticker = market_data.get_ticker(market)?;
+    trading.buy_limit(market, quantity, ticker.ask)
+}
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+#[test]
+fn should_run_generic_code_against_paper_trading_client_successfully() {
+    // Arrange
+    let market: Market = "BTC-LTC".parse().unwrap();
+    let market_data = FixedTicker(BittrexTicker { bid: decimal("0.009"), ask: decimal("0.01"), last: decimal("0.01") });
+    let paper_client = PaperTradingClient::offline();
+    paper_client.deposit(&Currency::new("BTC").unwrap(), decimal("1"));
+    paper_client.set_order_book(&market, BittrexPublicOrderBook {
+        buy: vec![],
+        sell: vec![BittrexPublicOrder { quantity: decimal("5"), rate: decimal("0.01") }],
+    });
+
+    // Act
+    let buy = buy_at_ask(&market_data, &paper_client, &market, decimal("5")).unwrap();
+
+    // Assert
+    let order = Trading::get_order(&paper_client, &buy.uuid).unwrap();
+    assert!(!order.is_open);
+    assert_eq!(Trading::get_order_history_by_market(&paper_client, &market).unwrap().len(), 1);
+    assert!(Trading::get_order_history_by_market(&paper_client, &"BTC-NEO".parse().unwrap()).unwrap().is_empty());
+    assert!(Trading::get_open_orders_by_market(&paper_client, &market).unwrap().is_empty());
+}
+
+#[test]
+fn should_run_generic_code_against_bittrex_client_successfully() {
+    // Arrange
+    let _ticker_mock = mock("GET", "/public/getticker?market=BTC-NEO")
+        .with_status(200)
+        .with_body(r#"{"success":true,"message":"","result":{"Bid":0.002,"Ask":0.003,"Last":0.0025}}"#)
+        .create();
+    let _buy_mock = mock("GET", Matcher::Regex(r"^/market/buylimit\?market=BTC-NEO&quantity=2&rate=0.003(.*)$".to_string()))
+        .with_status(200)
+        .with_body(r#"{"success":true,"message":"","result":{"uuid":"e606d53c-8d70-11e3-94b5-425861b86ab6"}}"#)
+        .create();
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+
+    // Act
+    let buy = buy_at_ask(&bittrex_client, &bittrex_client, &"BTC-NEO".parse().unwrap(), decimal("2")).unwrap();
+
+    // Assert
+    assert_eq!(buy.uuid, "e606d53c-8d70-11e3-94b5-425861b86ab6");
+}
+
+#[test]
+fn should_use_bittrex_client_as_trait_objects_successfully() {
+    // Arrange
+    let _mock = mock("GET", Matcher::Regex(r"^/account/getbalances\?apikey=(.*)$".to_string()))
+        .with_status(200)
+        .with_body(r#"{"success":true,"message":"","result":[{"Currency":"DOGE","Balance":4.2,"Available":4.2,"Pending":0.0,"CryptoAddress":null}]}"#)
+        .create();
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+    let account: &dyn Account = &bittrex_client;
+
+    // Act
+    let balances = account.get_balances().unwrap();
+
+    // Assert
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].balance, decimal("4.2"));
+}
+
+#[test]
+fn should_use_paper_trading_client_as_account_successfully() {
+    // Arrange
+    let btc = Currency::new("BTC").unwrap();
+    let paper_client = PaperTradingClient::offline();
+    paper_client.deposit(&btc, decimal("1"));
+    let account: &dyn Account = &paper_client;
+
+    // Act
+    let balances = account.get_balances().unwrap();
+    let deposit_address = account.get_deposit_address(&btc);
+    let withdrawal = account.withdraw(&btc, decimal("0.5"), "1PAPERADDRESS", "");
+
+    // Assert
+    assert_eq!(balances.len(), 1);
+    assert_eq!((balances[0].currency.as_str(), balances[0].balance), ("BTC", decimal("1")));
+    assert_eq!(deposit_address.err().unwrap().error_type, BittrexErrorType::Configuration);
+    assert_eq!(withdrawal.err().unwrap().error_type, BittrexErrorType::Configuration);
+    assert_eq!(account.get_balance(&btc).unwrap().available, decimal("1"));
+    assert!(account.get_deposit_history().unwrap().is_empty());
+    assert!(account.get_withdrawal_history_by_currency(&btc).unwrap().is_empty());
+}
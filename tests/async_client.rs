@@ -0,0 +1,178 @@
+extern crate mockito;
+extern crate tokio;
+extern crate bittrex_api;
+
+use mockito::{mock, Matcher};
+use tokio::runtime::Runtime;
+use std::time::{Duration, Instant};
+
+use bittrex_api::{AsyncBittrexClient, BittrexClient, Decimal, RetryPolicy};
+use bittrex_api::error::{ApiErrorCode, BittrexErrorType};
+
+fn async_client() -> AsyncBittrexClient {
+    BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url(mockito::server_url())
+        .build_async()
+        .unwrap()
+}
+
+#[test]
+fn should_get_markets_async_successfully() {
+    // Arrange
+    let _mock = mock("GET", "/public/getmarkets")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : [{
+                    "MarketCurrency" : "LTC",
+                    "BaseCurrency" : "BTC",
+                    "MarketCurrencyLong" : "Litecoin",
+                    "BaseCurrencyLong" : "Bitcoin",
+                    "MinTradeSize" : 0.01000000,
+                    "MarketName" : "BTC-LTC",
+                    "IsActive" : true,
+                    "Created" : "2014-02-13T00:00:00"
+                }
+            ]
+        }"#)
+        .create();
+    let mut runtime = Runtime::new().unwrap();
+
+    // Act
+    let markets = runtime.block_on(async_client().get_markets()).unwrap();
+
+    // Assert
+    assert_eq!(markets.len(), 1);
+    assert_eq!(markets[0].market_name, "BTC-LTC");
+}
+
+#[test]
+fn should_get_ticker_async_with_public_client_successfully() {
+    // Arrange
+    let _mock = mock("GET", "/public/getticker?market=BTC-LTC")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : {
+                "Bid" : 2.05670368,
+                "Ask" : 3.35579531,
+                "Last" : 3.35579531
+            }
+        }"#)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .api_url(mockito::server_url())
+        .build_async()
+        .unwrap();
+    let mut runtime = Runtime::new().unwrap();
+
+    // Act
+    let ticker = runtime.block_on(bittrex_client.get_ticker(&"BTC-LTC".parse().unwrap())).unwrap();
+
+    // Assert
+    assert_eq!(ticker.ask, Decimal::new(335579531, 8));
+}
+
+#[test]
+fn should_get_balances_async_with_signature_successfully() {
+    // Arrange
+    let _mock = mock("GET", Matcher::Regex(r"^/account/getbalances\?apikey=KEY&nonce=\d+$".to_string()))
+        .match_header("apisign", Matcher::Regex(r"^[0-9A-F]{128}$".to_string()))
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : [{
+                    "Currency" : "DOGE",
+                    "Balance" : 0.00000000,
+                    "Available" : 0.00000000,
+                    "Pending" : 0.00000000,
+                    "CryptoAddress" : "DLxcEt3AatMyr2NTatzjsfHNoB9NT62HiF",
+                    "Requested" : false,
+                    "Uuid" : null
+                }
+            ]
+        }"#)
+        .create();
+    let mut runtime = Runtime::new().unwrap();
+
+    // Act
+    let balances = runtime.block_on(async_client().get_balances()).unwrap();
+
+    // Assert
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].currency, "DOGE");
+}
+
+#[test]
+fn should_buy_limit_async_successfully() {
+    // Arrange
+    let _mock = mock("GET", Matcher::Regex(r"^/market/buylimit\?market=BTC-LTC&quantity=1.2&rate=1.3(.*)$".to_string()))
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : {
+                    "uuid" : "e606d53c-8d70-11e3-94b5-425861b86ab6"
+                }
+        }"#)
+        .create();
+    let mut runtime = Runtime::new().unwrap();
+
+    // Act
+    let buy_limit = runtime.block_on(async_client().buy_limit(&"BTC-LTC".parse().unwrap(), Decimal::new(12, 1), Decimal::new(13, 1))).unwrap();
+
+    // Assert
+    assert_eq!(buy_limit.uuid, "e606d53c-8d70-11e3-94b5-425861b86ab6".to_string());
+}
+
+#[test]
+fn should_return_api_error_async() {
+    // Arrange
+    let _mock = mock("GET", "/public/getmarketsummary?market=BTC-XYZ")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : false,
+            "message" : "INVALID_MARKET",
+            "result" : null
+        }"#)
+        .create();
+    let mut runtime = Runtime::new().unwrap();
+
+    // Act
+    let error = runtime.block_on(async_client().get_market_summary(&"BTC-XYZ".parse().unwrap())).err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::Api(ApiErrorCode::InvalidMarket));
+    assert_eq!(error.message, "INVALID_MARKET");
+}
+
+#[test]
+fn should_retry_idempotent_async_calls_only() {
+    // Arrange
+    let bittrex_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url("http://127.0.0.1:1".to_string())
+        .retry_policy(RetryPolicy::new(2, Duration::from_millis(200)).jitter(false))
+        .build_async()
+        .unwrap();
+    let mut runtime = Runtime::new().unwrap();
+
+    // Act
+    let started = Instant::now();
+    let balances = runtime.block_on(bittrex_client.get_balances());
+    let retried_duration = started.elapsed();
+
+    let started = Instant::now();
+    let buy_limit = runtime.block_on(bittrex_client.buy_limit(&"BTC-LTC".parse().unwrap(), Decimal::new(1, 0), Decimal::new(1, 0)));
+    let single_duration = started.elapsed();
+
+    // Assert
+    assert!(balances.is_err());
+    assert!(buy_limit.is_err());
+    assert!(retried_duration >= Duration::from_millis(400));
+    assert!(single_duration < Duration::from_millis(200));
+}
@@ -0,0 +1,132 @@
+extern crate chrono;
+extern crate bittrex_api;
+
+use std::time::Duration;
+
+use chrono::{DateTime, Timelike, Utc};
+
+use bittrex_api::{datetime, Decimal};
+use bittrex_api::candles::{BarType, CandleAggregator};
+use bittrex_api::values::{BittrexFillType, BittrexOrderSide, BittrexTrade};
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+fn at(minute: u32, second: u32) -> DateTime<Utc> {
+    datetime::parse(&format!("2020-01-01T10:{:02}:{:02}", minute, second)).unwrap()
+}
+
+fn trade(id: u32, time_stamp: DateTime<Utc>, quantity: &str, price: &str) -> BittrexTrade {
+    BittrexTrade {
+        id: id,
+        time_stamp: time_stamp,
+        quantity: decimal(quantity),
+        price: decimal(price),
+        total: decimal(quantity) * decimal(price),
+        fill_type: BittrexFillType::Fill,
+        order_type: BittrexOrderSide::Buy,
+    }
+}
+
+/// Trades of 10:00 to 10:07 as two overlapping, newest first windows of `get_market_history`.
+fn history_windows() -> (Vec<BittrexTrade>, Vec<BittrexTrade>) {
+    let first = vec![
+        trade(4, at(2, 30), "1", "12"),
+        trade(3, at(1, 0), "2", "9"),
+        trade(2, at(0, 40), "1", "11"),
+        trade(1, at(0, 10), "1", "10"),
+    ];
+    let second = vec![
+        trade(7, at(7, 0), "1", "15"),
+        trade(6, at(3, 20), "3", "14"),
+        trade(5, at(3, 5), "1", "13"),
+        trade(4, at(2, 30), "1", "12"),
+        trade(3, at(1, 0), "2", "9"),
+    ];
+    (first, second)
+}
+
+#[test]
+fn should_aggregate_time_bars_from_overlapping_windows_successfully() {
+    // Arrange
+    let (first, second) = history_windows();
+    let mut aggregator = CandleAggregator::new(BarType::Time(Duration::from_secs(3 * 60)));
+
+    // Act
+    let mut candles = aggregator.push_all(first);
+    candles.extend(aggregator.push_all(second));
+
+    // Assert
+    assert_eq!(candles.len(), 2);
+    assert_eq!(candles[0].time_stamp, at(0, 0));
+    assert_eq!((candles[0].open, candles[0].high, candles[0].low, candles[0].close), (decimal("10"), decimal("12"), decimal("9"), decimal("12")));
+    assert_eq!(candles[0].volume, decimal("5"));
+    assert_eq!(candles[0].base_volume, decimal("51"));
+    assert_eq!(candles[1].time_stamp, at(3, 0));
+    assert_eq!((candles[1].open, candles[1].close, candles[1].volume), (decimal("13"), decimal("14"), decimal("4")));
+    assert_eq!(aggregator.duplicate_trades(), 2);
+    assert_eq!(aggregator.late_trades(), 0);
+
+    let rest = aggregator.flush();
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest[0].time_stamp.minute(), 6);
+    assert_eq!(rest[0].close, decimal("15"));
+}
+
+#[test]
+fn should_aggregate_trade_bars_successfully() {
+    // Arrange
+    let (first, second) = history_windows();
+    let mut aggregator = CandleAggregator::new(BarType::Trades(3));
+
+    // Act
+    let mut candles = aggregator.push_all(first);
+    candles.extend(aggregator.push_all(second));
+    candles.extend(aggregator.flush());
+
+    // Assert
+    assert_eq!(candles.len(), 3);
+    assert_eq!(candles[0].time_stamp, at(0, 10));
+    assert_eq!((candles[0].open, candles[0].close), (decimal("10"), decimal("9")));
+    assert_eq!((candles[1].open, candles[1].high, candles[1].close), (decimal("12"), decimal("14"), decimal("14")));
+    assert_eq!(candles[2].volume, decimal("1"));
+}
+
+#[test]
+fn should_aggregate_volume_bars_successfully() {
+    // Arrange
+    let (first, second) = history_windows();
+    let mut aggregator = CandleAggregator::new(BarType::Volume(decimal("3")));
+
+    // Act
+    let mut candles = aggregator.push_all(first);
+    candles.extend(aggregator.push_all(second));
+
+    // Assert
+    assert_eq!(candles.len(), 2);
+    assert_eq!(candles[0].volume, decimal("4"));
+    assert_eq!(candles[0].close, decimal("9"));
+    assert_eq!(candles[1].volume, decimal("5"));
+    assert_eq!((candles[1].open, candles[1].close), (decimal("12"), decimal("14")));
+}
+
+#[test]
+fn should_merge_late_trades_within_lateness_and_drop_later_ones() {
+    // Arrange
+    let mut aggregator = CandleAggregator::new(BarType::Time(Duration::from_secs(60)))
+        .lateness(Duration::from_secs(30));
+
+    // Act
+    let mut candles = aggregator.push(trade(2, at(0, 50), "1", "11"));
+    candles.extend(aggregator.push(trade(3, at(1, 20), "1", "12")));
+    candles.extend(aggregator.push(trade(1, at(0, 55), "1", "10")));
+    candles.extend(aggregator.push(trade(4, at(1, 40), "1", "13")));
+    candles.extend(aggregator.push(trade(5, at(0, 59), "1", "99")));
+
+    // Assert
+    assert_eq!(candles.len(), 1);
+    assert_eq!(candles[0].time_stamp, at(0, 0));
+    assert_eq!((candles[0].open, candles[0].close, candles[0].volume), (decimal("11"), decimal("10"), decimal("2")));
+    assert_eq!(aggregator.late_trades(), 1);
+}
@@ -0,0 +1,140 @@
+extern crate mockito;
+extern crate bittrex_api;
+
+use std::env;
+use std::fs;
+
+use mockito::{mock, Matcher};
+
+use bittrex_api::BittrexClient;
+use bittrex_api::cassette::{Cassette, Interaction, ReplayTransport};
+use bittrex_api::error::BittrexErrorType;
+use bittrex_api::v3::V3OrderStatus;
+
+const BALANCES: &str = r#"{
+    "success" : true,
+    "message" : "",
+    "result" : [{
+            "Currency" : "DOGE",
+            "Balance" : 4.21549076,
+            "Available" : 4.21549076,
+            "Pending" : 0.00000000,
+            "CryptoAddress" : "DLxcEt3AatMyr2NTatzjsfHNoB9NT62HiF",
+            "Requested" : false,
+            "Uuid" : null
+        }
+    ]
+}"#;
+
+#[test]
+fn should_record_and_replay_calls_successfully() {
+    // Arrange
+    let _mock = mock("GET", Matcher::Regex(r"^/account/getbalances\?apikey=KEY&nonce=\d+$".to_string()))
+        .with_status(200)
+        .with_body(BALANCES)
+        .create();
+    let path = env::temp_dir().join(format!("bittrex-api-cassette-{}.json", std::process::id()));
+    let cassette = Cassette::new();
+    let recording_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url(mockito::server_url())
+        .record(cassette.clone())
+        .build()
+        .unwrap();
+
+    // Act
+    let recorded_balances = recording_client.get_balances().unwrap();
+    cassette.save(&path).unwrap();
+    let content = fs::read_to_string(&path).unwrap();
+
+    let replaying_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url(mockito::server_url())
+        .transport(ReplayTransport::new(Cassette::load(&path).unwrap()))
+        .build()
+        .unwrap();
+    let replayed_balances = replaying_client.get_balances().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    // Assert
+    assert_eq!(replayed_balances.len(), 1);
+    assert_eq!(replayed_balances[0].currency, recorded_balances[0].currency);
+    assert_eq!(replayed_balances[0].balance, recorded_balances[0].balance);
+    assert!(content.contains("apikey=REDACTED&nonce=REDACTED"));
+    assert!(!content.contains("KEY&"));
+    assert!(!content.contains("apisign"));
+    assert!(content.contains(r#""method": "GET""#));
+}
+
+#[test]
+fn should_match_recorded_calls_by_path_and_params() {
+    // Arrange
+    let cassette = Cassette::from_interactions(vec![
+        Interaction {
+            method: "GET".to_string(),
+            url: "https://bittrex.com/api/v1.1/public/getticker?market=BTC-ETH".to_string(),
+            request_body: None,
+            status: 200,
+            body: r#"{ "success" : true, "message" : "", "result" : { "Bid" : 1.0, "Ask" : 2.0, "Last" : 1.5 } }"#.to_string(),
+        },
+        Interaction {
+            method: "GET".to_string(),
+            url: "https://bittrex.com/api/v1.1/public/getticker?market=BTC-LTC".to_string(),
+            request_body: None,
+            status: 200,
+            body: r#"{ "success" : true, "message" : "", "result" : { "Bid" : 3.0, "Ask" : 4.0, "Last" : 3.5 } }"#.to_string(),
+        },
+        Interaction {
+            method: "GET".to_string(),
+            url: "https://bittrex.com/api/v1.1/account/getorder?uuid=ORDERID&apikey=REDACTED&nonce=REDACTED".to_string(),
+            request_body: None,
+            status: 200,
+            body: r#"{ "success" : false, "message" : "UUID_INVALID", "result" : null }"#.to_string(),
+        },
+    ]);
+    let bittrex_client = BittrexClient::builder()
+        .credentials("OTHERKEY".to_string(), "SECRET".to_string())
+        .transport(ReplayTransport::new(cassette))
+        .build()
+        .unwrap();
+
+    // Act
+    let ticker = bittrex_client.get_ticker(&"BTC-LTC".parse().unwrap()).unwrap();
+    let repeated_ticker = bittrex_client.get_ticker(&"BTC-LTC".parse().unwrap()).unwrap();
+    let order = bittrex_client.get_order("ORDERID");
+    let unknown = bittrex_client.get_ticker(&"BTC-DOGE".parse().unwrap());
+
+    // Assert
+    assert_eq!(ticker.last.to_string(), "3.5");
+    assert_eq!(repeated_ticker.last.to_string(), "3.5");
+    assert_eq!(order.err().unwrap().message, "UUID_INVALID");
+    assert_eq!(unknown.err().unwrap().error_type, BittrexErrorType::Configuration);
+}
+
+#[test]
+fn should_match_recorded_calls_by_method_successfully() {
+    // Arrange
+    let order = |status: &str| Interaction {
+        method: if status == "OPEN" { "GET" } else { "DELETE" }.to_string(),
+        url: "https://api.bittrex.com/v3/orders/ORDERID".to_string(),
+        request_body: None,
+        status: 200,
+        body: format!(r#"{{ "id" : "ORDERID", "marketSymbol" : "LTC-BTC", "direction" : "BUY", "type" : "LIMIT", "quantity" : "1", "limit" : "0.01",
+            "timeInForce" : "GOOD_TIL_CANCELLED", "fillQuantity" : "0", "commission" : "0", "proceeds" : "0", "status" : "{}",
+            "createdAt" : "2020-01-01T00:00:00Z" }}"#, status),
+    };
+    let cassette = Cassette::from_interactions(vec![order("CLOSED"), order("OPEN")]);
+    let bittrex_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .transport(ReplayTransport::new(cassette))
+        .build_v3()
+        .unwrap();
+
+    // Act
+    let open = bittrex_client.get_order("ORDERID").unwrap();
+    let cancelled = bittrex_client.cancel_order("ORDERID").unwrap();
+
+    // Assert
+    assert_eq!(open.status, V3OrderStatus::Open);
+    assert_eq!(cancelled.status, V3OrderStatus::Closed);
+}
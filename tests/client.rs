@@ -1,9 +1,14 @@
 extern crate mockito;
+extern crate chrono;
 extern crate bittrex_api;
 
 use mockito::{mock, Matcher};
-use bittrex_api::BittrexClient;
-use bittrex_api::values::BittrexOrderType;
+use chrono::{Datelike, Timelike};
+use std::time::{Duration, Instant};
+
+use bittrex_api::{BittrexClient, BittrexPublicClient, Currency, Decimal, RetryPolicy};
+use bittrex_api::error::{ApiErrorCode, BittrexErrorType};
+use bittrex_api::values::{BittrexOrderType, BittrexOrderSide, BittrexOrderKind, BittrexFillType, BittrexCondition, TickInterval};
 
 #[test]
 fn should_get_markets_successfully() {
@@ -35,7 +40,7 @@ fn should_get_markets_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     let markets = bittrex_client.get_markets().unwrap();
@@ -73,7 +78,7 @@ fn should_get_currencies_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     let currencies = bittrex_client.get_currencies().unwrap();
@@ -99,15 +104,15 @@ fn should_get_valid_ticker_successfully() {
             }
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let ticker = bittrex_client.get_ticker("BTC-LTC").unwrap();
+    let ticker = bittrex_client.get_ticker(&"BTC-LTC".parse().unwrap()).unwrap();
 
     // Assert
-    assert_eq!(ticker.bid, 2.05670368);
-    assert_eq!(ticker.ask, 3.35579531);
-    assert_eq!(ticker.last, 3.35579531);
+    assert_eq!(ticker.bid, Decimal::new(205670368, 8));
+    assert_eq!(ticker.ask, Decimal::new(335579531, 8));
+    assert_eq!(ticker.last, Decimal::new(335579531, 8));
 }
 
 #[test]
@@ -118,10 +123,10 @@ fn should_handle_invalid_ticker_successfully() {
         .with_status(200)
         .with_body(r#"{"success":false,"message":"INVALID_MARKET","result":null}"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    bittrex_client.get_ticker("BT-LT").unwrap();
+    bittrex_client.get_ticker(&"BT-LT".parse().unwrap()).unwrap();
 }
 
 #[test]
@@ -166,7 +171,7 @@ fn should_get_market_summaries_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     let summaries = bittrex_client.get_market_summaries().unwrap();
@@ -174,13 +179,17 @@ fn should_get_market_summaries_successfully() {
     // Assert
     assert_eq!(summaries.len(), 2);
     assert_eq!(summaries[0].market_name, "BTC-888");
-    assert_eq!(summaries[0].last, 0.00000820);
+    assert_eq!(summaries[0].last, Decimal::new(820, 8));
+    assert_eq!(summaries[0].volume, Decimal::new(7433961396015, 8));
+    assert_eq!(summaries[0].time_stamp.hour(), 7);
+    assert_eq!(summaries[0].time_stamp.nanosecond(), 150_000_000);
+    assert_eq!(summaries[1].created.year(), 2014);
 }
 
 #[test]
 fn should_get_market_summary_successfully() {
     // Arrange
-    let _mock = mock("GET", "/public/getmarketsummary?market=btc-ltc")
+    let _mock = mock("GET", "/public/getmarketsummary?market=BTC-LTC")
         .with_status(200)
         .with_body(r#"{
             "success" : true,
@@ -204,10 +213,10 @@ fn should_get_market_summary_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let summary = bittrex_client.get_market_summary("btc-ltc").unwrap();
+    let summary = bittrex_client.get_market_summary(&"btc-ltc".parse().unwrap()).unwrap();
 
     // Assert
     assert_eq!(summary.market_name, "BTC-LTC");
@@ -244,14 +253,14 @@ fn should_get_order_book_successfully() {
             }
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let summary = bittrex_client.get_order_book("BTC-LTC", BittrexOrderType::Both).unwrap();
+    let summary = bittrex_client.get_order_book(&"BTC-LTC".parse().unwrap(), BittrexOrderType::Both).unwrap();
 
     // Assert
     assert_eq!(summary.buy.len(), 1);
-    assert_eq!(summary.buy[0].quantity, 12.37000000);
+    assert_eq!(summary.buy[0].quantity, Decimal::new(1237, 2));
     assert_eq!(summary.sell.len(), 4);
 }
 
@@ -299,14 +308,71 @@ fn should_get_market_history_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let history = bittrex_client.get_market_history("BTC-DOGE").unwrap();
+    let history = bittrex_client.get_market_history(&"BTC-DOGE".parse().unwrap()).unwrap();
 
     // Assert
     assert_eq!(history.len(), 4);
-    assert_eq!(history[0].quantity, 0.30802438);
+    assert_eq!(history[0].quantity, Decimal::new(30802438, 8));
+    assert_eq!(history[1].fill_type, BittrexFillType::PartialFill);
+    assert_eq!(history[2].side(), BittrexOrderSide::Sell);
+}
+
+#[test]
+fn should_get_ticks_successfully() {
+    // Arrange
+    let _mock = mock("GET", "/pub/market/GetTicks?marketName=BTC-WAVES&tickInterval=thirtyMin")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : [
+                { "O" : 0.00011000, "H" : 0.00012000, "L" : 0.00010500, "C" : 0.00011500, "V" : 1200.5, "T" : "2017-08-03T19:00:00", "BV" : 0.13512 },
+                { "O" : 0.00011500, "H" : 0.00011600, "L" : 0.00011400, "C" : 0.00011400, "V" : 10.0, "T" : "2017-08-03T19:30:00", "BV" : 0.00114 }
+            ]
+        }"#)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .v2_api_url(mockito::server_url())
+        .build()
+        .unwrap();
+
+    // Act
+    let ticks = bittrex_client.get_ticks(&"BTC-WAVES".parse().unwrap(), TickInterval::ThirtyMin).unwrap();
+
+    // Assert
+    assert_eq!(ticks.len(), 2);
+    assert_eq!(ticks[0].open, Decimal::new(11000, 8));
+    assert_eq!(ticks[0].high, Decimal::new(12000, 8));
+    assert_eq!(ticks[0].volume, Decimal::new(12005, 1));
+    assert_eq!(ticks[0].base_volume, Decimal::new(13512, 5));
+    assert_eq!(ticks[1].time_stamp.minute(), 30);
+}
+
+#[test]
+fn should_get_latest_tick_successfully() {
+    // Arrange
+    let _mock = mock("GET", "/pub/market/GetLatestTick?marketName=BTC-LTC&tickInterval=oneMin")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : [{ "O" : 0.0062, "H" : 0.0063, "L" : 0.0061, "C" : 0.00625, "V" : 3.5, "T" : "2017-08-03T19:01:00", "BV" : 0.0218 }]
+        }"#)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .v2_api_url(mockito::server_url())
+        .build()
+        .unwrap();
+
+    // Act
+    let tick = bittrex_client.get_latest_tick(&"BTC-LTC".parse().unwrap(), TickInterval::OneMin).unwrap();
+
+    // Assert
+    assert_eq!(tick.close, Decimal::new(625, 5));
+    assert_eq!(tick.low, Decimal::new(61, 4));
 }
 
 #[test]
@@ -322,10 +388,10 @@ fn should_buy_limit_successfully() {
                 }
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let buy_limit = bittrex_client.buy_limit("BTC-LTC", 1.2, 1.3).unwrap();
+    let buy_limit = bittrex_client.buy_limit(&"BTC-LTC".parse().unwrap(), Decimal::new(12, 1), Decimal::new(13, 1)).unwrap();
 
     // Assert
     assert_eq!(buy_limit.uuid, "e606d53c-8d70-11e3-94b5-425861b86ab6".to_string());
@@ -344,10 +410,10 @@ fn should_sell_limit_successfully() {
                 }
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let sell_limit = bittrex_client.sell_limit("BTC-LTC", 1.2, 1.3).unwrap();
+    let sell_limit = bittrex_client.sell_limit(&"BTC-LTC".parse().unwrap(), Decimal::new(12, 1), Decimal::new(13, 1)).unwrap();
 
     // Assert
     assert_eq!(sell_limit.uuid, "e606d53c-8d70-11e3-94b5-425861b86ab6".to_string());
@@ -364,7 +430,7 @@ fn should_cancel_order_successfully() {
             "result" : null
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     bittrex_client.cancel_order("e606d53c-8d70-11e3-94b5-425861b86ab6").unwrap();
@@ -373,7 +439,7 @@ fn should_cancel_order_successfully() {
 #[test]
 fn should_get_open_orders_successfully() {
     // Arrange
-    let _mock = mock("GET", Matcher::Regex(r"^/market/getopenorders\?&apikey=(.*)$".to_string()))
+    let _mock = mock("GET", Matcher::Regex(r"^/market/getopenorders\?apikey=(.*)$".to_string()))
         .with_status(200)
         .with_body(r#"{
             "success" : true,
@@ -418,14 +484,16 @@ fn should_get_open_orders_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     let open_orders = bittrex_client.get_open_orders().unwrap();
 
     // Assert
     assert_eq!(open_orders.len(), 2);
-    assert_eq!(open_orders[0].quantity, 5.0);
+    assert_eq!(open_orders[0].quantity, Decimal::new(5, 0));
+    assert_eq!(open_orders[0].order_type, BittrexOrderKind::LimitSell);
+    assert_eq!(open_orders[1].side(), BittrexOrderSide::Buy);
 }
 
 #[test]
@@ -458,20 +526,20 @@ fn should_get_open_orders_by_market_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let open_orders = bittrex_client.get_open_orders_by_market("BTC-LTC").unwrap();
+    let open_orders = bittrex_client.get_open_orders_by_market(&"BTC-LTC".parse().unwrap()).unwrap();
 
     // Assert
     assert_eq!(open_orders.len(), 1);
-    assert_eq!(open_orders[0].quantity, 5.0);
+    assert_eq!(open_orders[0].quantity, Decimal::new(5, 0));
 }
 
 #[test]
 fn should_get_balances_successfully() {
     // Arrange
-    let _mock = mock("GET", Matcher::Regex(r"^/account/getbalances\?&apikey=(.*)$".to_string()))
+    let _mock = mock("GET", Matcher::Regex(r"^/account/getbalances\?apikey=(.*)$".to_string()))
         .with_status(200)
         .with_body(r#"{
             "success" : true,
@@ -496,7 +564,7 @@ fn should_get_balances_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     let balances = bittrex_client.get_balances().unwrap();
@@ -525,13 +593,14 @@ fn should_get_balance_successfully() {
             }
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let balance = bittrex_client.get_balance("BTC").unwrap();
+    let balance = bittrex_client.get_balance(&Currency::new("BTC").unwrap()).unwrap();
 
     // Assert
     assert_eq!(balance.currency, "BTC");
+    assert_eq!(balance.balance, Decimal::new(1421549076, 8));
 }
 
 #[test]
@@ -548,10 +617,10 @@ fn should_get_deposit_address_successfully() {
             }
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let deposit_address = bittrex_client.get_deposit_address("VTC").unwrap();
+    let deposit_address = bittrex_client.get_deposit_address(&Currency::new("VTC").unwrap()).unwrap();
 
     // Assert
     assert_eq!(deposit_address.currency, "VTC");
@@ -571,10 +640,10 @@ fn should_withdraw_successfully() {
                 }
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let withdraw = bittrex_client.withdraw("BTC", 1.2, "ADRESS", "").unwrap();
+    let withdraw = bittrex_client.withdraw(&Currency::new("BTC").unwrap(), Decimal::new(12, 1), "ADRESS", "").unwrap();
 
     // Assert
     assert_eq!(withdraw.uuid, "e606d53c-8d70-11e3-94b5-425861b86ab6".to_string());
@@ -615,19 +684,23 @@ fn should_get_order_successfully() {
             }
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     let order = bittrex_client.get_order("ORDERID").unwrap();
 
     // Assert
     assert_eq!(order.order_uuid, "0cb4c4e4-bdc7-4e13-8c13-430e587d2cc1".to_string());
+    assert_eq!(order.opened.day(), 13);
+    assert_eq!(order.closed, None);
+    assert_eq!(order.condition, Some(BittrexCondition::None));
+    assert!(order.is_limit());
 }
 
 #[test]
 fn should_get_order_history_successfully() {
     // Arrange
-    let _mock = mock("GET", Matcher::Regex(r"^/account/getorderhistory\?&apikey=(.*)$".to_string()))
+    let _mock = mock("GET", Matcher::Regex(r"^/account/getorderhistory\?apikey=(.*)$".to_string()))
         .with_status(200)
         .with_body(r#"{
             "success" : true,
@@ -666,7 +739,7 @@ fn should_get_order_history_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     let order_history = bittrex_client.get_order_history().unwrap();
@@ -703,10 +776,10 @@ fn should_get_order_history_by_market_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let order_history = bittrex_client.get_order_history_by_market("BTC-LTC").unwrap();
+    let order_history = bittrex_client.get_order_history_by_market(&"BTC-LTC".parse().unwrap()).unwrap();
 
     // Assert
     assert_eq!(order_history.len(), 1);
@@ -716,7 +789,7 @@ fn should_get_order_history_by_market_successfully() {
 #[test]
 fn should_get_withdrawal_history_successfully() {
     // Arrange
-    let _mock = mock("GET", Matcher::Regex(r"^/account/getwithdrawalhistory\?&apikey=(.*)$".to_string()))
+    let _mock = mock("GET", Matcher::Regex(r"^/account/getwithdrawalhistory\?apikey=(.*)$".to_string()))
         .with_status(200)
         .with_body(r#"{
             "success" : true,
@@ -749,7 +822,7 @@ fn should_get_withdrawal_history_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     let withdrawal_history = bittrex_client.get_withdrawal_history().unwrap();
@@ -783,10 +856,10 @@ fn should_get_withdrawal_history_by_currency_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let withdrawal_history = bittrex_client.get_withdrawal_history_by_currency("BTC").unwrap();
+    let withdrawal_history = bittrex_client.get_withdrawal_history_by_currency(&Currency::new("BTC").unwrap()).unwrap();
 
     // Assert
     assert_eq!(withdrawal_history.len(), 1);
@@ -796,7 +869,7 @@ fn should_get_withdrawal_history_by_currency_successfully() {
 #[test]
 fn should_get_deposit_history_successfully() {
     // Arrange
-    let _mock = mock("GET", Matcher::Regex(r"^/account/getdeposithistory\?&apikey=(.*)$".to_string()))
+    let _mock = mock("GET", Matcher::Regex(r"^/account/getdeposithistory\?apikey=(.*)$".to_string()))
         .with_status(200)
         .with_body(r#"{
             "success" : true,
@@ -829,7 +902,7 @@ fn should_get_deposit_history_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
     let deposit_history = bittrex_client.get_deposit_history().unwrap();
@@ -863,12 +936,246 @@ fn should_get_deposit_history_by_currency_successfully() {
             ]
         }"#)
         .create();
-    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::SERVER_URL.to_string());
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
 
     // Act
-    let deposit_history = bittrex_client.get_deposit_history_by_currency("BTC").unwrap();
+    let deposit_history = bittrex_client.get_deposit_history_by_currency(&Currency::new("BTC").unwrap()).unwrap();
 
     // Assert
     assert_eq!(deposit_history.len(), 1);
     assert_eq!(deposit_history[0].payment_uuid, "554ec664-8842-4fe9-b491-06225becbd59");
-}
\ No newline at end of file
+}
+
+#[test]
+fn should_reuse_configured_client_for_multiple_calls() {
+    // Arrange
+    let _mock = mock("GET", "/public/getticker?market=BTC-LTC")
+        .with_status(200)
+        .with_body(r#"{"success":true,"message":"","result":{"Bid":2.0,"Ask":3.0,"Last":2.5}}"#)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url(mockito::server_url())
+        .connect_timeout(Duration::from_secs(1))
+        .timeout(Duration::from_secs(5))
+        .max_idle_per_host(1)
+        .build()
+        .unwrap();
+    let cloned_client = bittrex_client.clone();
+
+    // Act
+    let first = bittrex_client.get_ticker(&"BTC-LTC".parse().unwrap()).unwrap();
+    let second = cloned_client.get_ticker(&"BTC-LTC".parse().unwrap()).unwrap();
+
+    // Assert
+    assert_eq!(first.last, Decimal::new(25, 1));
+    assert_eq!(second.last, Decimal::new(25, 1));
+}
+
+#[test]
+fn should_fail_to_build_client_with_invalid_proxy() {
+    // Act
+    let result = BittrexClient::builder()
+        .https_proxy("not a proxy url".to_string())
+        .build();
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn should_send_user_agent_and_default_headers() {
+    // Arrange
+    let _mock = mock("GET", "/public/getcurrencies")
+        .match_header("user-agent", "my-bot/1.0")
+        .match_header("x-client-id", "42")
+        .with_status(200)
+        .with_body(r#"{"success":true,"message":"","result":[]}"#)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .api_url(mockito::server_url())
+        .user_agent("my-bot/1.0".to_string())
+        .default_header("X-Client-Id".to_string(), "42".to_string())
+        .build()
+        .unwrap();
+
+    // Act
+    let currencies = bittrex_client.get_currencies().unwrap();
+
+    // Assert
+    assert_eq!(currencies.len(), 0);
+}
+
+#[test]
+fn should_fail_to_build_client_with_invalid_header() {
+    // Act
+    let result = BittrexClient::builder()
+        .default_header("Invalid Header".to_string(), "value".to_string())
+        .build();
+
+    // Assert
+    assert!(result.is_err());
+}
+
+#[test]
+fn should_get_ticker_with_public_client_successfully() {
+    // Arrange
+    let _mock = mock("GET", "/public/getticker?market=BTC-LTC")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : {
+                "Bid" : 2.05670368,
+                "Ask" : 3.35579531,
+                "Last" : 3.35579531
+            }
+        }"#)
+        .create();
+    let bittrex_client: BittrexPublicClient = BittrexClient::builder()
+        .api_url(mockito::server_url())
+        .build()
+        .unwrap();
+
+    // Act
+    let ticker = bittrex_client.get_ticker(&"BTC-LTC".parse().unwrap()).unwrap();
+
+    // Assert
+    assert_eq!(ticker.bid, Decimal::new(205670368, 8));
+}
+
+#[test]
+fn should_retry_idempotent_calls_only() {
+    // Arrange
+    let bittrex_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url("http://127.0.0.1:1".to_string())
+        .retry_policy(RetryPolicy::new(2, Duration::from_millis(200)).jitter(false))
+        .build()
+        .unwrap();
+
+    // Act
+    let started = Instant::now();
+    let balances = bittrex_client.get_balances();
+    let retried_duration = started.elapsed();
+
+    let started = Instant::now();
+    let buy_limit = bittrex_client.buy_limit(&"BTC-LTC".parse().unwrap(), Decimal::new(1, 0), Decimal::new(1, 0));
+    let single_duration = started.elapsed();
+
+    // Assert
+    assert!(balances.is_err());
+    assert!(buy_limit.is_err());
+    assert!(retried_duration >= Duration::from_millis(400));
+    assert!(single_duration < Duration::from_millis(200));
+}
+
+#[test]
+fn should_retry_server_errors() {
+    // Arrange
+    let mock = mock("GET", "/public/getcurrencies")
+        .with_status(503)
+        .expect(3)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .api_url(mockito::server_url())
+        .retry_policy(RetryPolicy::new(2, Duration::from_millis(10)))
+        .build()
+        .unwrap();
+
+    // Act
+    let currencies = bittrex_client.get_currencies();
+
+    // Assert
+    assert_eq!(currencies.err().unwrap().message, "HTTP status 503 Service Unavailable");
+    mock.assert();
+}
+
+#[test]
+fn should_retry_trading_calls_only_if_opted_in() {
+    // Arrange
+    let single_mock = mock("GET", Matcher::Regex(r"^/market/selllimit\?market=BTC-DOGE(.*)$".to_string()))
+        .with_status(500)
+        .expect(1)
+        .create();
+    let retried_mock = mock("GET", Matcher::Regex(r"^/market/selllimit\?market=BTC-XRP(.*)$".to_string()))
+        .with_status(500)
+        .expect(3)
+        .create();
+    let retry_policy = RetryPolicy::new(2, Duration::from_millis(10));
+    let bittrex_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url(mockito::server_url())
+        .retry_policy(retry_policy.clone())
+        .build()
+        .unwrap();
+    let retrying_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url(mockito::server_url())
+        .retry_policy(retry_policy.retry_trading(true))
+        .build()
+        .unwrap();
+
+    // Act
+    let single_sell_limit = bittrex_client.sell_limit(&"BTC-DOGE".parse().unwrap(), Decimal::new(1, 0), Decimal::new(1, 0));
+    let retried_sell_limit = retrying_client.sell_limit(&"BTC-XRP".parse().unwrap(), Decimal::new(1, 0), Decimal::new(1, 0));
+
+    // Assert
+    assert!(single_sell_limit.is_err());
+    assert!(retried_sell_limit.is_err());
+    single_mock.assert();
+    retried_mock.assert();
+}
+
+#[test]
+fn should_handle_null_results() {
+    // Arrange
+    let null_result = r#"{
+            "success" : true,
+            "message" : "",
+            "result" : null
+        }"#;
+    let _history_mock = mock("GET", Matcher::Regex(r"^/account/getdeposithistory\?apikey=(.*)$".to_string()))
+        .with_status(200)
+        .with_body(null_result)
+        .create();
+    let _address_mock = mock("GET", Matcher::Regex(r"^/account/getdepositaddress\?currency=LTC(.*)$".to_string()))
+        .with_status(200)
+        .with_body(null_result)
+        .create();
+    let _cancel_mock = mock("GET", Matcher::Regex(r"^/market/cancel\?uuid=ORDERID(.*)$".to_string()))
+        .with_status(200)
+        .with_body(null_result)
+        .create();
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+
+    // Act
+    let deposit_history = bittrex_client.get_deposit_history().unwrap();
+    let deposit_address = bittrex_client.get_deposit_address(&Currency::new("LTC").unwrap());
+    let cancel = bittrex_client.cancel_order("ORDERID");
+
+    // Assert
+    assert!(deposit_history.is_empty());
+    assert_eq!(deposit_address.err().unwrap().error_type, BittrexErrorType::NoResults);
+    assert!(cancel.is_ok());
+}
+
+#[test]
+fn should_return_error_for_rejected_cancel() {
+    // Arrange
+    let _mock = mock("GET", Matcher::Regex(r"^/market/cancel\?uuid=CLOSEDORDER(.*)$".to_string()))
+        .with_status(200)
+        .with_body(r#"{
+            "success" : false,
+            "message" : "ORDER_NOT_OPEN",
+            "result" : null
+        }"#)
+        .create();
+    let bittrex_client = BittrexClient::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+
+    // Act
+    let cancel = bittrex_client.cancel_order("CLOSEDORDER");
+
+    // Assert
+    assert_eq!(cancel.err().unwrap().error_type, BittrexErrorType::Api(ApiErrorCode::OrderNotOpen));
+}
@@ -0,0 +1,47 @@
+extern crate chrono;
+extern crate bittrex_api;
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use bittrex_api::datetime;
+
+fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32, milli: u32) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_milli_opt(hour, min, sec, milli).unwrap())
+}
+
+#[test]
+fn should_parse_timestamps_with_fractional_seconds() {
+    // Assert
+    assert_eq!(datetime::parse("2014-07-09T07:19:30.15").unwrap(), utc(2014, 7, 9, 7, 19, 30, 150));
+    assert_eq!(datetime::parse("2014-05-30T07:57:49.637").unwrap(), utc(2014, 5, 30, 7, 57, 49, 637));
+}
+
+#[test]
+fn should_parse_timestamps_without_fractional_seconds() {
+    // Assert
+    assert_eq!(datetime::parse("2014-02-13T00:00:00").unwrap(), utc(2014, 2, 13, 0, 0, 0, 0));
+    assert_eq!(datetime::parse("2014-02-13 10:11:12").unwrap(), utc(2014, 2, 13, 10, 11, 12, 0));
+}
+
+#[test]
+fn should_parse_timestamps_with_timezone() {
+    // Assert
+    assert_eq!(datetime::parse("2014-07-09T07:19:30.15Z").unwrap(), utc(2014, 7, 9, 7, 19, 30, 150));
+    assert_eq!(datetime::parse("2014-07-09T09:19:30+02:00").unwrap(), utc(2014, 7, 9, 7, 19, 30, 0));
+}
+
+#[test]
+fn should_reject_unknown_timestamps() {
+    // Assert
+    assert!(datetime::parse("yesterday").is_err());
+    assert!(datetime::parse("").is_err());
+}
+
+#[test]
+fn should_format_like_bittrex() {
+    // Act
+    let formatted = datetime::format(&utc(2014, 7, 9, 7, 19, 30, 150));
+
+    // Assert
+    assert_eq!(datetime::parse(&formatted).unwrap(), utc(2014, 7, 9, 7, 19, 30, 150));
+    assert!(formatted.starts_with("2014-07-09T07:19:30.15"));
+}
@@ -0,0 +1,142 @@
+extern crate serde_json;
+extern crate bittrex_api;
+
+use bittrex_api::Decimal;
+use bittrex_api::decimal::ParseDecimalError;
+
+#[test]
+fn should_parse_decimal_successfully() {
+    // Act
+    let value: Decimal = "0.00000821".parse().unwrap();
+    let negative: Decimal = "-12.5".parse().unwrap();
+
+    // Assert
+    assert_eq!(value.satoshis(), 821);
+    assert_eq!(negative, Decimal::new(-125, 1));
+}
+
+#[test]
+fn should_reject_invalid_decimals() {
+    // Assert
+    assert_eq!("".parse::<Decimal>(), Err(ParseDecimalError::Empty));
+    assert_eq!("1.2.3".parse::<Decimal>(), Err(ParseDecimalError::InvalidDigit));
+    assert_eq!("0.000000001".parse::<Decimal>(), Err(ParseDecimalError::TooManyDecimalPlaces));
+    assert_eq!("100000000000000000000".parse::<Decimal>(), Err(ParseDecimalError::Overflow));
+}
+
+#[test]
+fn should_format_decimal_successfully() {
+    // Arrange
+    let value = Decimal::new(150, 2);
+
+    // Assert
+    assert_eq!(value.to_string(), "1.5");
+    assert_eq!(format!("{:.8}", value), "1.50000000");
+    assert_eq!(format!("{:.2}", Decimal::new(-12345, 4)), "-1.23");
+    assert_eq!(Decimal::new(1, 8).to_string(), "0.00000001");
+    assert_eq!(Decimal::new(42, 0).to_string(), "42");
+}
+
+#[test]
+fn should_add_without_drift() {
+    // Arrange
+    let step = Decimal::new(1, 1);
+
+    // Act
+    let sum: Decimal = (0..10).map(|_| step).sum();
+
+    // Assert
+    assert_eq!(sum, Decimal::new(1, 0));
+    assert_eq!(Decimal::new(3, 1) - Decimal::new(1, 1) - Decimal::new(2, 1), Decimal::zero());
+}
+
+#[test]
+fn should_multiply_and_divide_with_rounding() {
+    // Arrange
+    let quantity = Decimal::new(15, 1);
+    let rate = Decimal::new(23, 5);
+
+    // Assert
+    assert_eq!(quantity * rate, Decimal::new(345, 6));
+    assert_eq!(Decimal::new(1, 0) / Decimal::new(3, 0), Decimal::new(33333333, 8));
+    assert_eq!(Decimal::new(2, 0) / Decimal::new(3, 0), Decimal::new(66666667, 8));
+    assert_eq!(Decimal::new(1, 0).checked_div(Decimal::zero()), None);
+}
+
+#[test]
+fn should_round_and_truncate_successfully() {
+    // Arrange
+    let value = Decimal::new(-123456789, 8);
+
+    // Assert
+    assert_eq!(value.round_dp(4), Decimal::new(-12346, 4));
+    assert_eq!(value.trunc_dp(4), Decimal::new(-12345, 4));
+}
+
+#[test]
+fn should_deserialize_json_numbers_losslessly() {
+    // Act
+    let values: Vec<Decimal> = serde_json::from_str("[0.00389158, 74339.61396015, 100, 0.1, \"0.00000001\"]").unwrap();
+
+    // Assert
+    assert_eq!(values[0].satoshis(), 389158);
+    assert_eq!(values[1].satoshis(), 7433961396015);
+    assert_eq!(values[2], Decimal::new(100, 0));
+    assert_eq!(values[3], Decimal::new(1, 1));
+    assert_eq!(values[4].satoshis(), 1);
+}
+
+#[test]
+fn should_serialize_to_json_number() {
+    // Act
+    let json = serde_json::to_string(&Decimal::new(821, 8)).unwrap();
+
+    // Assert
+    assert_eq!(serde_json::from_str::<Decimal>(&json).unwrap(), Decimal::new(821, 8));
+}
+
+#[test]
+fn should_round_trip_full_precision_through_json_successfully() {
+    // Arrange
+    let value: Decimal = "12345678.12345678".parse().unwrap();
+
+    // Act
+    let json = serde_json::to_string(&value).unwrap();
+    let parsed: Decimal = serde_json::from_str(&json).unwrap();
+    let from_value: Decimal = serde_json::from_value(serde_json::from_str(&json).unwrap()).unwrap();
+
+    // Assert
+    assert_eq!(json, "12345678.12345678");
+    assert_eq!(parsed, value);
+    assert_eq!(from_value, value);
+    assert_eq!(serde_json::from_str::<Decimal>("92233720368.54775807").unwrap().satoshis(), i64::max_value());
+}
+
+#[test]
+fn should_parse_exponent_notation_successfully() {
+    // Assert
+    assert_eq!("1E-8".parse::<Decimal>(), Ok(Decimal::new(1, 8)));
+    assert_eq!("2.5e-7".parse::<Decimal>(), Ok(Decimal::new(25, 8)));
+    assert_eq!("-1.5e+2".parse::<Decimal>(), Ok(Decimal::new(-150, 0)));
+    assert_eq!("1.50000e-7".parse::<Decimal>(), Ok(Decimal::new(15, 8)));
+    assert_eq!("0e10".parse::<Decimal>(), Ok(Decimal::zero()));
+    assert_eq!("1e-9".parse::<Decimal>(), Err(ParseDecimalError::TooManyDecimalPlaces));
+    assert_eq!("1e30".parse::<Decimal>(), Err(ParseDecimalError::Overflow));
+    assert_eq!("1e".parse::<Decimal>(), Err(ParseDecimalError::InvalidDigit));
+}
+
+#[test]
+fn should_deserialize_json_numbers_in_exponent_notation_successfully() {
+    // Act
+    let values: Vec<Decimal> = serde_json::from_str("[1E-8, 1.5e+2, 2.5e-7, 1.23456789e-5, 4e-9, 6e-9]").unwrap();
+
+    // Assert
+    assert_eq!(values, vec![
+        Decimal::new(1, 8),
+        Decimal::new(150, 0),
+        Decimal::new(25, 8),
+        Decimal::new(1235, 8),
+        Decimal::zero(),
+        Decimal::new(1, 8),
+    ]);
+}
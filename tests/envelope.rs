@@ -0,0 +1,323 @@
+extern crate mockito;
+extern crate quickcheck;
+extern crate rand;
+extern crate serde_json;
+extern crate tokio;
+extern crate bittrex_api;
+
+use mockito::{mock, Matcher};
+use quickcheck::{Arbitrary, Gen, QuickCheck};
+use rand::Rng;
+use serde_json::{Map, Number, Value};
+use tokio::runtime::Runtime;
+
+use bittrex_api::{BittrexClient, Currency, Decimal};
+use bittrex_api::error::{BittrexError, BittrexErrorType};
+use bittrex_api::values::{BittrexOrderType, TickInterval};
+use bittrex_api::v3::{BittrexV3Client, V3Error, V3NewOrder, V3NewWithdrawal, V3OrderDirection, V3TimeInForce};
+
+const FIELD_NAMES: &[&str] = &[
+    "AccountId", "Address", "Amount", "Ask", "Authorized", "Available", "BV", "Balance", "BaseAddress", "BaseCurrency",
+    "BaseCurrencyLong", "BaseVolume", "Bid", "C", "CancelInitiated", "Canceled", "Closed", "CoinType", "Commission",
+    "CommissionPaid", "CommissionReserveRemaining", "CommissionReserved", "Condition", "ConditionalTarget", "Created",
+    "CryptoAddress", "Currency", "CurrencyLong", "DisplayMarketName", "Exchange", "FillType", "H", "High", "Id",
+    "ImmediateOrCancel", "InvalidAddress", "IsActive", "IsConditional", "IsOpen", "L", "Last", "Limit", "Low",
+    "MarketCurrency", "MarketCurrencyLong", "MarketName", "MinConfirmation", "MinTradeSize", "Notice", "O",
+    "OpenBuyOrders", "OpenSellOrders", "Opened", "OrderType", "OrderUuid", "PaymentUuid", "Pending", "PendingPayment",
+    "PrevDay", "Price", "PricePerUnit", "Quantity", "QuantityRemaining", "Rate", "ReserveRemaining", "Reserved",
+    "Sentinel", "T", "TimeStamp", "Total", "TxCost", "TxFee", "TxId", "Type", "Uuid", "V", "Volume", "buy", "sell", "uuid",
+];
+
+const V3_FIELD_NAMES: &[&str] = &[
+    "ask", "askRate", "available", "baseCurrencySymbol", "bid", "bidRate", "ceiling", "clientOrderId",
+    "clientWithdrawalId", "close", "closedAt", "code", "commission", "completedAt", "confirmations", "createdAt", "cryptoAddress",
+    "cryptoAddressTag", "currencySymbol", "data", "detail", "direction", "fillQuantity", "high", "id", "lastTradeRate", "limit",
+    "low", "marketSymbol", "minTradeSize", "notice", "open", "precision", "proceeds", "quantity", "quoteCurrencySymbol",
+    "quoteVolume", "rate", "source", "startsAt", "status", "symbol", "timeInForce", "total", "txCost", "txId", "type",
+    "updatedAt", "volume",
+];
+
+const V3_STATUSES: &[usize] = &[200, 201, 400, 401, 403, 404, 409, 429, 500, 503];
+
+const STRINGS: &[&str] = &[
+    "", "BTC", "BTC-LTC", "BTC-", "-", "btc-ltc", "LIMIT_BUY", "LIMIT_SELL", "BUY", "SELL", "FILL", "PARTIAL_FILL",
+    "NONE", "GREATER_THAN", "2014-07-09T07:19:30.15", "2014-02-13T00:00:00", "2014-07-09T07:19:30Z", "31/02/2014",
+    "0.00000001", "0.000000001", "1e400", "-1", "NaN", "92233720368.54775807", "INVALID_MARKET", "\u{0}", "\u{1F4A9}",
+];
+
+/// A response body as Bittrex might send it, from well-formed envelopes with odd results to garbage.
+#[derive(Clone, Debug)]
+struct Envelope(String);
+
+impl Arbitrary for Envelope {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let body = match g.gen_range(0, 10) {
+            0 => arbitrary_value(g, 2).to_string(),
+            1 => {
+                let body = arbitrary_envelope(g).to_string();
+                truncate(g, &body)
+            },
+            _ => arbitrary_envelope(g).to_string(),
+        };
+        Envelope(body)
+    }
+}
+
+/// A response of the v3 API: a `V3Error` or a result with any status, or garbage.
+#[derive(Clone, Debug)]
+struct V3Response {
+    status: usize,
+    body: String,
+}
+
+impl Arbitrary for V3Response {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let status = V3_STATUSES[g.gen_range(0, V3_STATUSES.len())];
+        let body = match g.gen_range(0, 10) {
+            0 => arbitrary_value(g, 2).to_string(),
+            1 => {
+                let body = arbitrary_v3_object(g).to_string();
+                truncate(g, &body)
+            },
+            2..=4 => arbitrary_v3_error(g).to_string(),
+            5 => Value::Array((0..g.gen_range(0, 4)).map(|_| arbitrary_v3_object(g)).collect()).to_string(),
+            _ => arbitrary_v3_object(g).to_string(),
+        };
+        V3Response { status: status, body: body }
+    }
+}
+
+fn truncate<G: Gen>(g: &mut G, body: &str) -> String {
+    let length = g.gen_range(0, body.len() + 1);
+    String::from_utf8_lossy(&body.as_bytes()[..length]).into_owned()
+}
+
+fn arbitrary_envelope<G: Gen>(g: &mut G) -> Value {
+    let mut envelope = Map::new();
+    if g.gen_bool(0.95) {
+        let success = if g.gen_bool(0.9) { Value::Bool(g.gen()) } else { arbitrary_value(g, 0) };
+        envelope.insert("success".to_string(), success);
+    }
+    if g.gen_bool(0.95) {
+        let message = if g.gen_bool(0.9) { arbitrary_string(g) } else { arbitrary_value(g, 0) };
+        envelope.insert("message".to_string(), message);
+    }
+    if g.gen_bool(0.95) {
+        let result = match g.gen_range(0, 4) {
+            0 => Value::Null,
+            1 => arbitrary_object(g, 1),
+            2 => Value::Array((0..g.gen_range(0, 4)).map(|_| arbitrary_object(g, 1)).collect()),
+            _ => arbitrary_value(g, 3),
+        };
+        envelope.insert("result".to_string(), result);
+    }
+    Value::Object(envelope)
+}
+
+fn arbitrary_value<G: Gen>(g: &mut G, depth: u32) -> Value {
+    let kinds = if depth == 0 { 4 } else { 6 };
+    match g.gen_range(0, kinds) {
+        0 => Value::Null,
+        1 => Value::Bool(g.gen()),
+        2 => arbitrary_number(g),
+        3 => arbitrary_string(g),
+        4 => Value::Array((0..g.gen_range(0, 4)).map(|_| arbitrary_value(g, depth - 1)).collect()),
+        _ => arbitrary_object(g, depth - 1),
+    }
+}
+
+/// An object with a random selection of the fields Bittrex uses.
+fn arbitrary_object<G: Gen>(g: &mut G, depth: u32) -> Value {
+    let mut object = Map::new();
+    for field_name in FIELD_NAMES {
+        if g.gen_bool(0.7) {
+            object.insert(field_name.to_string(), arbitrary_value(g, depth));
+        }
+    }
+    Value::Object(object)
+}
+
+/// An object with a random selection of the fields of the v3 API.
+fn arbitrary_v3_object<G: Gen>(g: &mut G) -> Value {
+    let mut object = Map::new();
+    for field_name in V3_FIELD_NAMES {
+        if g.gen_bool(0.7) {
+            object.insert(field_name.to_string(), arbitrary_value(g, 1));
+        }
+    }
+    Value::Object(object)
+}
+
+fn arbitrary_v3_error<G: Gen>(g: &mut G) -> Value {
+    let mut error = Map::new();
+    if g.gen_bool(0.9) {
+        let code = if g.gen_bool(0.9) { arbitrary_string(g) } else { arbitrary_value(g, 0) };
+        error.insert("code".to_string(), code);
+    }
+    if g.gen_bool(0.5) {
+        error.insert("detail".to_string(), arbitrary_value(g, 0));
+    }
+    if g.gen_bool(0.3) {
+        error.insert("data".to_string(), arbitrary_value(g, 1));
+    }
+    Value::Object(error)
+}
+
+fn arbitrary_number<G: Gen>(g: &mut G) -> Value {
+    match g.gen_range(0, 6) {
+        0 => Value::Number(Number::from(g.gen::<i64>())),
+        1 => Value::Number(Number::from(g.gen::<u64>())),
+        2 => Value::Number(Number::from(g.gen_range(-1000, 1000))),
+        3 => Number::from_f64(g.gen::<f64>()).map(Value::Number).unwrap_or(Value::Null),
+        4 => Number::from_f64(1e300).map(Value::Number).unwrap_or(Value::Null),
+        _ => Number::from_f64(-0.000000005).map(Value::Number).unwrap_or(Value::Null),
+    }
+}
+
+fn arbitrary_string<G: Gen>(g: &mut G) -> Value {
+    if g.gen_bool(0.8) {
+        let index = g.gen_range(0, STRINGS.len());
+        Value::String(STRINGS[index].to_string())
+    } else {
+        Value::String(String::arbitrary(g))
+    }
+}
+
+/// Calls every endpoint of the blocking and the async client against a server which answers everything with `body`.
+/// Panics fail the test.
+fn decodes_without_panic(envelope: Envelope) -> bool {
+    let _mock = mock("GET", Matcher::Any)
+        .with_status(200)
+        .with_body(&envelope.0)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url(mockito::server_url())
+        .v2_api_url(mockito::server_url())
+        .build()
+        .unwrap();
+    let market = "BTC-LTC".parse().unwrap();
+    let currency = Currency::new("BTC").unwrap();
+
+    let _ = bittrex_client.get_markets();
+    let _ = bittrex_client.get_currencies();
+    let _ = bittrex_client.get_ticker(&market);
+    let _ = bittrex_client.get_market_summaries();
+    let _ = bittrex_client.get_market_summary(&market);
+    let _ = bittrex_client.get_order_book(&market, BittrexOrderType::Both);
+    let _ = bittrex_client.get_market_history(&market);
+    let _ = bittrex_client.get_ticks(&market, TickInterval::Hour);
+    let _ = bittrex_client.get_latest_tick(&market, TickInterval::OneMin);
+    let _ = bittrex_client.get_open_orders();
+    let _ = bittrex_client.get_open_orders_by_market(&market);
+    let _ = bittrex_client.get_order("ORDERID");
+    let _ = bittrex_client.get_order_history();
+    let _ = bittrex_client.get_order_history_by_market(&market);
+    let _ = bittrex_client.get_withdrawal_history();
+    let _ = bittrex_client.get_withdrawal_history_by_currency(&currency);
+    let _ = bittrex_client.get_deposit_history();
+    let _ = bittrex_client.get_deposit_history_by_currency(&currency);
+    let _ = bittrex_client.get_balances();
+    let _ = bittrex_client.get_balance(&currency);
+    let _ = bittrex_client.get_deposit_address(&currency);
+    let _ = bittrex_client.withdraw(&currency, Decimal::new(1, 0), "ADDRESS", "");
+    let _ = bittrex_client.buy_limit(&market, Decimal::new(1, 0), Decimal::new(1, 0));
+    let _ = bittrex_client.sell_limit(&market, Decimal::new(1, 0), Decimal::new(1, 0));
+    let _ = bittrex_client.cancel_order("ORDERID");
+
+    let async_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url(mockito::server_url())
+        .v2_api_url(mockito::server_url())
+        .build_async()
+        .unwrap();
+    let mut runtime = Runtime::new().unwrap();
+    let _ = runtime.block_on(async_client.get_markets());
+    let _ = runtime.block_on(async_client.get_currencies());
+    let _ = runtime.block_on(async_client.get_ticker(&market));
+    let _ = runtime.block_on(async_client.get_market_summaries());
+    let _ = runtime.block_on(async_client.get_market_summary(&market));
+    let _ = runtime.block_on(async_client.get_order_book(&market, BittrexOrderType::Both));
+    let _ = runtime.block_on(async_client.get_market_history(&market));
+    let _ = runtime.block_on(async_client.get_ticks(&market, TickInterval::Hour));
+    let _ = runtime.block_on(async_client.get_latest_tick(&market, TickInterval::OneMin));
+    let _ = runtime.block_on(async_client.get_open_orders());
+    let _ = runtime.block_on(async_client.get_open_orders_by_market(&market));
+    let _ = runtime.block_on(async_client.get_order("ORDERID"));
+    let _ = runtime.block_on(async_client.get_order_history());
+    let _ = runtime.block_on(async_client.get_order_history_by_market(&market));
+    let _ = runtime.block_on(async_client.get_withdrawal_history());
+    let _ = runtime.block_on(async_client.get_withdrawal_history_by_currency(&currency));
+    let _ = runtime.block_on(async_client.get_deposit_history());
+    let _ = runtime.block_on(async_client.get_deposit_history_by_currency(&currency));
+    let _ = runtime.block_on(async_client.get_balances());
+    let _ = runtime.block_on(async_client.get_balance(&currency));
+    let _ = runtime.block_on(async_client.get_deposit_address(&currency));
+    let _ = runtime.block_on(async_client.withdraw(&currency, Decimal::new(1, 0), "ADDRESS", ""));
+    let _ = runtime.block_on(async_client.buy_limit(&market, Decimal::new(1, 0), Decimal::new(1, 0)));
+    let _ = runtime.block_on(async_client.sell_limit(&market, Decimal::new(1, 0), Decimal::new(1, 0)));
+    let _ = runtime.block_on(async_client.cancel_order("ORDERID"));
+    true
+}
+
+/// Whether a v3 call failed as a response with an error status should: with an `Api` error for the code of
+/// a `V3Error` body and with an `HttpStatus` error for any other body. Calls answered with a success status only must not panic.
+fn rejected_by_status<T>(response: &V3Response, result: Result<T, BittrexError>) -> bool {
+    if response.status < 400 {
+        return true;
+    }
+    let error = match result {
+        Ok(_) => return false,
+        Err(error) => error,
+    };
+    let expected = match serde_json::from_str::<V3Error>(&response.body) {
+        Ok(v3_error) => BittrexError::api(v3_error.code).error_type,
+        Err(_) => BittrexErrorType::HttpStatus(response.status as u16),
+    };
+    error.error_type == expected && error.status() == Some(response.status as u16)
+}
+
+/// Calls every endpoint of the v3 client against a server which answers everything with `response`.
+fn v3_decodes_without_panic(response: V3Response) -> bool {
+    let _mocks: Vec<_> = ["GET", "POST", "DELETE"].iter()
+        .map(|method| mock(method, Matcher::Any).with_status(response.status).with_body(&response.body).create())
+        .collect();
+    let bittrex_client = BittrexV3Client::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+    let market = "BTC-LTC".parse().unwrap();
+    let currency = Currency::new("BTC").unwrap();
+    let order = V3NewOrder::limit(&market, V3OrderDirection::Buy, Decimal::new(1, 0), Decimal::new(1, 0), V3TimeInForce::GoodTilCancelled);
+    let withdrawal = V3NewWithdrawal::new(&currency, Decimal::new(1, 0), "ADDRESS");
+
+    rejected_by_status(&response, bittrex_client.get_markets())
+        && rejected_by_status(&response, bittrex_client.get_market(&market))
+        && rejected_by_status(&response, bittrex_client.get_tickers())
+        && rejected_by_status(&response, bittrex_client.get_ticker(&market))
+        && rejected_by_status(&response, bittrex_client.get_order_book(&market, Some(25)))
+        && rejected_by_status(&response, bittrex_client.get_candles(&market, TickInterval::Hour))
+        && rejected_by_status(&response, bittrex_client.get_balances())
+        && rejected_by_status(&response, bittrex_client.get_balance(&currency))
+        && rejected_by_status(&response, bittrex_client.get_open_orders(Some(&market)))
+        && rejected_by_status(&response, bittrex_client.get_closed_orders(None))
+        && rejected_by_status(&response, bittrex_client.get_order("ORDERID"))
+        && rejected_by_status(&response, bittrex_client.place_order(&order))
+        && rejected_by_status(&response, bittrex_client.cancel_order("ORDERID"))
+        && rejected_by_status(&response, bittrex_client.get_open_deposits(None))
+        && rejected_by_status(&response, bittrex_client.get_closed_deposits(Some(&currency)))
+        && rejected_by_status(&response, bittrex_client.get_deposit_address(&currency))
+        && rejected_by_status(&response, bittrex_client.get_open_withdrawals(None))
+        && rejected_by_status(&response, bittrex_client.get_closed_withdrawals(Some(&currency)))
+        && rejected_by_status(&response, bittrex_client.withdraw(&withdrawal))
+        && rejected_by_status(&response, bittrex_client.cancel_withdrawal("WITHDRAWALID"))
+}
+
+// A single test, as every case answers all paths of the shared mock server.
+#[test]
+fn should_decode_arbitrary_envelopes_without_panic() {
+    QuickCheck::new()
+        .tests(200)
+        .quickcheck(decodes_without_panic as fn(Envelope) -> bool);
+    QuickCheck::new()
+        .tests(200)
+        .quickcheck(v3_decodes_without_panic as fn(V3Response) -> bool);
+}
+
@@ -0,0 +1,185 @@
+extern crate mockito;
+extern crate bittrex_api;
+
+use mockito::{mock, Matcher};
+use std::error::Error;
+
+use bittrex_api::BittrexClient;
+use bittrex_api::error::{self, ApiErrorCode, BittrexError, BittrexErrorType};
+
+#[test]
+fn should_map_known_message_codes() {
+    // Arrange
+    let messages = ["INVALID_MARKET", "APIKEY_INVALID", "INSUFFICIENT_FUNDS", "MIN_TRADE_REQUIREMENT_NOT_MET", "ORDER_NOT_OPEN", "INVALID_SIGNATURE", "NONCE_USED"];
+
+    // Act
+    let codes: Vec<ApiErrorCode> = messages.iter().map(|message| ApiErrorCode::from(*message)).collect();
+
+    // Assert
+    assert_eq!(codes, vec![
+        ApiErrorCode::InvalidMarket,
+        ApiErrorCode::ApikeyInvalid,
+        ApiErrorCode::InsufficientFunds,
+        ApiErrorCode::MinTradeRequirementNotMet,
+        ApiErrorCode::OrderNotOpen,
+        ApiErrorCode::InvalidSignature,
+        ApiErrorCode::NonceUsed
+    ]);
+    assert!(codes.iter().zip(messages.iter()).all(|(code, message)| code.as_str() == *message));
+}
+
+#[test]
+fn should_keep_unknown_message_codes() {
+    // Act
+    let error = BittrexError::api("SOMETHING_NEW".to_string());
+
+    // Assert
+    assert_eq!(error.api_code(), Some(&ApiErrorCode::Unknown("SOMETHING_NEW".to_string())));
+    assert_eq!(error.to_string(), "Bittrex API rejected the call: SOMETHING_NEW");
+    assert!(!error.is_retryable());
+}
+
+#[test]
+fn should_classify_retryable_errors() {
+    // Assert
+    assert!(BittrexErrorType::Transport.is_retryable());
+    assert!(BittrexErrorType::RateLimited.is_retryable());
+    assert!(BittrexErrorType::HttpStatus(503).is_retryable());
+    assert!(BittrexErrorType::HttpStatus(429).is_retryable());
+    assert!(BittrexErrorType::Api(ApiErrorCode::NonceUsed).is_retryable());
+    assert!(!BittrexErrorType::HttpStatus(403).is_retryable());
+    assert!(!BittrexErrorType::Decoding.is_retryable());
+    assert!(!BittrexErrorType::Api(ApiErrorCode::InsufficientFunds).is_retryable());
+    assert!(!BittrexErrorType::Api(ApiErrorCode::InvalidSignature).is_retryable());
+}
+
+#[test]
+fn should_return_api_error_code() {
+    // Arrange
+    let _mock = mock("GET", "/public/getticker?market=BTC-ABC")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : false,
+            "message" : "INVALID_MARKET",
+            "result" : null
+        }"#)
+        .create();
+    let bittrex_client = BittrexClient::builder().api_url(mockito::server_url()).build().unwrap();
+
+    // Act
+    let error = bittrex_client.get_ticker(&"BTC-ABC".parse().unwrap()).err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::Api(ApiErrorCode::InvalidMarket));
+    assert_eq!(error.message, "INVALID_MARKET");
+}
+
+#[test]
+fn should_return_http_status_error() {
+    // Arrange
+    let _mock = mock("GET", "/public/getcurrencies")
+        .with_status(403)
+        .with_body("<html>Attention Required!</html>")
+        .create();
+    let bittrex_client = BittrexClient::builder().api_url(mockito::server_url()).build().unwrap();
+
+    // Act
+    let error = bittrex_client.get_currencies().err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::HttpStatus(403));
+    assert!(!error.is_retryable());
+}
+
+#[test]
+fn should_return_decoding_error() {
+    // Arrange
+    let _mock = mock("GET", "/public/getmarketsummaries")
+        .with_status(200)
+        .with_body("not json")
+        .create();
+    let bittrex_client = BittrexClient::builder().api_url(mockito::server_url()).build().unwrap();
+
+    // Act
+    let error = bittrex_client.get_market_summaries().err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::Decoding);
+}
+
+#[test]
+fn should_return_transport_error() {
+    // Arrange
+    let bittrex_client = BittrexClient::builder().api_url("http://127.0.0.1:1".to_string()).build().unwrap();
+
+    // Act
+    let error = bittrex_client.get_markets().err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::Transport);
+    assert!(error.is_retryable());
+}
+
+#[test]
+fn should_keep_status_url_and_body_of_undecodable_response() {
+    // Arrange
+    let _mock = mock("GET", Matcher::Regex(r"^/account/getbalances\?apikey=(.*)$".to_string()))
+        .with_status(200)
+        .with_body(format!("<html>{}</html>", "Maintenance ".repeat(200)))
+        .create();
+    let bittrex_client = BittrexClient::new_override_api_url("SECRETKEY".to_string(), "SECRET".to_string(), mockito::server_url());
+
+    // Act
+    let error = bittrex_client.get_balances().err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::Decoding);
+    assert_eq!(error.status(), Some(200));
+    assert!(error.url().unwrap().contains("/account/getbalances?apikey=REDACTED&nonce="));
+    assert!(error.body().unwrap().starts_with("<html>Maintenance "));
+    assert_eq!(error.body().unwrap().chars().count(), 1024 + 3);
+    assert!(error.source().is_some());
+    assert!(!error.to_string().contains("SECRETKEY"));
+}
+
+#[test]
+fn should_keep_body_of_http_error() {
+    // Arrange
+    let _mock = mock("GET", "/public/getmarkets")
+        .with_status(503)
+        .with_body("<html>Checking your browser</html>")
+        .create();
+    let bittrex_client = BittrexClient::builder().api_url(mockito::server_url()).build().unwrap();
+
+    // Act
+    let error = bittrex_client.get_markets().err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::HttpStatus(503));
+    assert_eq!(error.status(), Some(503));
+    assert_eq!(error.body(), Some("<html>Checking your browser</html>"));
+}
+
+#[test]
+fn should_redact_api_key_of_transport_error() {
+    // Arrange
+    let bittrex_client = BittrexClient::new_override_api_url("SECRETKEY".to_string(), "SECRET".to_string(), "http://127.0.0.1:1".to_string());
+
+    // Act
+    let error = bittrex_client.get_open_orders().err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::Transport);
+    assert_eq!(error.status(), None);
+    assert!(error.url().unwrap().contains("apikey=REDACTED"));
+    assert!(!error.to_string().contains("SECRETKEY"));
+    assert!(!error.source().unwrap().to_string().contains("SECRETKEY"));
+}
+
+#[test]
+fn should_redact_api_key_in_urls() {
+    // Assert
+    assert_eq!(error::redact_url("https://bittrex.com/api/v1.1/account/getbalances?&apikey=KEY&nonce=1"), "https://bittrex.com/api/v1.1/account/getbalances?&apikey=REDACTED&nonce=1");
+    assert_eq!(error::redact_url("/account/getbalance?currency=BTC&apikey=KEY"), "/account/getbalance?currency=BTC&apikey=REDACTED");
+    assert_eq!(error::redact_url("/public/getmarkets"), "/public/getmarkets");
+}
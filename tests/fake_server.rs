@@ -0,0 +1,175 @@
+#![cfg(feature = "fake-server")]
+extern crate reqwest;
+extern crate bittrex_api;
+
+use bittrex_api::{BittrexClient, Currency, Decimal};
+use bittrex_api::error::{ApiErrorCode, BittrexError, BittrexErrorType};
+use bittrex_api::fake_server::FakeBittrex;
+use bittrex_api::transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport};
+use bittrex_api::values::{BittrexOrderKind, BittrexOrderType};
+
+fn fake_bittrex() -> FakeBittrex {
+    let fake_bittrex = FakeBittrex::start().unwrap();
+    fake_bittrex.add_market(&"BTC-LTC".parse().unwrap(), Decimal::new(1, 2));
+    fake_bittrex.add_account("MAKER", "MAKERSECRET");
+    fake_bittrex.add_account("TAKER", "TAKERSECRET");
+    fake_bittrex.deposit("MAKER", &Currency::new("LTC").unwrap(), Decimal::new(10, 0));
+    fake_bittrex.deposit("TAKER", &Currency::new("BTC").unwrap(), Decimal::new(1, 0));
+    fake_bittrex
+}
+
+fn client(fake_bittrex: &FakeBittrex, api_key: &str, api_secret: &str) -> BittrexClient {
+    BittrexClient::new_override_api_url(api_key.to_string(), api_secret.to_string(), fake_bittrex.api_url())
+}
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+#[test]
+fn should_match_orders_and_settle_balances_successfully() {
+    // Arrange
+    let fake_bittrex = fake_bittrex();
+    let maker = client(&fake_bittrex, "MAKER", "MAKERSECRET");
+    let taker = client(&fake_bittrex, "TAKER", "TAKERSECRET");
+    let market = "BTC-LTC".parse().unwrap();
+
+    // Act
+    let sell = maker.sell_limit(&market, decimal("10"), decimal("0.01")).unwrap();
+    let buy = taker.buy_limit(&market, decimal("4"), decimal("0.011")).unwrap();
+
+    // Assert
+    let sell_order = maker.get_order(&sell.uuid).unwrap();
+    assert!(sell_order.is_open);
+    assert_eq!(sell_order.quantity_remaining, decimal("6"));
+    assert_eq!(sell_order.price, decimal("0.04"));
+    assert_eq!(sell_order.comission_paid, decimal("0.0001"));
+
+    let buy_order = taker.get_order(&buy.uuid).unwrap();
+    assert!(!buy_order.is_open);
+    assert_eq!(buy_order.order_type, BittrexOrderKind::LimitBuy);
+    assert_eq!(buy_order.price_per_unit, Some(decimal("0.01")));
+
+    let taker_btc = taker.get_balance(&Currency::new("BTC").unwrap()).unwrap();
+    assert_eq!(taker_btc.balance, decimal("0.9599"));
+    assert_eq!(taker_btc.available, decimal("0.9599"));
+    assert_eq!(taker.get_balance(&Currency::new("LTC").unwrap()).unwrap().balance, decimal("4"));
+
+    let maker_ltc = maker.get_balance(&Currency::new("LTC").unwrap()).unwrap();
+    assert_eq!(maker_ltc.balance, decimal("6"));
+    assert_eq!(maker_ltc.available, decimal("0"));
+    assert_eq!(maker.get_balance(&Currency::new("BTC").unwrap()).unwrap().balance, decimal("0.0399"));
+
+    let order_book = taker.get_order_book(&market, BittrexOrderType::Both).unwrap();
+    assert!(order_book.buy.is_empty());
+    assert_eq!(order_book.sell.len(), 1);
+    assert_eq!(order_book.sell[0].quantity, decimal("6"));
+    assert_eq!(taker.get_ticker(&market).unwrap().last, decimal("0.01"));
+    assert_eq!(taker.get_market_history(&market).unwrap().len(), 1);
+    assert_eq!(maker.get_open_orders().unwrap().len(), 1);
+    assert!(taker.get_open_orders().unwrap().is_empty());
+}
+
+#[test]
+fn should_release_reservation_on_cancel_successfully() {
+    // Arrange
+    let fake_bittrex = fake_bittrex();
+    let taker = client(&fake_bittrex, "TAKER", "TAKERSECRET");
+    let market = "BTC-LTC".parse().unwrap();
+    let buy = taker.buy_limit(&market, decimal("10"), decimal("0.05")).unwrap();
+    let reserved = fake_bittrex.balance("TAKER", &Currency::new("BTC").unwrap());
+
+    // Act
+    taker.cancel_order(&buy.uuid).unwrap();
+    let cancel_again = taker.cancel_order(&buy.uuid);
+
+    // Assert
+    assert_eq!(reserved.available, decimal("0.49875"));
+    assert_eq!(fake_bittrex.balance("TAKER", &Currency::new("BTC").unwrap()).available, decimal("1"));
+    assert_eq!(cancel_again.err().unwrap().api_code(), Some(&ApiErrorCode::OrderNotOpen));
+    assert!(taker.get_open_orders().unwrap().is_empty());
+    assert_eq!(taker.get_order_history().unwrap().len(), 1);
+}
+
+#[test]
+fn should_reject_invalid_orders_and_withdrawals() {
+    // Arrange
+    let fake_bittrex = fake_bittrex();
+    let taker = client(&fake_bittrex, "TAKER", "TAKERSECRET");
+    let market = "BTC-LTC".parse().unwrap();
+    let btc = Currency::new("BTC").unwrap();
+
+    // Act
+    let too_expensive = taker.buy_limit(&market, decimal("100"), decimal("0.05"));
+    let too_small = taker.buy_limit(&market, decimal("0.001"), decimal("0.05"));
+    let unknown_market = taker.buy_limit(&"BTC-XYZ".parse().unwrap(), decimal("1"), decimal("0.05"));
+    let withdraw = taker.withdraw(&btc, decimal("0.25"), "1BitcoinAddress", "");
+    let overdrawn = taker.withdraw(&btc, decimal("1"), "1BitcoinAddress", "");
+
+    // Assert
+    assert_eq!(too_expensive.err().unwrap().api_code(), Some(&ApiErrorCode::InsufficientFunds));
+    assert_eq!(too_small.err().unwrap().api_code(), Some(&ApiErrorCode::MinTradeRequirementNotMet));
+    assert_eq!(unknown_market.err().unwrap().api_code(), Some(&ApiErrorCode::InvalidMarket));
+    assert!(withdraw.is_ok());
+    assert_eq!(overdrawn.err().unwrap().api_code(), Some(&ApiErrorCode::InsufficientFunds));
+    assert_eq!(fake_bittrex.balance("TAKER", &btc).balance, decimal("0.75"));
+}
+
+#[test]
+fn should_reject_invalid_signature() {
+    // Arrange
+    let fake_bittrex = fake_bittrex();
+    let bittrex_client = client(&fake_bittrex, "TAKER", "WRONGSECRET");
+    let unknown_client = client(&fake_bittrex, "UNKNOWN", "TAKERSECRET");
+
+    // Act
+    let balances = bittrex_client.get_balances();
+    let unknown_balances = unknown_client.get_balances();
+
+    // Assert
+    assert_eq!(balances.err().unwrap().error_type, BittrexErrorType::Api(ApiErrorCode::InvalidSignature));
+    assert_eq!(unknown_balances.err().unwrap().error_type, BittrexErrorType::Api(ApiErrorCode::ApikeyInvalid));
+}
+
+/// Sends every request twice, as a replaying attacker would, and returns the second response.
+struct SendTwice(ReqwestTransport);
+
+impl HttpTransport for SendTwice {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, BittrexError> {
+        self.0.send(request)?;
+        self.0.send(request)
+    }
+}
+
+#[test]
+fn should_reject_reused_nonce() {
+    // Arrange
+    let fake_bittrex = fake_bittrex();
+    let bittrex_client = BittrexClient::builder()
+        .credentials("TAKER".to_string(), "TAKERSECRET".to_string())
+        .api_url(fake_bittrex.api_url())
+        .transport(SendTwice(ReqwestTransport::new(reqwest::Client::new())))
+        .build()
+        .unwrap();
+
+    // Act
+    let balances = bittrex_client.get_balances();
+
+    // Assert
+    assert_eq!(balances.err().unwrap().error_type, BittrexErrorType::Api(ApiErrorCode::NonceUsed));
+}
+
+#[test]
+fn should_sign_and_send_special_characters_in_parameters_successfully() {
+    // Arrange
+    let fake_bittrex = fake_bittrex();
+    let taker = client(&fake_bittrex, "TAKER", "TAKERSECRET");
+    let btc = Currency::new("BTC").unwrap();
+
+    // Act
+    let withdraw = taker.withdraw(&btc, decimal("0.25"), "1Bitcoin Address&quantity=0.5#", "memo+1/2=?");
+
+    // Assert
+    assert!(withdraw.is_ok());
+    assert_eq!(fake_bittrex.balance("TAKER", &btc).balance, decimal("0.75"));
+}
@@ -0,0 +1,70 @@
+extern crate serde_json;
+extern crate bittrex_api;
+
+use bittrex_api::{Currency, Market};
+use bittrex_api::market::ParseMarketError;
+
+#[test]
+fn should_parse_market_successfully() {
+    // Act
+    let market: Market = "btc-ltc".parse().unwrap();
+
+    // Assert
+    assert_eq!(market.base, Currency::new("BTC").unwrap());
+    assert_eq!(market.quote, "LTC");
+    assert_eq!(market.to_string(), "BTC-LTC");
+}
+
+#[test]
+fn should_reject_invalid_markets() {
+    // Assert
+    assert_eq!("BTCLTC".parse::<Market>(), Err(ParseMarketError::InvalidMarket("BTCLTC".to_string())));
+    assert_eq!("BTC-LTC-ETH".parse::<Market>(), Err(ParseMarketError::InvalidMarket("BTC-LTC-ETH".to_string())));
+    assert_eq!("BTC-".parse::<Market>(), Err(ParseMarketError::InvalidMarket("BTC-".to_string())));
+    assert_eq!("BTC-LTC&type=x".parse::<Market>(), Err(ParseMarketError::InvalidMarket("BTC-LTC&type=x".to_string())));
+}
+
+#[test]
+fn should_reject_invalid_currencies() {
+    // Assert
+    assert_eq!(Currency::new(""), Err(ParseMarketError::InvalidCurrency("".to_string())));
+    assert_eq!(Currency::new("BT C"), Err(ParseMarketError::InvalidCurrency("BT C".to_string())));
+    assert_eq!(Currency::new("1st").unwrap().as_str(), "1ST");
+}
+
+#[test]
+fn should_serialize_market_as_string() {
+    // Arrange
+    let market = Market::new(Currency::new("BTC").unwrap(), Currency::new("DOGE").unwrap());
+
+    // Act
+    let json = serde_json::to_string(&market).unwrap();
+
+    // Assert
+    assert_eq!(json, r#""BTC-DOGE""#);
+    assert_eq!(serde_json::from_str::<Market>(&json).unwrap(), market);
+    assert!(serde_json::from_str::<Market>(r#""BTCDOGE""#).is_err());
+}
+
+#[test]
+fn should_deserialize_unusual_currency_codes_successfully() {
+    // Act
+    let currency: Currency = serde_json::from_str(r#""usdt.e""#).unwrap();
+    let market: Market = serde_json::from_str(r#""USDT-btc_2""#).unwrap();
+
+    // Assert
+    assert_eq!(currency.as_str(), "USDT.E");
+    assert_eq!("USDT.E".parse::<Currency>().unwrap(), currency);
+    assert_eq!(market.base.as_str(), "USDT");
+    assert_eq!(market.quote.as_str(), "BTC_2");
+}
+
+#[test]
+fn should_reject_invalid_codes_in_responses() {
+    // Assert
+    assert!(serde_json::from_str::<Currency>(r#""""#).is_err());
+    assert!(serde_json::from_str::<Currency>(r#""A&B""#).is_err());
+    assert!(serde_json::from_str::<Market>(r#""BTC-""#).is_err());
+    assert!(serde_json::from_str::<Market>(r#""A&B-C D""#).is_err());
+    assert!(serde_json::from_str::<Market>(r#""BTC-LTC-DOGE""#).is_err());
+}
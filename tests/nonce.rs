@@ -0,0 +1,121 @@
+extern crate mockito;
+extern crate bittrex_api;
+
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+
+use mockito::{mock, Matcher};
+
+use bittrex_api::BittrexClient;
+use bittrex_api::error::{BittrexError, BittrexErrorType};
+use bittrex_api::nonce::{FileNonce, MonotonicNonce, NonceSource, SharedFileNonce};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("bittrex-api-nonce-{}-{}", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+/// Takes `count` nonces on each of 8 threads and checks they increase on each thread and are unique over all threads.
+fn assert_strictly_increasing<F>(count: usize, nonce_source: F) where F: Fn() -> Arc<dyn NonceSource> {
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let nonce_source = nonce_source();
+            thread::spawn(move || (0..count).map(|_| nonce_source.next_nonce().unwrap()).collect::<Vec<u64>>())
+        })
+        .collect();
+
+    let mut all = HashSet::new();
+    for thread in threads {
+        let nonces = thread.join().unwrap();
+        assert!(nonces.windows(2).all(|pair| pair[0] < pair[1]));
+        all.extend(nonces);
+    }
+    assert_eq!(all.len(), 8 * count);
+}
+
+#[test]
+fn should_generate_strictly_increasing_nonces_across_threads_successfully() {
+    // Arrange
+    let nonce_source: Arc<dyn NonceSource> = Arc::new(MonotonicNonce::new());
+
+    // Act & Assert
+    assert_strictly_increasing(1000, || nonce_source.clone());
+}
+
+#[test]
+fn should_continue_after_persisted_nonce_successfully() {
+    // Arrange
+    let path = temp_path("file");
+    let far_future = u64::max_value() / 2;
+    fs::write(&path, far_future.to_string()).unwrap();
+
+    // Act
+    let first = FileNonce::new(&path).next_nonce().unwrap();
+    let after_restart = FileNonce::new(&path).next_nonce().unwrap();
+
+    // Assert
+    assert_eq!(first, far_future + 1);
+    assert_eq!(after_restart, far_future + 2);
+    assert_eq!(fs::read_to_string(&path).unwrap(), after_restart.to_string());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn should_coordinate_nonces_through_shared_file_successfully() {
+    // Arrange
+    let path = temp_path("shared");
+
+    // Act & Assert
+    assert_strictly_increasing(100, || Arc::new(SharedFileNonce::new(&path)));
+    let last: u64 = fs::read_to_string(&path).unwrap().parse().unwrap();
+    assert!(SharedFileNonce::new(&path).next_nonce().unwrap() > last);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn should_reject_corrupt_nonce_file() {
+    // Arrange
+    let path = temp_path("corrupt");
+    fs::write(&path, "not a nonce").unwrap();
+
+    // Act
+    let nonce = FileNonce::new(&path).next_nonce();
+
+    // Assert
+    assert_eq!(nonce.err().unwrap().error_type, BittrexErrorType::Configuration);
+    fs::remove_file(&path).unwrap();
+}
+
+struct FixedNonce(u64);
+
+impl NonceSource for FixedNonce {
+    fn next_nonce(&self) -> Result<u64, BittrexError> {
+        Ok(self.0)
+    }
+}
+
+#[test]
+fn should_sign_calls_with_configured_nonce_source_successfully() {
+    // Arrange
+    let _mock = mock("GET", Matcher::Regex(r"^/account/getbalances\?apikey=KEY&nonce=42$".to_string()))
+        .with_status(200)
+        .with_body(r#"{"success":true,"message":"","result":[]}"#)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url(mockito::server_url())
+        .nonce_source(FixedNonce(42))
+        .build()
+        .unwrap();
+
+    // Act
+    let balances = bittrex_client.get_balances();
+
+    // Assert
+    assert!(balances.unwrap().is_empty());
+}
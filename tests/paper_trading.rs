@@ -0,0 +1,208 @@
+extern crate mockito;
+extern crate bittrex_api;
+
+use mockito::mock;
+
+use bittrex_api::{BittrexClient, Currency, Decimal, Market, PaperTradingClient};
+use bittrex_api::error::{ApiErrorCode, BittrexErrorType};
+use bittrex_api::values::{BittrexPublicOrder, BittrexPublicOrderBook};
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+fn level(quantity: &str, rate: &str) -> BittrexPublicOrder {
+    BittrexPublicOrder { quantity: decimal(quantity), rate: decimal(rate) }
+}
+
+fn paper_client(btc: &str, ltc: &str) -> PaperTradingClient {
+    let paper_client = PaperTradingClient::offline();
+    paper_client.deposit(&Currency::new("BTC").unwrap(), decimal(btc));
+    paper_client.deposit(&Currency::new("LTC").unwrap(), decimal(ltc));
+    paper_client.set_order_book(&"BTC-LTC".parse().unwrap(), BittrexPublicOrderBook {
+        buy: vec![level("5", "0.009"), level("5", "0.0095")],
+        sell: vec![level("3", "0.011"), level("2", "0.01")],
+    });
+    paper_client
+}
+
+#[test]
+fn should_fill_buy_order_against_supplied_order_book_successfully() {
+    // Arrange
+    let paper_client = paper_client("1", "0");
+    let market: Market = "BTC-LTC".parse().unwrap();
+
+    // Act
+    let buy = paper_client.buy_limit(&market, decimal("4"), decimal("0.011")).unwrap();
+
+    // Assert
+    let order = paper_client.get_order(&buy.uuid).unwrap();
+    assert!(!order.is_open);
+    assert_eq!(order.price, decimal("0.042"));
+    assert_eq!(order.comission_paid, decimal("0.000105"));
+    assert_eq!(order.price_per_unit, Some(decimal("0.0105")));
+
+    let btc = paper_client.get_balance(&Currency::new("BTC").unwrap()).unwrap();
+    assert_eq!(btc.balance, decimal("0.957895"));
+    assert_eq!(btc.available, decimal("0.957895"));
+    assert_eq!(paper_client.get_balance(&Currency::new("LTC").unwrap()).unwrap().balance, decimal("4"));
+    assert_eq!(paper_client.get_order_history().unwrap().len(), 1);
+}
+
+#[test]
+fn should_rest_partially_filled_order_until_refresh_successfully() {
+    // Arrange
+    let paper_client = paper_client("0", "10");
+    let market: Market = "BTC-LTC".parse().unwrap();
+    let sell = paper_client.sell_limit(&market, decimal("8"), decimal("0.0092")).unwrap();
+    let partially_filled = paper_client.get_order(&sell.uuid).unwrap();
+    paper_client.set_order_book(&market, BittrexPublicOrderBook { buy: vec![level("10", "0.0093")], sell: vec![] });
+
+    // Act
+    paper_client.refresh().unwrap();
+
+    // Assert
+    assert!(partially_filled.is_open);
+    assert_eq!(partially_filled.quantity_remaining, decimal("3"));
+
+    let order = paper_client.get_order(&sell.uuid).unwrap();
+    assert!(!order.is_open);
+    assert_eq!(order.price, decimal("0.0754"));
+    assert!(paper_client.get_open_orders().unwrap().is_empty());
+
+    let ltc = paper_client.get_balance(&Currency::new("LTC").unwrap()).unwrap();
+    assert_eq!(ltc.balance, decimal("2"));
+    assert_eq!(ltc.available, decimal("2"));
+    assert_eq!(paper_client.get_balance(&Currency::new("BTC").unwrap()).unwrap().balance, decimal("0.0752115"));
+}
+
+#[test]
+fn should_release_reservation_on_cancel_successfully() {
+    // Arrange
+    let paper_client = paper_client("1", "0");
+    let market: Market = "BTC-LTC".parse().unwrap();
+    let buy = paper_client.buy_limit(&market, decimal("10"), decimal("0.005")).unwrap();
+    let reserved = paper_client.get_balance(&Currency::new("BTC").unwrap()).unwrap();
+
+    // Act
+    paper_client.cancel_order(&buy.uuid).unwrap();
+    let cancel_again = paper_client.cancel_order(&buy.uuid);
+
+    // Assert
+    assert_eq!(reserved.available, decimal("0.949875"));
+    assert_eq!(paper_client.get_balance(&Currency::new("BTC").unwrap()).unwrap().available, decimal("1"));
+    assert_eq!(cancel_again.err().unwrap().api_code(), Some(&ApiErrorCode::OrderNotOpen));
+    assert!(paper_client.get_order(&buy.uuid).unwrap().cancel_initiated);
+}
+
+#[test]
+fn should_keep_balances_consistent_over_partial_fills_successfully() {
+    // Arrange
+    let paper_client = paper_client("1", "0");
+    let market: Market = "BTC-LTC".parse().unwrap();
+    paper_client.set_order_book(&market, BittrexPublicOrderBook {
+        buy: vec![],
+        sell: vec![level("1", "0.000202"), level("1", "0.000202"), level("1", "0.000202")],
+    });
+
+    // Act
+    let buy = paper_client.buy_limit(&market, decimal("3"), decimal("0.000202")).unwrap();
+
+    // Assert
+    let order = paper_client.get_order(&buy.uuid).unwrap();
+    assert!(!order.is_open);
+    assert_eq!(order.commission_reserved, decimal("0.00000152"));
+    assert_eq!(order.comission_paid, decimal("0.00000152"));
+
+    let btc = paper_client.get_balance(&Currency::new("BTC").unwrap()).unwrap();
+    assert_eq!(btc.balance, decimal("0.99939248"));
+    assert_eq!(btc.available, btc.balance);
+}
+
+#[test]
+fn should_charge_partial_fills_the_commission_of_a_single_fill_successfully() {
+    // Arrange
+    let market: Market = "BTC-LTC".parse().unwrap();
+    let partial_client = paper_client("1", "0");
+    partial_client.set_order_book(&market, BittrexPublicOrderBook {
+        buy: vec![],
+        sell: vec![level("1", "0.000201"), level("1", "0.000202"), level("1", "0.000203")],
+    });
+    let single_client = paper_client("1", "0");
+    single_client.set_order_book(&market, BittrexPublicOrderBook {
+        buy: vec![],
+        sell: vec![level("3", "0.000202")],
+    });
+
+    // Act
+    let partial = partial_client.buy_limit(&market, decimal("3"), decimal("0.000203")).unwrap();
+    let single = single_client.buy_limit(&market, decimal("3"), decimal("0.000203")).unwrap();
+
+    // Assert
+    let partial = partial_client.get_order(&partial.uuid).unwrap();
+    let single = single_client.get_order(&single.uuid).unwrap();
+    assert_eq!(partial.price, decimal("0.000606"));
+    assert_eq!(single.price, partial.price);
+    assert_eq!(partial.comission_paid, decimal("0.00000152"));
+    assert_eq!(single.comission_paid, partial.comission_paid);
+    assert_eq!(
+        partial_client.get_balance(&Currency::new("BTC").unwrap()).unwrap().balance,
+        single_client.get_balance(&Currency::new("BTC").unwrap()).unwrap().balance
+    );
+}
+
+#[test]
+fn should_reject_invalid_orders() {
+    // Arrange
+    let paper_client = paper_client("1", "0");
+    let market: Market = "BTC-LTC".parse().unwrap();
+
+    // Act
+    let too_expensive = paper_client.buy_limit(&market, decimal("100"), decimal("0.011"));
+    let dust = paper_client.buy_limit(&market, decimal("0.001"), decimal("0.011"));
+    let without_order_book = paper_client.buy_limit(&"BTC-NEO".parse().unwrap(), decimal("1"), decimal("0.01"));
+    let unknown_order = paper_client.cancel_order("UNKNOWN");
+
+    // Assert
+    assert_eq!(too_expensive.err().unwrap().api_code(), Some(&ApiErrorCode::InsufficientFunds));
+    assert_eq!(dust.err().unwrap().api_code(), Some(&ApiErrorCode::DustTradeDisallowed));
+    assert_eq!(without_order_book.err().unwrap().error_type, BittrexErrorType::Configuration);
+    assert_eq!(unknown_order.err().unwrap().api_code(), Some(&ApiErrorCode::InvalidOrder));
+    assert!(paper_client.get_open_orders().unwrap().is_empty());
+}
+
+#[test]
+fn should_fill_against_fetched_order_book_successfully() {
+    // Arrange
+    let _mock = mock("GET", "/public/getorderbook?market=BTC-LTC&type=Both")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : {
+                "buy" : [{
+                        "Quantity" : 12.37000000,
+                        "Rate" : 0.02525000
+                    }
+                ],
+                "sell" : [{
+                        "Quantity" : 32.55412402,
+                        "Rate" : 0.02540000
+                    }
+                ]
+            }
+        }"#)
+        .create();
+    let market_data = BittrexClient::builder().api_url(mockito::server_url()).build().unwrap();
+    let paper_client = PaperTradingClient::new(market_data);
+    paper_client.deposit(&Currency::new("LTC").unwrap(), decimal("20"));
+
+    // Act
+    let sell = paper_client.sell_limit(&"BTC-LTC".parse().unwrap(), decimal("20"), decimal("0.025")).unwrap();
+
+    // Assert
+    let order = paper_client.get_order(&sell.uuid).unwrap();
+    assert!(order.is_open);
+    assert_eq!(order.quantity_remaining, decimal("7.63"));
+    assert_eq!(paper_client.get_balance(&Currency::new("BTC").unwrap()).unwrap().balance, decimal("0.31156164"));
+}
@@ -0,0 +1,132 @@
+extern crate mockito;
+extern crate bittrex_api;
+
+use mockito::mock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bittrex_api::BittrexClient;
+use bittrex_api::error::BittrexErrorType;
+use bittrex_api::rate_limit::{EndpointClass, RateLimit, RateLimiter, RateLimitMode};
+
+#[test]
+fn should_allow_burst_and_fail_fast_afterwards() {
+    // Arrange
+    let rate_limiter = RateLimiter::new()
+        .limit(EndpointClass::Public, RateLimit::per_minute(3))
+        .mode(RateLimitMode::FailFast);
+
+    // Act
+    let results: Vec<bool> = (0..4).map(|_| rate_limiter.acquire(EndpointClass::Public).is_ok()).collect();
+    let error = rate_limiter.acquire(EndpointClass::Public).err().unwrap();
+
+    // Assert
+    assert_eq!(results, vec![true, true, true, false]);
+    assert_eq!(error.error_type, BittrexErrorType::RateLimited);
+}
+
+#[test]
+fn should_limit_endpoint_classes_separately() {
+    // Arrange
+    let rate_limiter = RateLimiter::new()
+        .limit(EndpointClass::Private, RateLimit::per_minute(1))
+        .mode(RateLimitMode::FailFast);
+
+    // Act
+    let private_results: Vec<bool> = (0..2).map(|_| rate_limiter.acquire(EndpointClass::Private).is_ok()).collect();
+    let public_results: Vec<bool> = (0..100).map(|_| rate_limiter.acquire(EndpointClass::Public).is_ok()).collect();
+
+    // Assert
+    assert_eq!(private_results, vec![true, false]);
+    assert!(public_results.iter().all(|result| *result));
+}
+
+#[test]
+fn should_queue_blocked_requests() {
+    // Arrange
+    let rate_limiter = RateLimiter::new()
+        .limit(EndpointClass::Public, RateLimit::per_second(10));
+
+    // Act
+    let delays: Vec<Duration> = (0..12).map(|_| rate_limiter.acquire(EndpointClass::Public).unwrap()).collect();
+
+    // Assert
+    assert!(delays[..10].iter().all(|delay| *delay == Duration::from_secs(0)));
+    assert!(delays[10] > Duration::from_millis(50) && delays[10] <= Duration::from_millis(100));
+    assert!(delays[11] > Duration::from_millis(150) && delays[11] <= Duration::from_millis(200));
+}
+
+#[test]
+fn should_share_rate_limit_across_clones_and_threads() {
+    // Arrange
+    let rate_limiter = RateLimiter::new()
+        .limit(EndpointClass::Private, RateLimit::per_minute(20))
+        .mode(RateLimitMode::FailFast);
+
+    // Act
+    let handles: Vec<_> = (0..4).map(|_| {
+        let rate_limiter = rate_limiter.clone();
+        thread::spawn(move || (0..10).filter(|_| rate_limiter.acquire(EndpointClass::Private).is_ok()).count())
+    }).collect();
+    let acquired: usize = handles.into_iter().map(|handle| handle.join().unwrap()).sum();
+
+    // Assert
+    assert_eq!(acquired, 20);
+}
+
+#[test]
+fn should_fail_fast_on_client_clones() {
+    // Arrange
+    let _mock = mock("GET", "/public/getmarketsummaries")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : []
+        }"#)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .api_url(mockito::server_url())
+        .rate_limiter(RateLimiter::new()
+            .limit(EndpointClass::Public, RateLimit::per_minute(1))
+            .mode(RateLimitMode::FailFast))
+        .build()
+        .unwrap();
+    let cloned_client = bittrex_client.clone();
+
+    // Act
+    let summaries = bittrex_client.get_market_summaries();
+    let cloned_summaries = cloned_client.get_market_summaries();
+
+    // Assert
+    assert!(summaries.is_ok());
+    assert_eq!(cloned_summaries.err().unwrap().error_type, BittrexErrorType::RateLimited);
+}
+
+#[test]
+fn should_block_client_until_slot_is_free() {
+    // Arrange
+    let _mock = mock("GET", "/public/getmarketsummaries")
+        .with_status(200)
+        .with_body(r#"{
+            "success" : true,
+            "message" : "",
+            "result" : []
+        }"#)
+        .create();
+    let bittrex_client = BittrexClient::builder()
+        .api_url(mockito::server_url())
+        .rate_limiter(RateLimiter::new().limit(EndpointClass::Public, RateLimit::per_second(4)))
+        .build()
+        .unwrap();
+
+    // Act
+    let started = Instant::now();
+    for _ in 0..6 {
+        bittrex_client.get_market_summaries().unwrap();
+    }
+    let duration = started.elapsed();
+
+    // Assert
+    assert!(duration >= Duration::from_millis(450));
+}
@@ -0,0 +1,63 @@
+extern crate bittrex_api;
+
+use std::time::Duration;
+
+use bittrex_api::RetryPolicy;
+
+#[test]
+fn should_double_delay_up_to_max_delay() {
+    // Arrange
+    let retry_policy = RetryPolicy::new(5, Duration::from_millis(100))
+        .max_delay(Duration::from_millis(500))
+        .jitter(false);
+
+    // Act
+    let delays: Vec<Duration> = (0..5).map(|retry| retry_policy.delay(retry)).collect();
+
+    // Assert
+    assert_eq!(delays, vec![
+        Duration::from_millis(100),
+        Duration::from_millis(200),
+        Duration::from_millis(400),
+        Duration::from_millis(500),
+        Duration::from_millis(500)
+    ]);
+}
+
+#[test]
+fn should_keep_jittered_delay_within_bounds() {
+    // Arrange
+    let retry_policy = RetryPolicy::new(3, Duration::from_millis(100));
+
+    // Act
+    let delays: Vec<Duration> = (0..100).map(|_| retry_policy.delay(2)).collect();
+
+    // Assert
+    assert!(delays.iter().all(|delay| *delay >= Duration::from_millis(200) && *delay <= Duration::from_millis(400)));
+}
+
+#[test]
+fn should_not_overflow_for_many_retries() {
+    // Arrange
+    let retry_policy = RetryPolicy::new(100, Duration::from_secs(1)).jitter(false);
+
+    // Act
+    let delay = retry_policy.delay(99);
+
+    // Assert
+    assert_eq!(delay, Duration::from_secs(30));
+}
+
+#[test]
+fn should_not_retry_trading_calls_by_default() {
+    // Arrange
+    let retry_policy = RetryPolicy::new(3, Duration::from_millis(100));
+
+    // Act
+    let opted_in = retry_policy.clone().retry_trading(true);
+
+    // Assert
+    assert!(!retry_policy.retry_trading);
+    assert!(opted_in.retry_trading);
+    assert_eq!(RetryPolicy::default().max_retries, 0);
+}
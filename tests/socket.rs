@@ -0,0 +1,448 @@
+extern crate base64;
+extern crate flate2;
+extern crate serde_json;
+extern crate sha_1 as sha1;
+extern crate bittrex_api;
+
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use flate2::Compression;
+use flate2::write::DeflateEncoder;
+use sha1::{Digest, Sha1};
+
+use bittrex_api::{datetime, BittrexClient, Decimal};
+use bittrex_api::error::BittrexErrorType;
+use bittrex_api::rate_limit::{EndpointClass, RateLimit, RateLimitMode, RateLimiter};
+use bittrex_api::socket::{DeltaType, SocketClient, SocketEvent};
+use bittrex_api::values::BittrexOrderSide;
+
+const EXCHANGE_STATE: &str = r#"{"M":null,"N":100,"Z":[{"Q":5.5,"R":0.0129}],"S":[{"Q":2.0,"R":0.0131}],"f":[{"I":42,"T":1577872800000,"Q":1.0,"P":0.013,"t":0.013,"F":"FILL","OT":"BUY","U":"b5e2ae2a-0b0c-4a2f-8b1a-3bb8e1c5f6a0"}]}"#;
+const EXCHANGE_DELTA: &str = r#"{"M":"BTC-LTC","N":101,"Z":[{"TY":2,"R":0.0129,"Q":4.5}],"S":[{"TY":1,"R":0.0131,"Q":0.0}],"f":[{"FI":43,"OI":"d3c0f8c2-6c43-4d9a-9a4f-4e0c2b1a7e55","OT":"BUY","R":0.0131,"Q":2.0,"T":1577872801000}]}"#;
+const SUMMARY_DELTAS: &str = r#"{"N":7,"D":[{"M":"BTC-LTC","H":0.0135,"L":0.0128,"V":1000.5,"l":0.0131,"m":13.2,"T":1577872801000,"B":0.0129,"A":0.0132,"G":10,"g":20,"PD":0.0127,"x":1506668518873}]}"#;
+
+/// Local stand-in for the SignalR endpoint: answers negotiate and start, accepts the websocket, answers the
+/// invocations of the client and pushes one exchange delta (and one summary delta, if subscribed) after the exchange state.
+/// Subscriptions to `BTC-XYZ` are rejected. The `User-Agent` headers of all requests are recorded.
+struct StandIn {
+    url: String,
+    websockets: Arc<AtomicUsize>,
+    pong: Arc<AtomicBool>,
+    user_agents: Arc<Mutex<Vec<String>>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Serve,
+    /// The first websocket is closed by the stand-in after the pushed messages.
+    DropFirst,
+    /// The websocket upgrade is answered with a wrong `Sec-WebSocket-Accept`.
+    BadAccept,
+    /// The negotiate response ends before the announced content length.
+    TruncatedNegotiate,
+}
+
+impl StandIn {
+    fn start(mode: Mode) -> StandIn {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/signalr", listener.local_addr().unwrap());
+        let websockets = Arc::new(AtomicUsize::new(0));
+        let pong = Arc::new(AtomicBool::new(false));
+        let user_agents = Arc::new(Mutex::new(Vec::new()));
+
+        let (thread_websockets, thread_pong, thread_user_agents) = (websockets.clone(), pong.clone(), user_agents.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let (websockets, pong, user_agents) = (thread_websockets.clone(), thread_pong.clone(), thread_user_agents.clone());
+                thread::spawn(move || serve(stream.unwrap(), mode, &websockets, &pong, &user_agents));
+            }
+        });
+        StandIn { url: url, websockets: websockets, pong: pong, user_agents: user_agents }
+    }
+}
+
+fn serve(mut stream: TcpStream, mode: Mode, websockets: &AtomicUsize, pong: &AtomicBool, user_agents: &Mutex<Vec<String>>) {
+    let head = match read_head(&mut stream) {
+        Some(head) => head,
+        None => return,
+    };
+    let path = head.split_whitespace().nth(1).unwrap().to_string();
+    assert!(path.contains("clientProtocol=1.5"));
+    assert!(path.contains("connectionData=%5B%7B%22name%22%3A%22c2%22%7D%5D"));
+    if let Some(user_agent) = header(&head, "User-Agent") {
+        user_agents.lock().unwrap().push(user_agent.to_string());
+    }
+
+    if path.starts_with("/signalr/negotiate") && mode == Mode::TruncatedNegotiate {
+        stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 100\r\nConnection: close\r\n\r\n{\"Url\"").unwrap();
+    } else if path.starts_with("/signalr/negotiate") {
+        respond(&mut stream, r#"{"Url":"/signalr","ConnectionToken":"token/1+","ConnectionId":"1","KeepAliveTimeout":20.0,"TryWebSockets":true}"#);
+    } else if path.starts_with("/signalr/start") {
+        assert!(path.contains("connectionToken=token%2F1%2B"));
+        respond(&mut stream, r#"{"Response":"started"}"#);
+    } else if path.starts_with("/signalr/connect") {
+        assert!(path.contains("transport=webSockets"));
+        assert!(path.contains("connectionToken=token%2F1%2B"));
+        let number = websockets.fetch_add(1, Ordering::SeqCst) + 1;
+        let key = header(&head, "Sec-WebSocket-Key").unwrap();
+        let accept = if mode == Mode::BadAccept {
+            base64::encode(&Sha1::digest(key.as_bytes()))
+        } else {
+            base64::encode(&Sha1::digest(format!("{}258EAFA5-E914-47DA-95CA-C5AB0DC85B11", key).as_bytes()))
+        };
+        let upgrade = format!("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n", accept);
+        stream.write_all(upgrade.as_bytes()).unwrap();
+        write_frame(&mut stream, 0x1, br#"{"C":"d-1","S":1,"M":[]}"#);
+        write_frame(&mut stream, 0x9, b"ping");
+
+        let mut summaries = false;
+        while let Some((opcode, payload)) = read_frame(&mut stream) {
+            match opcode {
+                0x8 => return,
+                0xA => {
+                    pong.store(payload == b"ping", Ordering::SeqCst);
+                    continue;
+                },
+                _ => {},
+            }
+            let invocation: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+            assert_eq!(invocation["H"], "c2");
+            let id = invocation["I"].as_str().unwrap().to_string();
+            match invocation["M"].as_str().unwrap() {
+                "SubscribeToExchangeDeltas" if invocation["A"][0] == "BTC-XYZ" => {
+                    write_frame(&mut stream, 0x1, format!(r#"{{"E":"There was an error invoking Hub method 'c2.SubscribeToExchangeDeltas'.","I":"{}"}}"#, id).as_bytes());
+                },
+                "SubscribeToExchangeDeltas" => {
+                    assert_eq!(invocation["A"][0], "BTC-LTC");
+                    write_frame(&mut stream, 0x1, format!(r#"{{"R":true,"I":"{}"}}"#, id).as_bytes());
+                },
+                "SubscribeToSummaryDeltas" => {
+                    summaries = true;
+                    write_frame(&mut stream, 0x1, format!(r#"{{"R":true,"I":"{}"}}"#, id).as_bytes());
+                },
+                "QueryExchangeState" => {
+                    write_frame(&mut stream, 0x1, format!(r#"{{"R":"{}","I":"{}"}}"#, compress(EXCHANGE_STATE), id).as_bytes());
+                    write_frame(&mut stream, 0x1, b"{}");
+                    write_frame(&mut stream, 0x1, hub_message("uE", EXCHANGE_DELTA).as_bytes());
+                    if summaries {
+                        write_frame(&mut stream, 0x1, hub_message("uS", SUMMARY_DELTAS).as_bytes());
+                    }
+                    if mode == Mode::DropFirst && number == 1 {
+                        write_frame(&mut stream, 0x8, &[]);
+                    }
+                },
+                method => panic!("Unexpected invocation {}", method),
+            }
+        }
+    }
+}
+
+/// Starts a forwarding HTTP proxy, which tunnels `CONNECT` requests and forwards other requests in origin form.
+/// Returns its url and the request lines it received.
+fn start_proxy() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let thread_requests = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let requests = thread_requests.clone();
+            thread::spawn(move || proxy(stream.unwrap(), &requests));
+        }
+    });
+    (url, requests)
+}
+
+fn proxy(mut stream: TcpStream, requests: &Mutex<Vec<String>>) {
+    let head = match read_head(&mut stream) {
+        Some(head) => head,
+        None => return,
+    };
+    let request_line = head.lines().next().unwrap().to_string();
+    requests.lock().unwrap().push(request_line.clone());
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap(), parts.next().unwrap());
+    let mut upstream = if method == "CONNECT" {
+        let upstream = TcpStream::connect(target).unwrap();
+        stream.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").unwrap();
+        upstream
+    } else {
+        let rest = &target["http://".len()..];
+        let index = rest.find('/').unwrap();
+        let mut upstream = TcpStream::connect(&rest[..index]).unwrap();
+        upstream.write_all(head.replacen(target, &rest[index..], 1).as_bytes()).unwrap();
+        upstream
+    };
+
+    let (mut stream_reader, mut upstream_writer) = (stream.try_clone().unwrap(), upstream.try_clone().unwrap());
+    thread::spawn(move || io::copy(&mut stream_reader, &mut upstream_writer));
+    let _ = io::copy(&mut upstream, &mut stream);
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+/// Reads the head of a request, `None` when the client is gone before.
+fn read_head(stream: &mut TcpStream) -> Option<String> {
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if stream.read(&mut byte).unwrap_or(0) == 0 {
+            return None;
+        }
+        head.push(byte[0]);
+    }
+    Some(String::from_utf8(head).unwrap())
+}
+
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines()
+        .find(|line| line.len() > name.len() && line[..name.len()].eq_ignore_ascii_case(name) && line[name.len()..].starts_with(':'))
+        .map(|line| line[name.len() + 1..].trim())
+}
+
+fn respond(stream: &mut TcpStream, body: &str) {
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(), body
+    );
+    stream.write_all(response.as_bytes()).unwrap();
+}
+
+/// Writes an unmasked server frame with a payload shorter than 64 KiB.
+fn write_frame(stream: &mut TcpStream, opcode: u8, payload: &[u8]) {
+    let mut frame = vec![0x80 | opcode];
+    if payload.len() < 126 {
+        frame.push(payload.len() as u8);
+    } else {
+        frame.push(126);
+        frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    }
+    frame.extend_from_slice(payload);
+    stream.write_all(&frame).unwrap();
+}
+
+/// Reads a masked client frame, `None` when the client is gone.
+fn read_frame(stream: &mut TcpStream) -> Option<(u8, Vec<u8>)> {
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).ok()?;
+    assert_eq!(header[1] & 0x80, 0x80, "Client frames must be masked");
+    let length = match header[1] & 0x7F {
+        126 => {
+            let mut length = [0u8; 2];
+            stream.read_exact(&mut length).ok()?;
+            u16::from_be_bytes(length) as usize
+        },
+        length => length as usize,
+    };
+    let mut mask = [0u8; 4];
+    stream.read_exact(&mut mask).ok()?;
+    let mut payload = vec![0u8; length];
+    stream.read_exact(&mut payload).ok()?;
+    for (index, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[index % 4];
+    }
+    Some((header[0] & 0x0F, payload))
+}
+
+fn hub_message(method: &str, json: &str) -> String {
+    format!(r#"{{"C":"d-2","M":[{{"H":"C2","M":"{}","A":["{}"]}}]}}"#, method, compress(json))
+}
+
+fn compress(json: &str) -> String {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(json.as_bytes()).unwrap();
+    base64::encode(&encoder.finish().unwrap())
+}
+
+fn describe(event: &SocketEvent) -> String {
+    match *event {
+        SocketEvent::Connected => "connected".to_string(),
+        SocketEvent::ExchangeState(ref state) => format!("state {} {}", state.market, state.nonce),
+        SocketEvent::ExchangeDelta(ref delta) => format!("delta {} {}", delta.market, delta.nonce),
+        SocketEvent::SummaryDeltas(ref deltas) => format!("summaries {}", deltas.nonce),
+        SocketEvent::Error(ref error) => format!("error {:?}", error.error_type),
+        SocketEvent::Disconnected(ref error) => format!("disconnected {:?}", error.error_type),
+    }
+}
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+#[test]
+fn should_decode_exchange_state_and_deltas_successfully() {
+    // Arrange
+    let stand_in = StandIn::start(Mode::Serve);
+    let client = SocketClient::with_url(&stand_in.url)
+        .exchange_deltas(vec!["BTC-LTC".parse().unwrap()])
+        .summary_deltas()
+        .timeout(Duration::from_secs(5));
+
+    // Act
+    let events: Vec<SocketEvent> = client.spawn().iter().take(4).collect();
+
+    // Assert
+    let descriptions: Vec<String> = events.iter().map(describe).collect();
+    assert_eq!(descriptions, vec!["connected", "state BTC-LTC 100", "delta BTC-LTC 101", "summaries 7"]);
+
+    if let SocketEvent::ExchangeState(ref state) = events[1] {
+        assert_eq!((state.order_book.buy[0].rate, state.order_book.buy[0].quantity), (decimal("0.0129"), decimal("5.5")));
+        assert_eq!(state.order_book.sell[0].rate, decimal("0.0131"));
+        assert_eq!(state.fills[0].id, 42);
+        assert_eq!(state.fills[0].time_stamp, datetime::parse("2020-01-01T10:00:00").unwrap());
+        assert_eq!(state.fills[0].order_type, BittrexOrderSide::Buy);
+    }
+    if let SocketEvent::ExchangeDelta(ref delta) = events[2] {
+        assert_eq!((delta.buys[0].delta_type, delta.buys[0].quantity), (DeltaType::Update, decimal("4.5")));
+        assert_eq!(delta.sells[0].delta_type, DeltaType::Remove);
+        assert_eq!((delta.fills[0].id, delta.fills[0].rate), (Some(43), decimal("0.0131")));
+        assert_eq!(delta.fills[0].time_stamp, datetime::parse("2020-01-01T10:00:01").unwrap());
+    }
+    if let SocketEvent::SummaryDeltas(ref deltas) = events[3] {
+        let summary = &deltas.summaries[0];
+        assert_eq!(summary.market_name.to_string(), "BTC-LTC");
+        assert_eq!((summary.last, summary.bid, summary.ask), (decimal("0.0131"), decimal("0.0129"), decimal("0.0132")));
+        assert_eq!((summary.open_buy_orders, summary.open_sell_orders), (10, 20));
+    }
+    for _ in 0..100 {
+        if stand_in.pong.load(Ordering::SeqCst) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert!(stand_in.pong.load(Ordering::SeqCst));
+}
+
+#[test]
+fn should_reconnect_and_query_exchange_state_again_successfully() {
+    // Arrange
+    let stand_in = StandIn::start(Mode::DropFirst);
+    let client = SocketClient::with_url(&stand_in.url)
+        .exchange_deltas(vec!["BTC-LTC".parse().unwrap()])
+        .reconnect_delay(Duration::from_millis(10))
+        .timeout(Duration::from_secs(5));
+
+    // Act
+    let events: Vec<String> = client.spawn().iter().take(7).map(|event| describe(&event)).collect();
+
+    // Assert
+    assert_eq!(events, vec![
+        "connected", "state BTC-LTC 100", "delta BTC-LTC 101",
+        "disconnected Transport",
+        "connected", "state BTC-LTC 100", "delta BTC-LTC 101",
+    ]);
+    assert_eq!(stand_in.websockets.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn should_report_rejected_subscription_as_hub_error() {
+    // Arrange
+    let stand_in = StandIn::start(Mode::Serve);
+    let client = SocketClient::with_url(&stand_in.url)
+        .exchange_deltas(vec!["BTC-XYZ".parse().unwrap()])
+        .timeout(Duration::from_secs(5));
+
+    // Act
+    let events: Vec<SocketEvent> = client.spawn().iter().take(3).collect();
+
+    // Assert
+    let descriptions: Vec<String> = events.iter().map(describe).collect();
+    assert_eq!(descriptions, vec!["connected", "error Hub", "state BTC-XYZ 100"]);
+    if let SocketEvent::Error(ref error) = events[1] {
+        assert!(error.message.contains("SubscribeToExchangeDeltas"));
+    }
+}
+
+#[test]
+fn should_reject_upgrade_with_invalid_accept() {
+    // Arrange
+    let stand_in = StandIn::start(Mode::BadAccept);
+    let client = SocketClient::with_url(&stand_in.url)
+        .exchange_deltas(vec!["BTC-LTC".parse().unwrap()])
+        .reconnect_delay(Duration::from_secs(60))
+        .timeout(Duration::from_secs(5));
+
+    // Act
+    let event = client.spawn().recv().unwrap();
+
+    // Assert
+    assert!(format!("{:?}", event).contains("Sec-WebSocket-Accept"));
+    assert_eq!(describe(&event), "disconnected Transport");
+}
+
+#[test]
+fn should_connect_through_proxy_with_user_agent_successfully() {
+    // Arrange
+    let stand_in = StandIn::start(Mode::Serve);
+    let (proxy_url, proxy_requests) = start_proxy();
+    let client = BittrexClient::builder()
+        .api_url(stand_in.url.clone())
+        .http_proxy(proxy_url)
+        .user_agent("my-trading-bot/1.0".to_string())
+        .timeout(Duration::from_secs(5))
+        .build_socket()
+        .unwrap()
+        .exchange_deltas(vec!["BTC-LTC".parse().unwrap()]);
+
+    // Act
+    let events: Vec<String> = client.spawn().iter().take(2).map(|event| describe(&event)).collect();
+
+    // Assert
+    assert_eq!(events, vec!["connected", "state BTC-LTC 100"]);
+    let proxy_requests = proxy_requests.lock().unwrap();
+    assert_eq!(proxy_requests.len(), 3);
+    assert!(proxy_requests[0].starts_with(&format!("GET {}/negotiate?", stand_in.url)));
+    assert!(proxy_requests[1].starts_with(&format!("CONNECT {} ", &stand_in.url["http://".len()..stand_in.url.len() - "/signalr".len()])));
+    assert!(proxy_requests[2].starts_with(&format!("GET {}/start?", stand_in.url)));
+    assert_eq!(*stand_in.user_agents.lock().unwrap(), vec!["my-trading-bot/1.0"; 3]);
+}
+
+#[test]
+fn should_count_connection_requests_against_rate_limit() {
+    // Arrange
+    let stand_in = StandIn::start(Mode::Serve);
+    let rate_limiter = RateLimiter::new()
+        .limit(EndpointClass::Public, RateLimit::per_minute(2))
+        .mode(RateLimitMode::FailFast);
+    let client = BittrexClient::builder()
+        .api_url(stand_in.url.clone())
+        .rate_limiter(rate_limiter)
+        .timeout(Duration::from_secs(5))
+        .build_socket()
+        .unwrap()
+        .reconnect_delay(Duration::from_secs(60));
+
+    // Act
+    let event = client.spawn().recv().unwrap();
+
+    // Assert
+    assert_eq!(describe(&event), "disconnected RateLimited");
+    assert_eq!(stand_in.websockets.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn should_keep_status_and_url_when_reading_negotiate_response_fails() {
+    // Arrange
+    let stand_in = StandIn::start(Mode::TruncatedNegotiate);
+    let client = SocketClient::with_url(&stand_in.url)
+        .reconnect_delay(Duration::from_secs(60))
+        .timeout(Duration::from_secs(5));
+
+    // Act
+    let event = client.spawn().recv().unwrap();
+
+    // Assert
+    match event {
+        SocketEvent::Disconnected(error) => {
+            assert_eq!(error.error_type, BittrexErrorType::Transport);
+            assert_eq!(error.status(), Some(200));
+            assert!(error.url().unwrap().starts_with(&format!("{}/negotiate?", stand_in.url)));
+        },
+        event => panic!("Unexpected event {}", describe(&event)),
+    }
+}
@@ -0,0 +1,143 @@
+extern crate bittrex_api;
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use bittrex_api::{Decimal, Market, MarketData};
+use bittrex_api::datetime;
+use bittrex_api::error::{BittrexError, BittrexErrorType};
+use bittrex_api::rate_limit::{EndpointClass, RateLimit, RateLimiter};
+use bittrex_api::trade_feed::{TradeEvent, TradeFeed};
+use bittrex_api::values::*;
+
+/// Returns the scripted windows of trade ids per market in order, then empty windows.
+struct ScriptedHistory {
+    windows: Mutex<HashMap<Market, VecDeque<Vec<u32>>>>,
+}
+
+impl ScriptedHistory {
+    fn new(windows: Vec<(&str, Vec<Vec<u32>>)>) -> Self {
+        let windows = windows.into_iter()
+            .map(|(market, windows)| (market.parse().unwrap(), windows.into_iter().collect()))
+            .collect();
+        ScriptedHistory { windows: Mutex::new(windows) }
+    }
+}
+
+impl MarketData for ScriptedHistory {
+    fn get_markets(&self) -> Result<Vec<BittrexMarket>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn get_currencies(&self) -> Result<Vec<BittrexCurrency>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn get_ticker(&self, _market: &Market) -> Result<BittrexTicker, BittrexError> {
+        Err(BittrexError::new(BittrexErrorType::NoResults, "No ticker".to_string()))
+    }
+
+    fn get_market_summaries(&self) -> Result<Vec<BittrexMarketSummary>, BittrexError> {
+        Ok(Vec::new())
+    }
+
+    fn get_market_summary(&self, _market: &Market) -> Result<BittrexMarketSummary, BittrexError> {
+        Err(BittrexError::new(BittrexErrorType::NoResults, "No market summary".to_string()))
+    }
+
+    fn get_order_book(&self, _market: &Market, _book_type: BittrexOrderType) -> Result<BittrexPublicOrderBook, BittrexError> {
+        Ok(BittrexPublicOrderBook { buy: Vec::new(), sell: Vec::new() })
+    }
+
+    fn get_market_history(&self, market: &Market) -> Result<Vec<BittrexTrade>, BittrexError> {
+        let mut windows = self.windows.lock().unwrap();
+        let window = windows.get_mut(market).and_then(|windows| windows.pop_front()).unwrap_or_default();
+        Ok(window.into_iter().map(trade).collect())
+    }
+}
+
+fn trade(id: u32) -> BittrexTrade {
+    BittrexTrade {
+        id: id,
+        time_stamp: datetime::parse("2020-01-01T10:00:00").unwrap(),
+        quantity: Decimal::new(1, 0),
+        price: Decimal::new(1, 2),
+        total: Decimal::new(1, 2),
+        fill_type: BittrexFillType::Fill,
+        order_type: BittrexOrderSide::Buy,
+    }
+}
+
+fn describe(event: &TradeEvent) -> String {
+    match *event {
+        TradeEvent::Trade(ref market, ref trade) => format!("{} {}", market, trade.id),
+        TradeEvent::Gap { ref market, last_id, next_id } => format!("{} gap {}-{}", market, last_id, next_id),
+        TradeEvent::Error(ref market, ref error) => format!("{} error {}", market, error),
+    }
+}
+
+#[test]
+fn should_emit_only_new_trades_in_order_successfully() {
+    // Arrange
+    let history = ScriptedHistory::new(vec![("BTC-LTC", vec![vec![3, 2, 1], vec![5, 4, 3, 2], vec![5, 4]])]);
+    let mut feed = TradeFeed::new(history, vec!["BTC-LTC".parse().unwrap()])
+        .poll_interval(Duration::from_millis(1));
+
+    // Act
+    let events: Vec<String> = feed.by_ref().take(5).map(|event| describe(&event)).collect();
+
+    // Assert
+    assert_eq!(events, vec!["BTC-LTC 1", "BTC-LTC 2", "BTC-LTC 3", "BTC-LTC 4", "BTC-LTC 5"]);
+    assert_eq!(feed.last_id(&"BTC-LTC".parse().unwrap()), Some(5));
+}
+
+#[test]
+fn should_detect_gap_between_windows_successfully() {
+    // Arrange
+    let history = ScriptedHistory::new(vec![("BTC-LTC", vec![vec![2, 1], vec![3, 2], vec![9, 8]])]);
+    let feed = TradeFeed::new(history, vec!["BTC-LTC".parse().unwrap()])
+        .poll_interval(Duration::from_millis(1));
+
+    // Act
+    let events: Vec<String> = feed.take(6).map(|event| describe(&event)).collect();
+
+    // Assert
+    assert_eq!(events, vec!["BTC-LTC 1", "BTC-LTC 2", "BTC-LTC 3", "BTC-LTC gap 3-8", "BTC-LTC 8", "BTC-LTC 9"]);
+}
+
+#[test]
+fn should_detect_gap_only_before_last_seen_trade_with_sparse_ids_successfully() {
+    // Arrange
+    let history = ScriptedHistory::new(vec![("BTC-LTC", vec![vec![20, 10], vec![35, 20], vec![60, 50]])]);
+    let feed = TradeFeed::new(history, vec!["BTC-LTC".parse().unwrap()])
+        .poll_interval(Duration::from_millis(1));
+
+    // Act
+    let events: Vec<String> = feed.take(6).map(|event| describe(&event)).collect();
+
+    // Assert
+    assert_eq!(events, vec!["BTC-LTC 10", "BTC-LTC 20", "BTC-LTC 35", "BTC-LTC gap 35-50", "BTC-LTC 50", "BTC-LTC 60"]);
+}
+
+#[test]
+fn should_share_rate_budget_between_markets_successfully() {
+    // Arrange
+    let history = ScriptedHistory::new(vec![
+        ("BTC-LTC", vec![vec![1]]),
+        ("BTC-ETH", vec![vec![2]]),
+        ("BTC-NEO", vec![vec![3]]),
+    ]);
+    let markets = vec!["BTC-LTC".parse().unwrap(), "BTC-ETH".parse().unwrap(), "BTC-NEO".parse().unwrap()];
+    let rate_limiter = RateLimiter::new().limit(EndpointClass::Public, RateLimit::new(2, Duration::from_millis(400)));
+    let start = Instant::now();
+
+    // Act
+    let receiver = TradeFeed::new(history, markets).rate_limiter(rate_limiter).spawn();
+    let mut events: Vec<String> = receiver.iter().take(3).map(|event| describe(&event)).collect();
+
+    // Assert
+    events.sort();
+    assert_eq!(events, vec!["BTC-ETH 2", "BTC-LTC 1", "BTC-NEO 3"]);
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
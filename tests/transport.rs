@@ -0,0 +1,180 @@
+extern crate bittrex_api;
+extern crate reqwest;
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use bittrex_api::{BittrexClient, Currency, Decimal, RetryPolicy};
+use bittrex_api::error::{BittrexError, BittrexErrorType};
+use bittrex_api::nonce::NonceSource;
+use bittrex_api::transport::{HttpRequest, HttpResponse, HttpTransport, ReqwestTransport, StatusCode};
+
+/// Answers requests with the given responses in order and records the requests.
+#[derive(Clone)]
+struct FakeTransport {
+    responses: Arc<Mutex<Vec<Result<HttpResponse, BittrexError>>>>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl FakeTransport {
+    fn new(mut responses: Vec<Result<HttpResponse, BittrexError>>) -> Self {
+        responses.reverse();
+        FakeTransport { responses: Arc::new(Mutex::new(responses)), requests: Arc::new(Mutex::new(Vec::new())) }
+    }
+
+    fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl HttpTransport for FakeTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, BittrexError> {
+        self.requests.lock().unwrap().push(request.clone());
+        self.responses.lock().unwrap().pop().expect("No response left")
+    }
+}
+
+struct FixedNonce(u64);
+
+impl NonceSource for FixedNonce {
+    fn next_nonce(&self) -> Result<u64, BittrexError> {
+        Ok(self.0)
+    }
+}
+
+fn response(status: StatusCode, body: &str) -> Result<HttpResponse, BittrexError> {
+    Ok(HttpResponse { status: status, url: "http://fake/".to_string(), body: body.to_string() })
+}
+
+#[test]
+fn should_send_public_calls_through_transport_successfully() {
+    // Arrange
+    let transport = FakeTransport::new(vec![response(StatusCode::OK, r#"{
+        "success" : true,
+        "message" : "",
+        "result" : { "Bid" : 2.05670368, "Ask" : 3.35579531, "Last" : 3.35579531 }
+    }"#)]);
+    let bittrex_client = BittrexClient::builder()
+        .api_url("http://fake".to_string())
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    // Act
+    let ticker = bittrex_client.get_ticker(&"BTC-LTC".parse().unwrap()).unwrap();
+
+    // Assert
+    assert_eq!(ticker.last.to_string(), "3.35579531");
+    assert_eq!(transport.requests(), vec![HttpRequest::get("http://fake/public/getticker?market=BTC-LTC".to_string(), Vec::new())]);
+}
+
+#[test]
+fn should_send_signed_private_calls_through_transport_successfully() {
+    // Arrange
+    let transport = FakeTransport::new(vec![response(StatusCode::OK, r#"{ "success" : true, "message" : "", "result" : [] }"#)]);
+    let bittrex_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url("http://fake".to_string())
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    // Act
+    let balances = bittrex_client.get_balances().unwrap();
+
+    // Assert
+    let requests = transport.requests();
+    assert!(balances.is_empty());
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].url.starts_with("http://fake/account/getbalances?apikey=KEY&nonce="));
+    assert_eq!(requests[0].headers.len(), 1);
+    assert_eq!(requests[0].headers[0].0, "apisign");
+    assert_eq!(requests[0].headers[0].1.len(), 128);
+}
+
+#[test]
+fn should_encode_parameters_and_sign_final_url_successfully() {
+    // Arrange
+    let transport = FakeTransport::new(vec![response(StatusCode::OK, r#"{ "success" : true, "message" : "", "result" : { "uuid" : "ID" } }"#)]);
+    let bittrex_client = BittrexClient::builder()
+        .credentials("KEY".to_string(), "SECRET".to_string())
+        .api_url("http://fake".to_string())
+        .nonce_source(FixedNonce(42))
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    // Act
+    bittrex_client.withdraw(&Currency::new("XMR").unwrap(), Decimal::new(15, 1), "4Ab&apikey=EVIL #x", "memo+1 2/\u{e9}").unwrap();
+
+    // Assert
+    assert_eq!(transport.requests(), vec![HttpRequest::get(
+        "http://fake/account/withdraw?currency=XMR&quantity=1.5&address=4Ab%26apikey%3DEVIL%20%23x&paymentid=memo%2B1%202%2F%C3%A9&apikey=KEY&nonce=42".to_string(),
+        vec![("apisign".to_string(), "AA62083B5448B64D75FE05C02AE6A8D7D1454AA825EC2E9BDEEE9A4ECC2D5CCB261F80981F5261CFE68D9CD496A34B49D3EBD75AC84279ED64177D0209075A28".to_string())],
+    )]);
+}
+
+#[test]
+fn should_retry_transport_errors_and_server_errors() {
+    // Arrange
+    let transport = FakeTransport::new(vec![
+        Err(BittrexError::new(BittrexErrorType::Transport, "Connection reset".to_string())),
+        response(StatusCode::SERVICE_UNAVAILABLE, ""),
+        response(StatusCode::OK, r#"{ "success" : true, "message" : "", "result" : [] }"#),
+    ]);
+    let bittrex_client = BittrexClient::builder()
+        .transport(transport.clone())
+        .retry_policy(RetryPolicy::new(2, Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    // Act
+    let markets = bittrex_client.get_markets().unwrap();
+
+    // Assert
+    assert!(markets.is_empty());
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[test]
+fn should_return_http_status_error_from_transport() {
+    // Arrange
+    let transport = FakeTransport::new(vec![response(StatusCode::NOT_FOUND, "Not Found")]);
+    let bittrex_client = BittrexClient::builder()
+        .transport(transport)
+        .build()
+        .unwrap();
+
+    // Act
+    let error = bittrex_client.get_markets().err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::HttpStatus(404));
+    assert_eq!(error.body(), Some("Not Found"));
+}
+
+#[test]
+fn should_keep_status_and_url_when_reading_the_body_fails() {
+    // Arrange
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0u8; 1024];
+        let _ = stream.read(&mut request).unwrap();
+        stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\n{\"success\"").unwrap();
+    });
+    let transport = ReqwestTransport::new(reqwest::Client::new());
+    let url = format!("http://{}/account/getbalances?apikey=KEY&nonce=1", address);
+
+    // Act
+    let error = transport.send(&HttpRequest::get(url, Vec::new())).err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::Transport);
+    assert_eq!(error.status(), Some(200));
+    assert_eq!(error.url(), Some(format!("http://{}/account/getbalances?apikey=REDACTED&nonce=1", address).as_str()));
+}
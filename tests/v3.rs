@@ -0,0 +1,201 @@
+extern crate mockito;
+extern crate bittrex_api;
+
+use mockito::{mock, Matcher};
+
+use bittrex_api::{BittrexClient, Currency, Decimal};
+use bittrex_api::error::{ApiErrorCode, BittrexErrorType};
+use bittrex_api::values::{BittrexCandle, TickInterval};
+use bittrex_api::v3::*;
+
+const EMPTY_CONTENT_HASH: &str = "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e";
+
+const ORDER_BODY: &str = r#"{"marketSymbol":"LTC-BTC","direction":"BUY","type":"LIMIT","quantity":"1.5","limit":"0.0123","timeInForce":"GOOD_TIL_CANCELLED"}"#;
+
+const ORDER: &str = r#"{
+    "id" : "a7d6e8ce-7a16-4b6a-9e1c-5d1a6b5a4f4c",
+    "marketSymbol" : "LTC-BTC",
+    "direction" : "BUY",
+    "type" : "LIMIT",
+    "quantity" : "1.50000000",
+    "limit" : "0.01230000",
+    "timeInForce" : "GOOD_TIL_CANCELLED",
+    "fillQuantity" : "0.00000000",
+    "commission" : "0.00000000",
+    "proceeds" : "0.00000000",
+    "status" : "OPEN",
+    "createdAt" : "2020-01-01T00:00:00.12Z",
+    "updatedAt" : "2020-01-01T00:00:00.12Z"
+}"#;
+
+fn decimal(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+#[test]
+fn should_compute_signature_test_vectors_successfully() {
+    // Arrange
+    let url = "https://api.bittrex.com/v3/balances";
+
+    // Act
+    let headers = auth::headers("KEY", "SECRET", 1_577_836_800_000, "GET", url, "");
+
+    // Assert
+    assert_eq!(auth::content_hash(""), EMPTY_CONTENT_HASH);
+    assert_eq!(auth::content_hash(ORDER_BODY), "3ecf41ed20fc3ea471d29c7071520804996bf72dbd3122cb08d22cadb5a2785baaee575f81d00a37fe1ea9edf043024996b982582782f8066c8f61b4ec271440");
+    assert_eq!(headers, vec![
+        ("Api-Key".to_string(), "KEY".to_string()),
+        ("Api-Timestamp".to_string(), "1577836800000".to_string()),
+        ("Api-Content-Hash".to_string(), EMPTY_CONTENT_HASH.to_string()),
+        ("Api-Signature".to_string(), "13d11b30d8e98e7eca594d04da2e92aa56c2dc8b9f6bb6b16077200503b9af87a7c62e60cab5f2acdb2b957e297e0fbdeb86e5bf0e61ed2653f79a512d6e36e6".to_string()),
+    ]);
+    assert_eq!(
+        auth::signature("SECRET", "1577836800000", "https://api.bittrex.com/v3/orders", "POST", &auth::content_hash(ORDER_BODY)),
+        "6b50d604b20b79827033f1266f1e819a0c6aa4c9dd4af7ff3b3d008515c0e2633fe16da7c3b8039765f0671ddad409b6a85a6c2296e9f56638df928e592c4141"
+    );
+}
+
+#[test]
+fn should_get_ticker_successfully() {
+    // Arrange
+    let _mock = mock("GET", "/markets/LTC-BTC/ticker")
+        .with_status(200)
+        .with_body(r#"{ "symbol" : "LTC-BTC", "lastTradeRate" : "0.00621500", "bidRate" : "0.00621100", "askRate" : "0.00622400" }"#)
+        .create();
+    let bittrex_client = BittrexClient::builder().api_url(mockito::server_url()).build_v3().unwrap();
+
+    // Act
+    let ticker = bittrex_client.get_ticker(&"BTC-LTC".parse().unwrap()).unwrap();
+
+    // Assert
+    assert_eq!(ticker.symbol, "BTC-LTC");
+    assert_eq!(ticker.bid_rate, decimal("0.006211"));
+    assert_eq!(ticker.ask_rate, decimal("0.006224"));
+}
+
+#[test]
+fn should_get_candles_successfully() {
+    // Arrange
+    let _mock = mock("GET", "/markets/LTC-BTC/candles/HOUR_1/recent")
+        .with_status(200)
+        .with_body(r#"[{
+            "startsAt" : "2020-01-01T10:00:00Z", "open" : "0.00620000", "high" : "0.00630000", "low" : "0.00610000",
+            "close" : "0.00625000", "volume" : "350.00000000", "quoteVolume" : "2.18000000"
+        }]"#)
+        .create();
+    let bittrex_client = BittrexClient::builder().api_url(mockito::server_url()).build_v3().unwrap();
+    let market = "BTC-LTC".parse().unwrap();
+
+    // Act
+    let candles = bittrex_client.get_candles(&market, TickInterval::Hour).unwrap();
+    let unsupported = bittrex_client.get_candles(&market, TickInterval::ThirtyMin);
+
+    // Assert
+    let candle: BittrexCandle = candles.into_iter().next().unwrap().into();
+    assert_eq!(candle.open, decimal("0.0062"));
+    assert_eq!(candle.close, decimal("0.00625"));
+    assert_eq!(candle.volume, decimal("350"));
+    assert_eq!(candle.base_volume, decimal("2.18"));
+    assert_eq!(unsupported.err().unwrap().error_type, BittrexErrorType::Configuration);
+}
+
+#[test]
+fn should_place_order_with_signed_headers_successfully() {
+    // Arrange
+    let _mock = mock("POST", "/orders")
+        .match_header("Api-Key", "KEY")
+        .match_header("Api-Timestamp", Matcher::Regex(r"^\d{13}$".to_string()))
+        .match_header("Api-Content-Hash", "3ecf41ed20fc3ea471d29c7071520804996bf72dbd3122cb08d22cadb5a2785baaee575f81d00a37fe1ea9edf043024996b982582782f8066c8f61b4ec271440")
+        .match_header("Api-Signature", Matcher::Regex(r"^[0-9a-f]{128}$".to_string()))
+        .match_header("Content-Type", "application/json")
+        .match_body(ORDER_BODY)
+        .with_status(201)
+        .with_body(ORDER)
+        .create();
+    let bittrex_client = BittrexV3Client::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+    let order = V3NewOrder::limit(&"BTC-LTC".parse().unwrap(), V3OrderDirection::Buy, decimal("1.5"), decimal("0.0123"), V3TimeInForce::GoodTilCancelled);
+
+    // Act
+    let order = bittrex_client.place_order(&order).unwrap();
+
+    // Assert
+    assert_eq!(order.market_symbol, "BTC-LTC");
+    assert_eq!(order.order_type, V3OrderType::Limit);
+    assert_eq!(order.status, V3OrderStatus::Open);
+    assert_eq!(order.limit, Some(decimal("0.0123")));
+    assert!(order.closed_at.is_none());
+}
+
+#[test]
+fn should_return_api_error_of_rejected_order() {
+    // Arrange
+    let _mock = mock("POST", "/orders")
+        .with_status(400)
+        .with_body(r#"{ "code" : "INSUFFICIENT_FUNDS" }"#)
+        .create();
+    let bittrex_client = BittrexV3Client::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+    let order = V3NewOrder::limit(&"BTC-LTC".parse().unwrap(), V3OrderDirection::Sell, decimal("100"), decimal("1"), V3TimeInForce::ImmediateOrCancel);
+
+    // Act
+    let error = bittrex_client.place_order(&order).err().unwrap();
+
+    // Assert
+    assert_eq!(error.error_type, BittrexErrorType::Api(ApiErrorCode::InsufficientFunds));
+    assert_eq!(error.status(), Some(400));
+}
+
+#[test]
+fn should_cancel_order_successfully() {
+    // Arrange
+    let _mock = mock("DELETE", "/orders/a7d6e8ce-7a16-4b6a-9e1c-5d1a6b5a4f4c")
+        .match_header("Api-Content-Hash", EMPTY_CONTENT_HASH)
+        .with_status(200)
+        .with_body(ORDER.replace(r#""status" : "OPEN""#, r#""status" : "CLOSED", "closedAt" : "2020-01-01T00:01:00Z""#))
+        .create();
+    let bittrex_client = BittrexV3Client::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+
+    // Act
+    let order = bittrex_client.cancel_order("a7d6e8ce-7a16-4b6a-9e1c-5d1a6b5a4f4c").unwrap();
+
+    // Assert
+    assert_eq!(order.status, V3OrderStatus::Closed);
+    assert!(order.closed_at.is_some());
+}
+
+#[test]
+fn should_get_balances_deposits_and_withdrawals_successfully() {
+    // Arrange
+    let _balances_mock = mock("GET", "/balances")
+        .with_status(200)
+        .with_body(r#"[{ "currencySymbol" : "BTC", "total" : "1.25000000", "available" : "1.00000000", "updatedAt" : "2020-01-01T00:00:00Z" }]"#)
+        .create();
+    let _deposits_mock = mock("GET", "/deposits/closed?currencySymbol=BTC")
+        .with_status(200)
+        .with_body(r#"[{
+            "id" : "d1", "currencySymbol" : "BTC", "quantity" : "0.50000000", "cryptoAddress" : "1BTC", "txId" : "tx1",
+            "confirmations" : 3, "updatedAt" : "2020-01-01T00:00:00Z", "completedAt" : "2020-01-01T00:00:00Z", "status" : "COMPLETED", "source" : "BLOCKCHAIN"
+        }]"#)
+        .create();
+    let _withdrawals_mock = mock("GET", "/withdrawals/open")
+        .with_status(200)
+        .with_body(r#"[{
+            "id" : "w1", "currencySymbol" : "BTC", "quantity" : "0.25000000", "cryptoAddress" : "1ABC", "txCost" : "0.00050000",
+            "status" : "PENDING", "createdAt" : "2020-01-01T00:00:00Z"
+        }]"#)
+        .create();
+    let bittrex_client = BittrexV3Client::new_override_api_url("KEY".to_string(), "SECRET".to_string(), mockito::server_url());
+    let btc = Currency::new("BTC").unwrap();
+
+    // Act
+    let balances = bittrex_client.get_balances().unwrap();
+    let deposits = bittrex_client.get_closed_deposits(Some(&btc)).unwrap();
+    let withdrawals = bittrex_client.get_open_withdrawals(None).unwrap();
+
+    // Assert
+    assert_eq!(balances[0].currency_symbol, btc);
+    assert_eq!(balances[0].total, decimal("1.25"));
+    assert_eq!(deposits[0].quantity, decimal("0.5"));
+    assert_eq!(deposits[0].confirmations, 3);
+    assert_eq!(withdrawals[0].tx_cost, Some(decimal("0.0005")));
+    assert!(withdrawals[0].completed_at.is_none());
+}
@@ -0,0 +1,79 @@
+extern crate serde_json;
+extern crate bittrex_api;
+
+use bittrex_api::Decimal;
+use bittrex_api::values::{BittrexOrderSide, BittrexOrderKind, BittrexFillType, BittrexCondition, BittrexOpenOrder};
+
+#[test]
+fn should_deserialize_order_kinds_successfully() {
+    // Act
+    let kinds: Vec<BittrexOrderKind> = serde_json::from_str(r#"["LIMIT_BUY", "LIMIT_SELL", "MARKET_SELL", "CONDITIONAL_BUY"]"#).unwrap();
+
+    // Assert
+    assert_eq!(kinds[0], BittrexOrderKind::LimitBuy);
+    assert_eq!(kinds[1], BittrexOrderKind::LimitSell);
+    assert_eq!(kinds[2], BittrexOrderKind::MarketSell);
+    assert_eq!(kinds[3], BittrexOrderKind::Unknown("CONDITIONAL_BUY".to_string()));
+}
+
+#[test]
+fn should_classify_order_kinds_successfully() {
+    // Assert
+    assert_eq!(BittrexOrderKind::LimitBuy.side(), BittrexOrderSide::Buy);
+    assert_eq!(BittrexOrderKind::MarketSell.side(), BittrexOrderSide::Sell);
+    assert_eq!(BittrexOrderKind::from("CONDITIONAL_SELL").side(), BittrexOrderSide::Sell);
+    assert_eq!(BittrexOrderKind::from("SWAP").side(), BittrexOrderSide::Unknown("SWAP".to_string()));
+    assert!(BittrexOrderKind::LimitSell.is_limit());
+    assert!(!BittrexOrderKind::MarketBuy.is_limit());
+}
+
+#[test]
+fn should_deserialize_fill_types_and_conditions_successfully() {
+    // Act
+    let fill_types: Vec<BittrexFillType> = serde_json::from_str(r#"["FILL", "PARTIAL_FILL"]"#).unwrap();
+    let conditions: Vec<Option<BittrexCondition>> = serde_json::from_str(r#"["NONE", "GREATER_THAN", "STOP_LOSS_PERCENTAGE", null, "TRAILING"]"#).unwrap();
+
+    // Assert
+    assert_eq!(fill_types, vec![BittrexFillType::Fill, BittrexFillType::PartialFill]);
+    assert_eq!(conditions[0], Some(BittrexCondition::None));
+    assert_eq!(conditions[1], Some(BittrexCondition::GreaterThan));
+    assert_eq!(conditions[2], Some(BittrexCondition::StopLossPercentage));
+    assert_eq!(conditions[3], None);
+    assert_eq!(conditions[4], Some(BittrexCondition::Unknown("TRAILING".to_string())));
+}
+
+#[test]
+fn should_serialize_enums_as_api_strings() {
+    // Assert
+    assert_eq!(serde_json::to_string(&BittrexOrderSide::Sell).unwrap(), r#""SELL""#);
+    assert_eq!(serde_json::to_string(&BittrexOrderKind::Unknown("FOO".to_string())).unwrap(), r#""FOO""#);
+    assert_eq!(BittrexCondition::LessThan.to_string(), "LESS_THAN");
+}
+
+#[test]
+fn should_deserialize_conditional_target_as_decimal_successfully() {
+    // Act
+    let order: BittrexOpenOrder = serde_json::from_str(r#"{
+        "Uuid" : null,
+        "OrderUuid" : "09aa5bb6-8232-41aa-9b78-a5a1093e0211",
+        "Exchange" : "BTC-LTC",
+        "OrderType" : "LIMIT_SELL",
+        "Quantity" : 5.00000000,
+        "QuantityRemaining" : 5.00000000,
+        "Limit" : 0.00012000,
+        "CommissionPaid" : 0.00000000,
+        "Price" : 0.00000000,
+        "PricePerUnit" : null,
+        "Opened" : "2014-07-09T03:55:48.77",
+        "Closed" : null,
+        "CancelInitiated" : false,
+        "ImmediateOrCancel" : false,
+        "IsConditional" : true,
+        "Condition" : "LESS_THAN",
+        "ConditionalTarget" : 0.00012345
+    }"#).unwrap();
+
+    // Assert
+    assert_eq!(order.condition, Some(BittrexCondition::LessThan));
+    assert_eq!(order.conditional_target, Some(Decimal::new(12345, 8)));
+}